@@ -1,3 +1,6 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
 use super::utils::tilde_expand;
 use crate::shared::env::ENV;
 
@@ -27,6 +30,39 @@ impl From<String> for MpdPassword {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MpdProfileFile {
+    pub name: String,
+    pub address: String,
+    #[serde(default)]
+    pub password: Option<String>,
+}
+
+/// Named MPD server which can be connected to instead of the default address,
+/// either on startup with `--profile` or at runtime from the profiles modal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MpdProfile {
+    pub name: String,
+    pub address: MpdAddress,
+    pub password: Option<MpdPassword>,
+}
+
+impl From<MpdProfileFile> for MpdProfile {
+    fn from(value: MpdProfileFile) -> Self {
+        let (address, password) = MpdAddress::resolve_config(value.address, value.password);
+        Self { name: value.name, address, password }
+    }
+}
+
+impl MpdProfile {
+    pub fn find<'a>(profiles: &'a [MpdProfile], name: &str) -> Result<&'a MpdProfile> {
+        profiles
+            .iter()
+            .find(|profile| profile.name == name)
+            .with_context(|| format!("Profile '{name}' was not found in the config"))
+    }
+}
+
 impl Default for MpdAddress {
     fn default() -> Self {
         Self::IpAndPort("127.0.0.1:6600".to_string())
@@ -34,9 +70,13 @@ impl Default for MpdAddress {
 }
 
 impl MpdAddress {
+    /// Address given on the command line takes precedence over the selected
+    /// profile, which in turn takes precedence over `MPD_HOST` and the
+    /// address in the config
     pub fn resolve(
         addr_from_cli: Option<String>,
         pw_from_cli: Option<String>,
+        profile: Option<&MpdProfile>,
         addr_from_config: String,
         pw_from_config: Option<String>,
     ) -> (MpdAddress, Option<MpdPassword>) {
@@ -48,6 +88,10 @@ impl MpdAddress {
             return (cli_addr, cli_pw);
         }
 
+        if let Some(profile) = profile {
+            return (profile.address.clone(), cli_pw.or_else(|| profile.password.clone()));
+        }
+
         if let Some(env) = env {
             return env;
         }
@@ -134,7 +178,7 @@ mod tests {
 
     use test_case::test_case;
     use crate::shared::env::ENV;
-    use super::{MpdAddress, MpdPassword};
+    use super::{MpdAddress, MpdPassword, MpdProfile, MpdProfileFile};
 
     static TEST_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
 
@@ -177,12 +221,48 @@ mod tests {
             ENV.set("MPD_PORT".to_string(), port.to_string());
         }
 
-        let result = MpdAddress::resolve(cli_addr.map(|v| v.to_string()), cli_pw.map(|v| v.to_string()), config_addr.to_string(), config_pw.map(|v| v.to_string()));
+        let result = MpdAddress::resolve(cli_addr.map(|v| v.to_string()), cli_pw.map(|v| v.to_string()), None, config_addr.to_string(), config_pw.map(|v| v.to_string()));
 
         assert_eq!(result.0, expected_addr);
         assert_eq!(result.1, expected_pw);
     }
 
+    //               CLI Arg              Cli Pass           MPD_HOST                    Expected                                            Description
+    #[test_case(Some("127.0.0.1:6600"),           None, Some("192.168.0.1"), MpdAddress::IpAndPort("127.0.0.1:6600".to_string()),          None ; "prefer CLI over profile")]
+    #[test_case(                  None,           None, Some("192.168.0.1"), MpdAddress::IpAndPort("10.0.0.5:6600".to_string()), Some("pi".into()) ; "prefer profile over ENV")]
+    #[test_case(                  None, Some("secret"),                None, MpdAddress::IpAndPort("10.0.0.5:6600".to_string()), Some("secret".into()) ; "CLI password with profile")]
+    fn resolves_profile(
+        cli_addr: Option<&str>,
+        cli_pw: Option<&str>,
+        host: Option<&str>,
+        expected_addr: MpdAddress,
+        expected_pw: Option<MpdPassword>
+    ) {
+        let _guard = TEST_LOCK.lock().unwrap();
+
+        ENV.clear();
+        if let Some(host) = host {
+            ENV.set("MPD_HOST".to_string(), host.to_string());
+        }
+        let profile: MpdProfile = MpdProfileFile { name: "pi".to_string(), address: "10.0.0.5:6600".to_string(), password: Some("pi".to_string()) }.into();
+
+        let result = MpdAddress::resolve(cli_addr.map(|v| v.to_string()), cli_pw.map(|v| v.to_string()), Some(&profile), "127.0.0.1:7600".to_string(), None);
+
+        assert_eq!(result.0, expected_addr);
+        assert_eq!(result.1, expected_pw);
+    }
+
+    #[test]
+    fn finds_profile_by_name() {
+        let profiles: Vec<MpdProfile> = vec![
+            MpdProfileFile { name: "desktop".to_string(), address: "~/.mpd/socket".to_string(), password: None }.into(),
+            MpdProfileFile { name: "nas".to_string(), address: "192.168.0.10:6600".to_string(), password: None }.into(),
+        ];
+
+        assert_eq!(MpdProfile::find(&profiles, "nas").unwrap().address, MpdAddress::IpAndPort("192.168.0.10:6600".to_string()));
+        assert!(MpdProfile::find(&profiles, "pi").is_err());
+    }
+
     #[test]
     fn password_is_obfuscated() {
         let pw: MpdPassword = "verysecretpassword".to_string().into();
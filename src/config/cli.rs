@@ -17,6 +17,13 @@ pub struct Args {
     #[arg(short, long)]
     /// Override the MPD password
     pub password: Option<String>,
+    #[arg(long)]
+    /// MPD partition to use instead of the default one
+    pub partition: Option<String>,
+    #[arg(long)]
+    /// Connect to the MPD server of the given profile from the config file.
+    /// Ignored when --address is given.
+    pub profile: Option<String>,
 }
 
 #[derive(Subcommand, Clone, Debug, PartialEq)]
@@ -102,6 +109,27 @@ pub enum Command {
     Single { value: OnOffOneshot },
     /// On, off or oneshot
     Consume { value: OnOffOneshot },
+    /// Sets crossfade between songs in seconds, relative if prefixed by + or -.
+    /// Prints current crossfade if no arguments is given.
+    Crossfade {
+        #[arg(allow_negative_numbers(true))]
+        value: Option<String>,
+    },
+    /// Sets mixramp overlap threshold and delay. Prints current values if no
+    /// arguments are given.
+    #[command(name = "mixramp")]
+    MixRamp {
+        /// Volume threshold in decibels, usually negative
+        #[arg(long, allow_negative_numbers(true))]
+        db: Option<f32>,
+        /// Delay in seconds, 'off' disables mixramp and falls back to
+        /// crossfade
+        #[arg(long)]
+        delay: Option<String>,
+    },
+    /// Sets replay gain mode. Prints current mode if no arguments is given.
+    #[command(name = "replaygain")]
+    ReplayGain { mode: Option<ReplayGainMode> },
     /// Seeks current song(seconds), relative if prefixed by + or -
     Seek {
         #[arg(allow_negative_numbers(true))]
@@ -150,8 +178,77 @@ pub enum Command {
         // Id of the output to disable
         id: u32,
     },
+    /// List, create and delete MPD partitions
+    Partition {
+        #[command(subcommand)]
+        cmd: PartitionCmd,
+    },
+    /// List, show and edit stored playlists
+    Playlist {
+        #[command(subcommand)]
+        cmd: PlaylistCmd,
+    },
+    /// Search the database and print found songs, or add them to the queue.
+    /// Case sensitivity and match mode default to the `search` section of the
+    /// config.
+    Search {
+        /// Match any tag
+        #[arg(long)]
+        any: Option<String>,
+        /// Match artist tag
+        #[arg(long)]
+        artist: Option<String>,
+        /// Match album tag
+        #[arg(long)]
+        album: Option<String>,
+        /// Match album artist tag
+        #[arg(long = "album-artist")]
+        album_artist: Option<String>,
+        /// Match title tag
+        #[arg(long)]
+        title: Option<String>,
+        /// Match genre tag
+        #[arg(long)]
+        genre: Option<String>,
+        /// Other tag in `tag=value` format, ie. `date=2020`. Can be given
+        /// multiple times.
+        #[arg(long)]
+        tag: Vec<String>,
+        /// Filter expression in MPD's syntax, ie. "(artist == 'Foo')". Can be
+        /// given multiple times, all filters have to match.
+        #[arg(long)]
+        filter: Vec<String>,
+        /// How the tag values are matched
+        #[arg(short, long)]
+        mode: Option<SearchMode>,
+        /// Match tag values case sensitively
+        #[arg(long = "case-sensitive", conflicts_with = "ignore_case")]
+        case_sensitive: bool,
+        /// Match tag values case insensitively
+        #[arg(long = "ignore-case")]
+        ignore_case: bool,
+        /// Add found songs to the end of the queue instead of printing them
+        #[arg(long, conflicts_with = "insert_next")]
+        add: bool,
+        /// Add found songs after the current song instead of printing them
+        #[arg(long = "insert-next")]
+        insert_next: bool,
+    },
+    /// List and edit songs in the current queue
+    Queue {
+        #[command(subcommand)]
+        cmd: QueueCmd,
+    },
     /// List MPD decoder plugins
     Decoders,
+    /// Prints statistics of the music library like the number of songs and
+    /// their total playtime
+    Stats {
+        /// Instead prints the number of songs and their playtime for each
+        /// value of the given tag, ie. Genre or Artist
+        #[arg(short, long)]
+        group: Option<String>,
+    },
     /// Prints various information like the playback status
     Status,
     /// Prints info about the current song.
@@ -168,6 +265,47 @@ pub enum Command {
     Unmount { name: String },
     /// List currently mounted storages
     ListMounts,
+    /// List storages discovered on the network by MPD's neighbor plugins
+    ListNeighbors,
+    /// Rate the currently playing song. The rating is stored in the sticker
+    /// configured in the `rating` section of the config.
+    Rate {
+        /// Rating from 0 to the configured maximum
+        #[arg(required_unless_present = "like", conflicts_with = "like")]
+        value: Option<u8>,
+        /// Like the song or remove the like if it is already liked
+        #[arg(long)]
+        like: bool,
+    },
+    /// Send a message to an MPD channel. Other clients subscribed to the
+    /// channel, including running rmpc instances, will receive it.
+    SendMessage { channel: String, message: String },
+    /// List MPD channels with at least one subscribed client
+    Channels,
+    /// Set the priority of songs in the queue. Songs with higher priority are
+    /// played first in random mode. Changes the currently playing song if
+    /// neither --id nor --range is given.
+    Priority {
+        /// Priority from 0 to 255, 0 is the default
+        value: u8,
+        /// Id of the song in the queue, can be given multiple times
+        #[arg(long, conflicts_with = "range")]
+        id: Vec<u32>,
+        /// Range of queue positions like `5:10`, end is exclusive. A single
+        /// position like `5` is also accepted.
+        #[arg(long)]
+        range: Option<String>,
+    },
+    /// Set the portion of a song in the queue which will be played. Changes
+    /// the currently playing song if --id is not given.
+    Range {
+        /// Range in seconds like `10:60.5`, both ends are optional. An empty
+        /// range like `:` plays the whole song again.
+        range: String,
+        /// Id of the song in the queue
+        #[arg(long)]
+        id: Option<u32>,
+    },
     /// Manipulate and query song stickers
     Sticker {
         #[command(subcommand)]
@@ -209,6 +347,48 @@ pub enum RemoteCmd {
         #[command(subcommand)]
         command: SetCommand,
     },
+    /// Queries the state of a running rmpc instance, the result is printed as
+    /// JSON
+    Get {
+        #[command(subcommand)]
+        command: GetCommand,
+    },
+    /// Simulates a key press in a running rmpc instance
+    Keybind {
+        /// Key in the same format as in the keybinds config, ie. "p" or
+        /// "<C-d>"
+        key: String,
+    },
+    /// Triggers an action in a running rmpc instance as if its key was pressed
+    Action {
+        /// Action in the same format as in the keybinds config, ie.
+        /// `TogglePause` or `SwitchToTab("Queue")`
+        action: String,
+        /// Keybinds section of the action. If not provided, global, navigation,
+        /// queue and playlists actions are tried in this order.
+        #[arg(short, long)]
+        section: Option<ActionSection>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ActionSection {
+    Global,
+    Navigation,
+    Queue,
+    Playlists,
+}
+
+#[derive(Subcommand, Clone, Debug, PartialEq)]
+#[clap(rename_all = "kebab-case")]
+pub enum GetCommand {
+    /// Name of the currently active tab
+    ActiveTab,
+    /// Songs marked in the focused pane or the song under cursor if nothing
+    /// is marked
+    SelectedSongs,
+    /// Type of the currently focused pane
+    FocusedPane,
 }
 
 #[derive(Subcommand, Clone, Debug, PartialEq)]
@@ -284,6 +464,209 @@ pub enum StickerCmd {
     },
 }
 
+#[derive(Subcommand, Clone, Debug, PartialEq)]
+#[clap(rename_all = "lower")]
+pub enum PartitionCmd {
+    /// List all partitions
+    List,
+    /// Create a new partition
+    New {
+        /// Name of the partition to create
+        name: String,
+    },
+    /// Delete a partition. The partition must not be in use by any client.
+    Delete {
+        /// Name of the partition to delete
+        name: String,
+    },
+    /// Move an output to a partition
+    MoveOutput {
+        /// Name of the output to move
+        output: String,
+        /// Name of the partition the output will be moved to
+        partition: String,
+    },
+}
+
+#[derive(Subcommand, Clone, Debug, PartialEq)]
+#[clap(rename_all = "lower")]
+pub enum QueueCmd {
+    /// List songs in the queue
+    List,
+    /// Play song with the given id
+    Play {
+        /// Id of the song in the queue
+        id: u32,
+    },
+    /// Remove songs from the queue
+    Remove {
+        /// Positions like `5` or ranges of positions like `5:10`, end is
+        /// exclusive
+        #[arg(required = true)]
+        songs: Vec<String>,
+        /// Treat the values as song ids instead of positions
+        #[arg(long)]
+        id: bool,
+    },
+    /// Move songs within the queue
+    Move {
+        /// Position like `5` or range of positions like `5:10`, end is
+        /// exclusive
+        from: String,
+        /// New position of the songs, relative to the current song if
+        /// prefixed by + or -
+        #[arg(allow_negative_numbers(true))]
+        to: String,
+        /// Treat `from` as song id instead of position
+        #[arg(long)]
+        id: bool,
+    },
+    /// Swap two songs in the queue
+    Swap {
+        /// Position of the first song
+        first: u32,
+        /// Position of the second song
+        second: u32,
+        /// Treat the values as song ids instead of positions
+        #[arg(long)]
+        id: bool,
+    },
+    /// Shuffle the whole queue or only the songs in the given range
+    Shuffle {
+        /// Range of positions like `5:10`, end is exclusive
+        range: Option<String>,
+    },
+    /// Remove all songs except the currently playing one
+    Crop,
+}
+
+#[derive(Subcommand, Clone, Debug, PartialEq)]
+#[clap(rename_all = "lower")]
+pub enum PlaylistCmd {
+    /// List all stored playlists
+    List,
+    /// List songs in a stored playlist
+    Show {
+        /// Name of the stored playlist
+        name: String,
+    },
+    /// Add songs of a stored playlist to the end of the queue
+    Load {
+        /// Name of the stored playlist
+        name: String,
+    },
+    /// Save the current queue as a stored playlist
+    Save {
+        /// Name of the stored playlist
+        name: String,
+        /// Whether to create a new playlist, append to an existing one or
+        /// replace it. Append and replace require MPD 0.24.0 or newer.
+        #[arg(short, long)]
+        mode: Option<PlaylistSaveMode>,
+    },
+    /// Rename a stored playlist
+    Rename {
+        /// Current name of the stored playlist
+        name: String,
+        /// New name of the stored playlist
+        new_name: String,
+    },
+    /// Delete a stored playlist
+    Delete {
+        /// Name of the stored playlist
+        name: String,
+    },
+    /// Add songs to a stored playlist. The playlist is created if it does not
+    /// exist.
+    Add {
+        /// Name of the stored playlist
+        name: String,
+        /// Paths to songs, relative to music directory root, or URLs
+        #[arg(required = true)]
+        uris: Vec<String>,
+        /// Position in the playlist to insert the songs at, appended to the
+        /// end if not given
+        #[arg(short, long)]
+        position: Option<usize>,
+    },
+    /// Remove songs from a stored playlist
+    Remove {
+        /// Name of the stored playlist
+        name: String,
+        /// Position like `5` or range of positions like `5:10`, end is
+        /// exclusive
+        range: String,
+    },
+    /// Move songs within a stored playlist
+    Move {
+        /// Name of the stored playlist
+        name: String,
+        /// Position like `5` or range of positions like `5:10`, end is
+        /// exclusive
+        range: String,
+        /// New position of the songs
+        to: usize,
+    },
+    /// Write a stored playlist to a playlist file. Prints the playlist if no
+    /// output file is given.
+    Export {
+        /// Name of the stored playlist
+        name: String,
+        /// Format of the playlist file. Guessed from the output file's
+        /// extension if not given, m3u8 otherwise.
+        #[arg(short, long)]
+        format: Option<PlaylistFileFormat>,
+        /// File to write the playlist to
+        #[arg(short, long, value_hint = ValueHint::FilePath)]
+        output: Option<PathBuf>,
+        /// Write absolute paths instead of paths relative to the music
+        /// directory. Requires socket connection to MPD.
+        #[arg(long)]
+        absolute: bool,
+    },
+    /// Create a stored playlist from a m3u, m3u8, pls or xspf file. Paths are
+    /// resolved relative to the music directory, songs which are not found
+    /// are looked up by their title, artist and album.
+    Import {
+        /// Playlist file to import
+        #[arg(value_hint = ValueHint::FilePath)]
+        file: PathBuf,
+        /// Name of the created playlist, defaults to the file's name
+        #[arg(short, long)]
+        name: Option<String>,
+    },
+}
+
+#[derive(Parser, ValueEnum, Copy, Clone, Debug, PartialEq)]
+pub enum SearchMode {
+    /// Value has to be equal to the tag
+    Exact,
+    /// Tag has to start with the value
+    StartsWith,
+    /// Tag has to contain the value
+    Contains,
+    /// Value is a regular expression
+    Regex,
+}
+
+#[derive(Parser, ValueEnum, Copy, Clone, Debug, PartialEq)]
+pub enum PlaylistSaveMode {
+    /// Fail if the playlist already exists
+    Create,
+    /// Add the queue to the end of the existing playlist
+    Append,
+    /// Overwrite the existing playlist
+    Replace,
+}
+
+#[derive(Parser, ValueEnum, Copy, Clone, Debug, PartialEq)]
+pub enum PlaylistFileFormat {
+    M3u,
+    M3u8,
+    Pls,
+    Xspf,
+}
+
 #[derive(Parser, ValueEnum, Copy, Clone, Debug, PartialEq)]
 pub enum OnOff {
     /// Enable
@@ -302,6 +685,18 @@ pub enum OnOffOneshot {
     Oneshot,
 }
 
+#[derive(Parser, ValueEnum, Copy, Clone, Debug, PartialEq)]
+pub enum ReplayGainMode {
+    /// Disable replay gain
+    Off,
+    /// Use track gain
+    Track,
+    /// Use album gain
+    Album,
+    /// Use album gain when playing in order, track gain otherwise
+    Auto,
+}
+
 fn get_default_config_path() -> PathBuf {
     let mut path = PathBuf::new();
     if let Ok(dir) = std::env::var("XDG_CONFIG_HOME") {
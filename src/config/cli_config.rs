@@ -3,7 +3,15 @@ use std::path::PathBuf;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
-use super::{Config, ConfigFile, MpdAddress, address::MpdPassword, utils::tilde_expand};
+use super::{
+    Config,
+    ConfigFile,
+    MpdAddress,
+    address::{MpdPassword, MpdProfile, MpdProfileFile},
+    rating::{Rating, RatingFile},
+    search::{Search, SearchFile},
+    utils::tilde_expand,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct CliConfigFile {
@@ -12,9 +20,15 @@ pub struct CliConfigFile {
     #[serde(default)]
     password: Option<String>,
     #[serde(default)]
+    profiles: Vec<MpdProfileFile>,
+    #[serde(default)]
     cache_dir: Option<String>,
     #[serde(default)]
     lyrics_dir: Option<String>,
+    #[serde(default)]
+    rating: RatingFile,
+    #[serde(default)]
+    search: SearchFile,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -23,6 +37,8 @@ pub struct CliConfig {
     pub password: Option<MpdPassword>,
     pub cache_dir: Option<String>,
     pub lyrics_dir: Option<String>,
+    pub rating: Rating,
+    pub search: Search,
 }
 
 impl From<ConfigFile> for CliConfigFile {
@@ -30,8 +46,11 @@ impl From<ConfigFile> for CliConfigFile {
         Self {
             address: value.address,
             password: value.password,
+            profiles: value.profiles,
             cache_dir: value.cache_dir,
             lyrics_dir: value.lyrics_dir,
+            rating: value.rating,
+            search: value.search,
         }
     }
 }
@@ -43,6 +62,8 @@ impl From<Config> for CliConfig {
             password: value.password,
             cache_dir: value.cache_dir,
             lyrics_dir: value.lyrics_dir,
+            rating: value.rating,
+            search: value.search,
         }
     }
 }
@@ -54,6 +75,8 @@ impl From<&Config> for CliConfig {
             password: value.password.clone(),
             cache_dir: value.cache_dir.clone(),
             lyrics_dir: value.lyrics_dir.clone(),
+            rating: value.rating.clone(),
+            search: value.search.clone(),
         }
     }
 }
@@ -71,11 +94,14 @@ impl CliConfigFile {
         self,
         address_cli: Option<String>,
         password_cli: Option<String>,
-    ) -> CliConfig {
+        profile_cli: Option<&str>,
+    ) -> Result<CliConfig> {
+        let profiles: Vec<MpdProfile> = self.profiles.into_iter().map(Into::into).collect();
+        let profile = profile_cli.map(|name| MpdProfile::find(&profiles, name)).transpose()?;
         let (address, password) =
-            MpdAddress::resolve(address_cli, password_cli, self.address, self.password);
+            MpdAddress::resolve(address_cli, password_cli, profile, self.address, self.password);
 
-        CliConfig {
+        Ok(CliConfig {
             cache_dir: self.cache_dir.map(|v| if v.ends_with('/') { v } else { format!("{v}/") }),
             lyrics_dir: self.lyrics_dir.map(|v| {
                 let v = tilde_expand(&v);
@@ -83,6 +109,8 @@ impl CliConfigFile {
             }),
             address,
             password,
-        }
+            rating: self.rating.into(),
+            search: self.search.into(),
+        })
     }
 }
@@ -1,4 +1,6 @@
 #![allow(dead_code)]
+use super::{queue_sort::QueueSortFile, search::FilterKindFile};
+
 pub fn default_column_widths() -> Vec<u16> {
     vec![20, 38, 42]
 }
@@ -11,6 +13,38 @@ pub fn default_true() -> bool {
     true
 }
 
+pub fn default_tag_separator() -> String {
+    " | ".to_string()
+}
+
+pub fn default_rating_sticker() -> String {
+    "rating".to_string()
+}
+
+pub fn default_rating_max() -> u8 {
+    10
+}
+
+pub fn default_like_sticker() -> String {
+    "like".to_string()
+}
+
+pub fn default_reconnect_initial_delay_ms() -> u64 {
+    1000
+}
+
+pub fn default_reconnect_max_delay_ms() -> u64 {
+    30_000
+}
+
+pub fn default_connected_label() -> String {
+    "Connected".to_string()
+}
+
+pub fn default_reconnecting_label() -> String {
+    "Reconnecting".to_string()
+}
+
 pub fn default_volume_step() -> u8 {
     5
 }
@@ -31,6 +65,10 @@ pub fn default_write_timeout() -> u64 {
     5000
 }
 
+pub fn default_key_sequence_timeout() -> u64 {
+    1000
+}
+
 #[allow(clippy::unnecessary_wraps)]
 pub fn default_progress_update_interval_ms() -> Option<u64> {
     Some(1000)
@@ -64,6 +102,22 @@ pub fn default_stopped_label() -> String {
     "Stopped".to_string()
 }
 
+pub fn default_full_star() -> String {
+    "★".to_string()
+}
+
+pub fn default_half_star() -> String {
+    "⯪".to_string()
+}
+
+pub fn default_empty_star() -> String {
+    "☆".to_string()
+}
+
+pub fn default_last_played_format() -> String {
+    "%Y-%m-%d %H:%M".to_string()
+}
+
 pub fn default_on_label() -> String {
     "On".to_string()
 }
@@ -75,3 +129,11 @@ pub fn default_off_label() -> String {
 pub fn default_oneshot_label() -> String {
     "OS".to_string()
 }
+
+pub fn default_smart_playlist_filter_kind() -> FilterKindFile {
+    FilterKindFile::Exact
+}
+
+pub fn default_queue_sort() -> Vec<QueueSortFile> {
+    QueueSortFile::default_presets()
+}
@@ -15,6 +15,11 @@ pub enum GlobalAction {
     ShowCurrentSongInfo,
     ShowOutputs,
     ShowDecoders,
+    ShowPartitions,
+    ShowMounts,
+    ShowProfiles,
+    Reconnect,
+    ShowStats,
     NextTrack,
     PreviousTrack,
     Stop,
@@ -25,6 +30,11 @@ pub enum GlobalAction {
     TogglePause,
     VolumeUp,
     VolumeDown,
+    CrossfadeUp,
+    CrossfadeDown,
+    CycleReplayGain,
+    Undo,
+    Redo,
     SeekForward,
     SeekBack,
     CommandMode,
@@ -44,6 +54,11 @@ pub enum GlobalActionFile {
     ShowCurrentSongInfo,
     ShowOutputs,
     ShowDecoders,
+    ShowPartitions,
+    ShowMounts,
+    ShowProfiles,
+    Reconnect,
+    ShowStats,
     NextTrack,
     PreviousTrack,
     Stop,
@@ -54,6 +69,11 @@ pub enum GlobalActionFile {
     TogglePause,
     VolumeUp,
     VolumeDown,
+    CrossfadeUp,
+    CrossfadeDown,
+    CycleReplayGain,
+    Undo,
+    Redo,
     SeekForward,
     SeekBack,
     NextTab,
@@ -76,6 +96,11 @@ impl From<GlobalActionFile> for GlobalAction {
             GlobalActionFile::Quit => GlobalAction::Quit,
             GlobalActionFile::ShowOutputs => GlobalAction::ShowOutputs,
             GlobalActionFile::ShowDecoders => GlobalAction::ShowDecoders,
+            GlobalActionFile::ShowPartitions => GlobalAction::ShowPartitions,
+            GlobalActionFile::ShowMounts => GlobalAction::ShowMounts,
+            GlobalActionFile::ShowProfiles => GlobalAction::ShowProfiles,
+            GlobalActionFile::Reconnect => GlobalAction::Reconnect,
+            GlobalActionFile::ShowStats => GlobalAction::ShowStats,
             GlobalActionFile::ShowCurrentSongInfo => GlobalAction::ShowCurrentSongInfo,
             GlobalActionFile::CommandMode => GlobalAction::CommandMode,
             GlobalActionFile::Command { command, description } => {
@@ -93,6 +118,11 @@ impl From<GlobalActionFile> for GlobalAction {
             GlobalActionFile::SeekBack => GlobalAction::SeekBack,
             GlobalActionFile::VolumeDown => GlobalAction::VolumeDown,
             GlobalActionFile::VolumeUp => GlobalAction::VolumeUp,
+            GlobalActionFile::CrossfadeUp => GlobalAction::CrossfadeUp,
+            GlobalActionFile::CrossfadeDown => GlobalAction::CrossfadeDown,
+            GlobalActionFile::CycleReplayGain => GlobalAction::CycleReplayGain,
+            GlobalActionFile::Undo => GlobalAction::Undo,
+            GlobalActionFile::Redo => GlobalAction::Redo,
             GlobalActionFile::PreviousTab => GlobalAction::PreviousTab,
             GlobalActionFile::NextTab => GlobalAction::NextTab,
             GlobalActionFile::ToggleConsume => GlobalAction::ToggleConsume,
@@ -121,6 +151,11 @@ impl ToDescription for GlobalAction {
             GlobalAction::Quit => "Exit rmpc".into(),
             GlobalAction::ShowOutputs => "Show MPD outputs config".into(),
             GlobalAction::ShowDecoders => "Show MPD decoder plugins".into(),
+            GlobalAction::ShowPartitions => "Show MPD partitions".into(),
+            GlobalAction::ShowMounts => "Show MPD mounts and neighbors".into(),
+            GlobalAction::ShowProfiles => "Show MPD server profiles".into(),
+            GlobalAction::Reconnect => "Try to reconnect to MPD right away".into(),
+            GlobalAction::ShowStats => "Show library statistics".into(),
             GlobalAction::ShowCurrentSongInfo => {
                 "Show metadata of the currently playing song in a modal popup".into()
             }
@@ -134,6 +169,13 @@ impl ToDescription for GlobalAction {
             GlobalAction::Stop => "Stop playback".into(),
             GlobalAction::VolumeUp => "Raise volume".into(),
             GlobalAction::VolumeDown => "Lower volume".into(),
+            GlobalAction::CrossfadeUp => "Increase crossfade by one second".into(),
+            GlobalAction::CrossfadeDown => "Decrease crossfade by one second".into(),
+            GlobalAction::CycleReplayGain => {
+                "Cycle ReplayGain mode between off, track, album and auto".into()
+            }
+            GlobalAction::Undo => "Undo the last change of the queue made in rmpc".into(),
+            GlobalAction::Redo => "Redo the last undone change of the queue".into(),
             GlobalAction::NextTrack => "Play next track in the queue".into(),
             GlobalAction::PreviousTrack => "Play previous track in the queue".into(),
             GlobalAction::SeekForward => "Seek currently playing track forwards".into(),
@@ -261,6 +303,11 @@ pub enum QueueActionsFile {
     AddToPlaylist,
     ShowInfo,
     JumpToCurrent,
+    SortBy,
+    Shuffle,
+    PriorityUp,
+    PriorityDown,
+    SetRange,
 }
 
 #[derive(Debug, Display, PartialEq, Eq, Hash, Clone, Copy)]
@@ -272,6 +319,11 @@ pub enum QueueActions {
     AddToPlaylist,
     ShowInfo,
     JumpToCurrent,
+    SortBy,
+    Shuffle,
+    PriorityUp,
+    PriorityDown,
+    SetRange,
 }
 
 impl From<QueueActionsFile> for QueueActions {
@@ -284,6 +336,11 @@ impl From<QueueActionsFile> for QueueActions {
             QueueActionsFile::AddToPlaylist => QueueActions::AddToPlaylist,
             QueueActionsFile::ShowInfo => QueueActions::ShowInfo,
             QueueActionsFile::JumpToCurrent => QueueActions::JumpToCurrent,
+            QueueActionsFile::SortBy => QueueActions::SortBy,
+            QueueActionsFile::Shuffle => QueueActions::Shuffle,
+            QueueActionsFile::PriorityUp => QueueActions::PriorityUp,
+            QueueActionsFile::PriorityDown => QueueActions::PriorityDown,
+            QueueActionsFile::SetRange => QueueActions::SetRange,
         }
     }
 }
@@ -300,6 +357,11 @@ impl ToDescription for QueueActions {
             QueueActions::JumpToCurrent => {
                 "Moves the cursor in Queue table to the currently playing song"
             }
+            QueueActions::SortBy => "Sort marked songs or the whole queue by the selected keys",
+            QueueActions::Shuffle => "Shuffle queue, upcoming or marked songs, or whole albums",
+            QueueActions::PriorityUp => "Raise priority of marked songs or song under cursor",
+            QueueActions::PriorityDown => "Lower priority of marked songs or song under cursor",
+            QueueActions::SetRange => "Set the played portion of marked songs or song under cursor",
         }
         .into()
     }
@@ -341,6 +403,10 @@ pub enum CommonActionFile {
     Confirm,
     FocusInput,
     AddAll,
+    Rate(u8),
+    RateUp,
+    RateDown,
+    ToggleLike,
 }
 
 #[derive(Debug, Display, PartialEq, Eq, Hash, Clone, Copy)]
@@ -375,6 +441,10 @@ pub enum CommonAction {
     Confirm,
     FocusInput,
     AddAll,
+    Rate(u8),
+    RateUp,
+    RateDown,
+    ToggleLike,
 }
 
 impl ToDescription for CommonAction {
@@ -420,6 +490,10 @@ impl ToDescription for CommonAction {
             CommonAction::PaneUp => "Focus the pane above the current one",
             CommonAction::PaneRight => "Focus the pane to the right of the current one",
             CommonAction::PaneLeft => "Focus the pane to the left of the current one",
+            CommonAction::Rate(_) => "Set rating of the selected songs to the given value",
+            CommonAction::RateUp => "Increase rating of the selected songs by one",
+            CommonAction::RateDown => "Decrease rating of the selected songs by one",
+            CommonAction::ToggleLike => "Like or unlike the selected songs",
         }.into()
     }
 }
@@ -457,6 +531,10 @@ impl From<CommonActionFile> for CommonAction {
             CommonActionFile::PaneDown => CommonAction::PaneDown,
             CommonActionFile::PaneLeft => CommonAction::PaneLeft,
             CommonActionFile::PaneRight => CommonAction::PaneRight,
+            CommonActionFile::Rate(value) => CommonAction::Rate(value),
+            CommonActionFile::RateUp => CommonAction::RateUp,
+            CommonActionFile::RateDown => CommonAction::RateDown,
+            CommonActionFile::ToggleLike => CommonAction::ToggleLike,
         }
     }
 }
@@ -464,20 +542,37 @@ impl From<CommonActionFile> for CommonAction {
 // Playlist actions
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash, Clone)]
-pub enum PlaylistsActionsFile {}
+pub enum PlaylistsActionsFile {
+    ExportPlaylist,
+    ImportPlaylist,
+}
 
 #[derive(Debug, Display, PartialEq, Eq, Hash, Clone, Copy)]
-pub enum PlaylistsActions {}
+pub enum PlaylistsActions {
+    ExportPlaylist,
+    ImportPlaylist,
+}
 
 impl ToDescription for PlaylistsActions {
     fn to_description(&self) -> Cow<'static, str> {
-        "".into()
+        match self {
+            PlaylistsActions::ExportPlaylist => {
+                "Export playlist under cursor to a m3u, m3u8, pls or xspf file"
+            }
+            PlaylistsActions::ImportPlaylist => {
+                "Create a new playlist from a m3u, m3u8, pls or xspf file"
+            }
+        }
+        .into()
     }
 }
 
 impl From<PlaylistsActionsFile> for PlaylistsActions {
-    fn from(_value: PlaylistsActionsFile) -> Self {
-        unreachable!()
+    fn from(value: PlaylistsActionsFile) -> Self {
+        match value {
+            PlaylistsActionsFile::ExportPlaylist => PlaylistsActions::ExportPlaylist,
+            PlaylistsActionsFile::ImportPlaylist => PlaylistsActions::ImportPlaylist,
+        }
     }
 }
 
@@ -183,6 +183,93 @@ impl FromStr for Key {
         Ok(Self { key, modifiers })
     }
 }
+
+/// One or more keys which have to be pressed one after another to trigger a
+/// keybind, ie. `gg` or `<Space>pa`
+#[derive(Debug, SerializeDisplay, DeserializeFromStr, PartialEq, Eq, Hash, Clone)]
+pub struct KeySequence(pub Vec<Key>);
+
+impl KeySequence {
+    /// True if `prefix` is a strict prefix of this sequence
+    pub fn continues(&self, prefix: &[Key]) -> bool {
+        self.0.len() > prefix.len() && self.0.starts_with(prefix)
+    }
+}
+
+impl From<Key> for KeySequence {
+    fn from(value: Key) -> Self {
+        Self(vec![value])
+    }
+}
+
+impl Display for KeySequence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for key in &self.0 {
+            write!(f, "{key}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for KeySequence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Single keys keep being parsed exactly as before, including the
+        // unbracketed forms like "C-a"
+        if let Some(key) = parse_single_key(s) {
+            return Ok(Self(vec![key]));
+        }
+
+        let chars = s.chars().collect_vec();
+        let mut keys = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let bracketed = (chars[i] == '<' && chars.get(i + 1).is_some_and(|c| c != &'<'))
+                .then(|| {
+                    (i + 2..chars.len()).filter(|j| chars[*j] == '>').find_map(|j| {
+                        chars[i..=j]
+                            .iter()
+                            .collect::<String>()
+                            .parse::<Key>()
+                            .ok()
+                            .filter(|key| key.key != KeyCode::Null)
+                            .map(|key| (key, j))
+                    })
+                })
+                .flatten();
+
+            if let Some((key, end)) = bracketed {
+                keys.push(key);
+                i = end + 1;
+            } else {
+                keys.push(chars[i].to_string().parse()?);
+                i += 1;
+            }
+        }
+
+        if keys.is_empty() {
+            return Err(format!("Invalid key sequence: '{s}'"));
+        }
+
+        Ok(Self(keys))
+    }
+}
+
+/// Parses `s` as a [`Key`] only if the whole input describes exactly one key.
+/// [`Key::from_str`] is lenient and would accept ie. "gg" as a single "g".
+fn parse_single_key(s: &str) -> Option<Key> {
+    let key = s.parse::<Key>().ok()?;
+    let inner = s.strip_prefix('<').and_then(|s| s.strip_suffix('>')).unwrap_or(s);
+    let mut key_part = inner;
+    while let Some(rest) = ["C-", "A-", "S-"].iter().find_map(|m| key_part.strip_prefix(m)) {
+        key_part = rest;
+    }
+
+    (key_part.chars().count() == 1 || key_part == "Space" || !matches!(key.key, KeyCode::Char(_)))
+        .then_some(key)
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -256,4 +343,35 @@ mod tests {
         let deserialized: Key = input.parse().unwrap();
         assert_eq!(deserialized, expected);
     }
+
+    #[rstest]
+    #[case("g",         &["g"])]
+    #[case("<C-a>",     &["<C-a>"])]
+    #[case("C-a",       &["<C-a>"])]
+    #[case("<",         &["<"])]
+    #[case("gg",        &["g", "g"])]
+    #[case("dd",        &["d", "d"])]
+    #[case("<Space>pa", &["<Space>", "p", "a"])]
+    #[case("g<C-a>",    &["g", "<C-a>"])]
+    #[case("<<C-a>",    &["<", "<C-a>"])]
+    #[case("<>",        &["<", ">"])]
+    #[case("<a",        &["<", "a"])]
+    #[case("<C-S->>g",  &["<C-S->>", "g"])]
+    #[case("Gg",        &["G", "g"])]
+    fn sequence_deserialization(#[case] input: &str, #[case] expected: &[&str]) {
+        let deserialized: KeySequence = input.parse().unwrap();
+        let expected = expected.iter().map(|key| key.parse::<Key>().unwrap()).collect_vec();
+        assert_eq!(deserialized, KeySequence(expected));
+    }
+
+    #[rstest]
+    #[case("g")]
+    #[case("<C-a>")]
+    #[case("gg")]
+    #[case("<Space>pa")]
+    #[case("g<Tab>G")]
+    fn sequence_serialization_round_trip(#[case] input: &str) {
+        let deserialized: KeySequence = input.parse().unwrap();
+        assert_eq!(deserialized.to_string(), input);
+    }
 }
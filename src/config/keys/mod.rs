@@ -5,15 +5,13 @@ pub use actions::LogsActions;
 #[cfg(debug_assertions)]
 use actions::LogsActionsFile;
 pub use actions::{
-    AlbumsActions, ArtistsActions, CommonAction, DirectoriesActions, GlobalAction,
-    PlaylistsActions, QueueActions, SearchActions,
-};
-use actions::{
-    AlbumsActionsFile, ArtistsActionsFile, CommonActionFile, DirectoriesActionsFile,
-    GlobalActionFile, PlaylistsActionsFile, QueueActionsFile,
+    AlbumsActions, ArtistsActions, CommonAction, CommonActionFile, DirectoriesActions,
+    GlobalAction, GlobalActionFile, PlaylistsActions, PlaylistsActionsFile, QueueActions,
+    QueueActionsFile, SearchActions,
 };
+use actions::{AlbumsActionsFile, ArtistsActionsFile, DirectoriesActionsFile};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-pub use key::Key;
+pub use key::{Key, KeySequence};
 use serde::{Deserialize, Serialize};
 
 mod actions;
@@ -21,34 +19,35 @@ mod key;
 
 #[derive(Debug, PartialEq, Default, Clone)]
 pub struct KeyConfig {
-    pub global: HashMap<Key, GlobalAction>,
-    pub navigation: HashMap<Key, CommonAction>,
-    pub albums: HashMap<Key, AlbumsActions>,
-    pub artists: HashMap<Key, ArtistsActions>,
-    pub directories: HashMap<Key, DirectoriesActions>,
-    pub playlists: HashMap<Key, PlaylistsActions>,
-    pub search: HashMap<Key, SearchActions>,
+    pub global: HashMap<KeySequence, GlobalAction>,
+    pub navigation: HashMap<KeySequence, CommonAction>,
+    pub albums: HashMap<KeySequence, AlbumsActions>,
+    pub artists: HashMap<KeySequence, ArtistsActions>,
+    pub directories: HashMap<KeySequence, DirectoriesActions>,
+    pub playlists: HashMap<KeySequence, PlaylistsActions>,
+    pub search: HashMap<KeySequence, SearchActions>,
     #[cfg(debug_assertions)]
-    pub logs: HashMap<Key, LogsActions>,
-    pub queue: HashMap<Key, QueueActions>,
+    pub logs: HashMap<KeySequence, LogsActions>,
+    pub queue: HashMap<KeySequence, QueueActions>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct KeyConfigFile {
     #[serde(default)]
-    pub global: HashMap<Key, GlobalActionFile>,
+    pub global: HashMap<KeySequence, GlobalActionFile>,
     #[serde(default)]
-    pub navigation: HashMap<Key, CommonActionFile>,
+    pub navigation: HashMap<KeySequence, CommonActionFile>,
     // pub albums: HashMap<AlbumsActions, Vec<Key>>,
     // pub artists: HashMap<ArtistsActions, Vec<Key>>,
     // pub directories: HashMap<DirectoriesActions, Vec<Key>>,
-    // pub playlists: HashMap<PlaylistsActions, Vec<Key>>,
+    #[serde(default)]
+    pub playlists: HashMap<KeySequence, PlaylistsActionsFile>,
     // pub search: HashMap<SearchActions, Vec<Key>>,
     #[cfg(debug_assertions)]
     #[serde(default)]
-    pub logs: HashMap<Key, LogsActionsFile>,
+    pub logs: HashMap<KeySequence, LogsActionsFile>,
     #[serde(default)]
-    pub queue: HashMap<Key, QueueActionsFile>,
+    pub queue: HashMap<KeySequence, QueueActionsFile>,
 }
 
 impl Default for KeyConfigFile {
@@ -67,7 +66,7 @@ impl Default for KeyConfigFile {
         use LogsActionsFile as L;
         use QueueActionsFile as Q;
         Self {
-            global: HashMap::from([
+            global: keymap([
                 (Key { key: K::Char('q'), modifiers: M::NONE  }, G::Quit),
                 (Key { key: K::Char(':'), modifiers: M::NONE  }, G::CommandMode),
                 (Key { key: K::Char('~'), modifiers: M::NONE  }, G::ShowHelp),
@@ -86,6 +85,8 @@ impl Default for KeyConfigFile {
                 (Key { key: K::Char('b'), modifiers: M::NONE  }, G::SeekBack),
                 (Key { key: K::Char(','), modifiers: M::NONE  }, G::VolumeDown),
                 (Key { key: K::Char('.'), modifiers: M::NONE  }, G::VolumeUp),
+                (Key { key: K::Char('u'), modifiers: M::NONE  }, G::Undo),
+                (Key { key: K::Char('r'), modifiers: M::CONTROL }, G::Redo),
                 (Key { key: K::BackTab,   modifiers: M::SHIFT }, G::PreviousTab),
                 (Key { key: K::Tab,       modifiers: M::NONE  }, G::NextTab),
                 (Key { key: K::Char('1'), modifiers: M::NONE  }, G::SwitchToTab("Queue".to_string())),
@@ -96,7 +97,7 @@ impl Default for KeyConfigFile {
                 (Key { key: K::Char('6'), modifiers: M::NONE  }, G::SwitchToTab("Playlists".to_string())),
                 (Key { key: K::Char('7'), modifiers: M::NONE  }, G::SwitchToTab("Search".to_string())),
             ]),
-            navigation: HashMap::from([
+            navigation: keymap([
                 (Key { key: K::Char('k'), modifiers: M::NONE    }, C::Up),
                 (Key { key: K::Char('j'), modifiers: M::NONE    }, C::Down),
                 (Key { key: K::Char('l'), modifiers: M::NONE    }, C::Right),
@@ -120,7 +121,7 @@ impl Default for KeyConfigFile {
                 (Key { key: K::Char('N'), modifiers: M::SHIFT   }, C::PreviousResult),
                 (Key { key: K::Char(' '), modifiers: M::NONE    }, C::Select),
                 (Key { key: K::Char(' '), modifiers: M::CONTROL }, C::InvertSelection),
-                (Key { key: K::Char('Z'), modifiers: M::NONE    }, C::AddNext),
+                (Key { key: K::Char('Z'), modifiers: M::SHIFT   }, C::AddNext),
                 (Key { key: K::Char('a'), modifiers: M::NONE    }, C::Add),
                 (Key { key: K::Char('A'), modifiers: M::SHIFT   }, C::AddAll),
                 (Key { key: K::Char('D'), modifiers: M::SHIFT   }, C::Delete),
@@ -130,20 +131,22 @@ impl Default for KeyConfigFile {
                 (Key { key: K::Enter,     modifiers: M::NONE    }, C::Confirm),
                 (Key { key: K::Char('i'), modifiers: M::NONE    }, C::FocusInput),
             ]),
-            // albums: HashMap::from([
-            // ]),
-            // artists: HashMap::from([
+            // albums: keymap([
             // ]),
-            // directories: HashMap::from([
+            // artists: keymap([
             // ]),
-            // playlists: HashMap::from([
+            // directories: keymap([
             // ]),
+            playlists: keymap([
+                (Key { key: K::Char('e'), modifiers: M::NONE    }, P::ExportPlaylist),
+                (Key { key: K::Char('E'), modifiers: M::SHIFT   }, P::ImportPlaylist),
+            ]),
             #[cfg(debug_assertions)]
-            logs: HashMap::from([
+            logs: keymap([
                 (Key { key: K::Char('D'), modifiers: M::SHIFT   }, L::Clear),
                 (Key { key: K::Char('S'), modifiers: M::SHIFT   }, L::ToggleScroll),
             ]),
-            queue: HashMap::from([
+            queue: keymap([
                 (Key { key: K::Char('d'), modifiers: M::NONE    }, Q::Delete),
                 (Key { key: K::Char('D'), modifiers: M::SHIFT   }, Q::DeleteAll),
                 (Key { key: K::Enter,     modifiers: M::NONE    }, Q::Play),
@@ -151,6 +154,11 @@ impl Default for KeyConfigFile {
                 (Key { key: K::Char('a'), modifiers: M::NONE    }, Q::AddToPlaylist),
                 (Key { key: K::Char('i'), modifiers: M::NONE    }, Q::ShowInfo),
                 (Key { key: K::Char('C'), modifiers: M::SHIFT   }, Q::JumpToCurrent),
+                (Key { key: K::Char('o'), modifiers: M::NONE    }, Q::SortBy),
+                (Key { key: K::Char('X'), modifiers: M::SHIFT   }, Q::Shuffle),
+                (Key { key: K::Char('+'), modifiers: M::NONE    }, Q::PriorityUp),
+                (Key { key: K::Char('-'), modifiers: M::NONE    }, Q::PriorityDown),
+                (Key { key: K::Char('R'), modifiers: M::SHIFT   }, Q::SetRange),
             ]),
         }
     }
@@ -164,11 +172,10 @@ impl From<KeyConfigFile> for KeyConfig {
             // albums: invert_map(value.albums),
             // artists: invert_map(value.artists),
             // directories: invert_map(value.directories),
-            // playlists: invert_map(value.playlists),
             albums: HashMap::new(),
             artists: HashMap::new(),
             directories: HashMap::new(),
-            playlists: HashMap::new(),
+            playlists: value.playlists.into_iter().map(|(k, v)| (k, v.into())).collect(),
             search: HashMap::new(),
             #[cfg(debug_assertions)]
             logs: value.logs.into_iter().map(|(k, v)| (k, v.into())).collect(),
@@ -183,23 +190,51 @@ impl From<KeyEvent> for Key {
     }
 }
 
+impl KeyConfig {
+    fn sequences(&self) -> impl Iterator<Item = &KeySequence> {
+        let global = self.global.keys();
+        let navigation = self.navigation.keys();
+        let queue = self.queue.keys();
+        let playlists = self.playlists.keys();
+        #[cfg(debug_assertions)]
+        let queue = queue.chain(self.logs.keys());
+
+        global.chain(navigation).chain(queue).chain(playlists)
+    }
+
+    /// True if any keybind continues with more keys after `prefix`
+    pub fn has_continuation(&self, prefix: &[Key]) -> bool {
+        self.sequences().any(|seq| seq.continues(prefix))
+    }
+
+    /// True if `keys` are bound to an action or start a longer keybind
+    pub fn is_bound(&self, keys: &[Key]) -> bool {
+        self.sequences().any(|seq| seq.0.starts_with(keys))
+    }
+}
+
+fn keymap<V, const N: usize>(binds: [(Key, V); N]) -> HashMap<KeySequence, V> {
+    binds.into_iter().map(|(key, value)| (key.into(), value)).collect()
+}
+
 pub trait ToDescription {
     fn to_description(&self) -> Cow<'static, str>;
 }
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
     use std::collections::HashMap;
 
     use crossterm::event::{KeyCode, KeyModifiers};
 
-    use super::{Key, KeyConfig, KeyConfigFile};
+    use super::{Key, KeyConfig, KeyConfigFile, KeySequence, keymap};
     #[cfg(debug_assertions)]
     use crate::config::keys::LogsActions;
     #[cfg(debug_assertions)]
     use crate::config::keys::LogsActionsFile;
     use crate::config::keys::{
-        CommonAction, GlobalAction, QueueActions,
+        CommonAction, GlobalAction, PlaylistsActions, PlaylistsActionsFile, QueueActions,
         actions::{CommonActionFile, GlobalActionFile, QueueActionsFile},
     };
 
@@ -207,33 +242,33 @@ mod tests {
     #[rustfmt::skip]
     fn converts() {
         let input = KeyConfigFile {
-            global: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, GlobalActionFile::Quit)]),
+            global: keymap([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, GlobalActionFile::Quit)]),
 
             #[cfg(debug_assertions)]
-            logs: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, LogsActionsFile::Clear)]),
-            queue: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, QueueActionsFile::Play),
+            logs: keymap([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, LogsActionsFile::Clear)]),
+            queue: keymap([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, QueueActionsFile::Play),
                                   (Key { key: KeyCode::Char('b'), modifiers: KeyModifiers::SHIFT, }, QueueActionsFile::Save)]),
-            // albums: HashMap::from([]),
-            // artists: HashMap::from([]),
-            // directories: HashMap::from([]),
-            // playlists: HashMap::from([]),
-            navigation: HashMap::from([
+            // albums: keymap([]),
+            // artists: keymap([]),
+            // directories: keymap([]),
+            playlists: keymap([(Key { key: KeyCode::Char('e'), modifiers: KeyModifiers::NONE, }, PlaylistsActionsFile::ExportPlaylist)]),
+            navigation: keymap([
                 (Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, CommonActionFile::Up),
                 (Key { key: KeyCode::Char('b'), modifiers: KeyModifiers::SHIFT }, CommonActionFile::Up)
             ])
         };
         let expected = KeyConfig {
-            global: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, GlobalAction::Quit)]),
+            global: keymap([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, GlobalAction::Quit)]),
             #[cfg(debug_assertions)]
-            logs: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, LogsActions::Clear)]),
-            queue: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, QueueActions::Play),
+            logs: keymap([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, LogsActions::Clear)]),
+            queue: keymap([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, QueueActions::Play),
                                   (Key { key: KeyCode::Char('b'), modifiers: KeyModifiers::SHIFT, }, QueueActions::Save)]),
-            albums: HashMap::from([]),
-            artists: HashMap::from([]),
-            directories: HashMap::from([]),
-            playlists: HashMap::from([]),
-            search: HashMap::from([]),
-            navigation: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL }, CommonAction::Up),
+            albums: keymap([]),
+            artists: keymap([]),
+            directories: keymap([]),
+            playlists: keymap([(Key { key: KeyCode::Char('e'), modifiers: KeyModifiers::NONE, }, PlaylistsActions::ExportPlaylist)]),
+            search: keymap([]),
+            navigation: keymap([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL }, CommonAction::Up),
                                        (Key { key: KeyCode::Char('b'), modifiers: KeyModifiers::SHIFT }, CommonAction::Up)]),
         };
 
@@ -242,4 +277,30 @@ mod tests {
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn has_continuation() {
+        let config = KeyConfig {
+            global: HashMap::from([("gt".parse().unwrap(), GlobalAction::NextTab)]),
+            navigation: HashMap::from([
+                ("g".parse().unwrap(), CommonAction::Top),
+                ("<Space>pa".parse().unwrap(), CommonAction::Add),
+            ]),
+            ..Default::default()
+        };
+        let keys = |input: &str| input.parse::<KeySequence>().unwrap().0;
+
+        assert!(config.has_continuation(&keys("g")));
+        assert!(config.has_continuation(&keys("<Space>")));
+        assert!(config.has_continuation(&keys("<Space>p")));
+        assert!(!config.has_continuation(&keys("gt")));
+        assert!(!config.has_continuation(&keys("<Space>pa")));
+        assert!(!config.has_continuation(&keys("x")));
+
+        assert!(config.is_bound(&keys("g")));
+        assert!(config.is_bound(&keys("gt")));
+        assert!(config.is_bound(&keys("<Space>")));
+        assert!(!config.is_bound(&keys("x")));
+        assert!(!config.is_bound(&keys("gx")));
+    }
 }
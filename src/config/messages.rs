@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    defaults,
+    keys::{GlobalAction, GlobalActionFile},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessagesConfig {
+    pub channels: Vec<String>,
+    pub show_in_status_bar: bool,
+    pub actions: HashMap<String, GlobalAction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MessagesConfigFile {
+    #[serde(default)]
+    pub channels: Vec<String>,
+    #[serde(default = "defaults::default_true")]
+    pub show_in_status_bar: bool,
+    #[serde(default)]
+    pub actions: HashMap<String, GlobalActionFile>,
+}
+
+impl Default for MessagesConfigFile {
+    fn default() -> Self {
+        Self { channels: Vec::new(), show_in_status_bar: true, actions: HashMap::new() }
+    }
+}
+
+impl Default for MessagesConfig {
+    fn default() -> Self {
+        MessagesConfigFile::default().into()
+    }
+}
+
+impl From<MessagesConfigFile> for MessagesConfig {
+    fn from(value: MessagesConfigFile) -> Self {
+        Self {
+            channels: value.channels,
+            show_in_status_bar: value.show_in_status_bar,
+            actions: value
+                .actions
+                .into_iter()
+                .map(|(message, action)| (message, action.into()))
+                .collect(),
+        }
+    }
+}
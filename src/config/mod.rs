@@ -5,16 +5,29 @@ use std::{
     time::Duration,
 };
 
-use address::MpdPassword;
+use address::{MpdPassword, MpdProfile, MpdProfileFile};
 use album_art::{AlbumArtConfig, AlbumArtConfigFile, ImageMethod, ImageMethodFile};
 use anyhow::{Context, Result};
 use artists::{Artists, ArtistsFile};
 use clap::Parser;
-use cli::{Args, OnOff, OnOffOneshot};
+use cli::{
+    Args,
+    OnOff,
+    OnOffOneshot,
+    PlaylistFileFormat,
+    PlaylistSaveMode,
+    ReplayGainMode,
+    SearchMode,
+};
 use itertools::Itertools;
+use messages::{MessagesConfig, MessagesConfigFile};
+use queue_sort::{QueueSort, QueueSortFile};
+use rating::{Rating, RatingFile};
+use reconnect::{Reconnect, ReconnectFile};
 use rustix::path::Arg;
 use search::SearchFile;
 use serde::{Deserialize, Serialize};
+use smart_playlist::{SmartPlaylist, SmartPlaylistFile};
 use tabs::{PaneType, Tabs, TabsFile, validate_tabs};
 use utils::tilde_expand;
 
@@ -25,7 +38,12 @@ pub mod cli;
 pub mod cli_config;
 mod defaults;
 pub mod keys;
+pub mod messages;
+pub mod queue_sort;
+pub mod rating;
+pub mod reconnect;
 mod search;
+pub mod smart_playlist;
 pub mod tabs;
 pub mod theme;
 
@@ -46,6 +64,10 @@ use crate::{
 pub struct Config {
     pub address: MpdAddress,
     pub password: Option<MpdPassword>,
+    pub profiles: Vec<MpdProfile>,
+    /// Name of the profile rmpc is connected to, `None` when connected to the
+    /// default address
+    pub active_profile: Option<String>,
     pub cache_dir: Option<String>,
     pub lyrics_dir: Option<String>,
     pub volume_step: u8,
@@ -57,8 +79,11 @@ pub struct Config {
     pub enable_config_hot_reload: bool,
     pub status_update_interval_ms: Option<u64>,
     pub select_current_song_on_change: bool,
+    pub record_playback_stats: bool,
     pub mpd_read_timeout: Duration,
     pub mpd_write_timeout: Duration,
+    pub reconnect: Reconnect,
+    pub key_sequence_timeout: Duration,
     pub theme: UiConfig,
     pub theme_name: Option<String>,
     pub album_art: AlbumArtConfig,
@@ -66,6 +91,10 @@ pub struct Config {
     pub on_resize: Option<Arc<Vec<String>>>,
     pub search: Search,
     pub artists: Artists,
+    pub rating: Rating,
+    pub messages: MessagesConfig,
+    pub smart_playlists: Vec<SmartPlaylist>,
+    pub queue_sort: Vec<QueueSort>,
     pub tabs: Tabs,
     pub active_panes: Vec<PaneType>,
 }
@@ -78,6 +107,8 @@ pub struct ConfigFile {
     #[serde(default)]
     password: Option<String>,
     #[serde(default)]
+    profiles: Vec<MpdProfileFile>,
+    #[serde(default)]
     cache_dir: Option<String>,
     #[serde(default)]
     lyrics_dir: Option<String>,
@@ -95,10 +126,16 @@ pub struct ConfigFile {
     status_update_interval_ms: Option<u64>,
     #[serde(default = "defaults::default_false")]
     select_current_song_on_change: bool,
+    #[serde(default = "defaults::default_false")]
+    record_playback_stats: bool,
     #[serde(default = "defaults::default_read_timeout")]
     mpd_read_timeout_ms: u64,
     #[serde(default = "defaults::default_write_timeout")]
     mpd_write_timeout_ms: u64,
+    #[serde(default)]
+    reconnect: ReconnectFile,
+    #[serde(default = "defaults::default_key_sequence_timeout")]
+    key_sequence_timeout_ms: u64,
     #[serde(default = "defaults::default_true")]
     enable_mouse: bool,
     #[serde(default = "defaults::default_true")]
@@ -120,6 +157,14 @@ pub struct ConfigFile {
     #[serde(default)]
     artists: ArtistsFile,
     #[serde(default)]
+    rating: RatingFile,
+    #[serde(default)]
+    messages: MessagesConfigFile,
+    #[serde(default)]
+    smart_playlists: Vec<SmartPlaylistFile>,
+    #[serde(default = "defaults::default_queue_sort")]
+    queue_sort: Vec<QueueSortFile>,
+    #[serde(default)]
     tabs: TabsFile,
 }
 
@@ -145,12 +190,15 @@ impl Default for ConfigFile {
             status_update_interval_ms: Some(1000),
             mpd_write_timeout_ms: 5000,
             mpd_read_timeout_ms: 10_000,
+            reconnect: ReconnectFile::default(),
+            key_sequence_timeout_ms: 1000,
             max_fps: 30,
             theme: None,
             cache_dir: None,
             lyrics_dir: None,
             image_method: None,
             select_current_song_on_change: false,
+            record_playback_stats: false,
             album_art_max_size_px: Size::default(),
             album_art: AlbumArtConfigFile {
                 disabled_protocols: defaults::disabled_album_art_protos(),
@@ -164,7 +212,12 @@ impl Default for ConfigFile {
             enable_config_hot_reload: true,
             wrap_navigation: false,
             password: None,
+            profiles: Vec::new(),
             artists: ArtistsFile::default(),
+            rating: RatingFile::default(),
+            messages: MessagesConfigFile::default(),
+            smart_playlists: Vec::new(),
+            queue_sort: defaults::default_queue_sort(),
         }
     }
 }
@@ -210,9 +263,10 @@ impl ConfigFile {
         theme_cli: Option<&Path>,
         address_cli: Option<String>,
         password_cli: Option<String>,
+        profile_cli: Option<&str>,
         skip_album_art_check: bool,
     ) -> Result<Config> {
-        let theme = if let Some(path) = theme_cli {
+        let mut theme: UiConfigFile = if let Some(path) = theme_cli {
             let file = std::fs::File::open(path).with_context(|| {
                 format!("Failed to open theme file {:?}", path.to_string_lossy())
             })?;
@@ -228,6 +282,8 @@ impl ConfigFile {
             UiConfigFile::default()
         };
 
+        let rating: Rating = self.rating.into();
+        theme.use_rating_config(&rating);
         let theme = UiConfig::try_from(theme)?;
 
         let tabs: Tabs = self.tabs.try_into()?;
@@ -239,8 +295,12 @@ impl ConfigFile {
             .unique()
             .collect_vec();
 
+        let profiles: Vec<MpdProfile> = self.profiles.into_iter().map(Into::into).collect();
+        let profile = profile_cli.map(|name| MpdProfile::find(&profiles, name)).transpose()?;
+        let active_profile =
+            profile.filter(|_| address_cli.is_none()).map(|profile| profile.name.clone());
         let (address, password) =
-            MpdAddress::resolve(address_cli, password_cli, self.address, self.password);
+            MpdAddress::resolve(address_cli, password_cli, profile, self.address, self.password);
         let album_art_method = self.album_art.method;
         let mut config = Config {
             theme,
@@ -254,6 +314,8 @@ impl ConfigFile {
             active_panes,
             address,
             password,
+            profiles,
+            active_profile,
             volume_step: self.volume_step,
             max_fps: self.max_fps,
             scrolloff: self.scrolloff,
@@ -261,12 +323,23 @@ impl ConfigFile {
             status_update_interval_ms: self.status_update_interval_ms.map(|v| v.max(100)),
             mpd_read_timeout: Duration::from_millis(self.mpd_read_timeout_ms),
             mpd_write_timeout: Duration::from_millis(self.mpd_write_timeout_ms),
+            reconnect: self.reconnect.into(),
+            key_sequence_timeout: Duration::from_millis(self.key_sequence_timeout_ms),
             enable_mouse: self.enable_mouse,
             enable_config_hot_reload: self.enable_config_hot_reload,
             keybinds: self.keybinds.into(),
             select_current_song_on_change: self.select_current_song_on_change,
+            record_playback_stats: self.record_playback_stats,
             search: self.search.into(),
             artists: self.artists.into(),
+            queue_sort: self
+                .queue_sort
+                .into_iter()
+                .map(|sort| sort.into_queue_sort(&rating))
+                .collect(),
+            rating,
+            messages: self.messages.into(),
+            smart_playlists: self.smart_playlists.into_iter().map(Into::into).collect(),
             album_art: self.album_art.into(),
             on_song_change: self.on_song_change.map(|arr| {
                 Arc::new(arr.into_iter().map(|v| tilde_expand(&v).into_owned()).collect_vec())
@@ -355,6 +428,49 @@ impl From<OnOffOneshot> for crate::mpd::commands::status::OnOffOneshot {
     }
 }
 
+impl From<ReplayGainMode> for crate::mpd::commands::replay_gain::ReplayGainMode {
+    fn from(value: ReplayGainMode) -> Self {
+        match value {
+            ReplayGainMode::Off => crate::mpd::commands::replay_gain::ReplayGainMode::Off,
+            ReplayGainMode::Track => crate::mpd::commands::replay_gain::ReplayGainMode::Track,
+            ReplayGainMode::Album => crate::mpd::commands::replay_gain::ReplayGainMode::Album,
+            ReplayGainMode::Auto => crate::mpd::commands::replay_gain::ReplayGainMode::Auto,
+        }
+    }
+}
+
+impl From<SearchMode> for crate::mpd::mpd_client::FilterKind {
+    fn from(value: SearchMode) -> Self {
+        match value {
+            SearchMode::Exact => crate::mpd::mpd_client::FilterKind::Exact,
+            SearchMode::StartsWith => crate::mpd::mpd_client::FilterKind::StartsWith,
+            SearchMode::Contains => crate::mpd::mpd_client::FilterKind::Contains,
+            SearchMode::Regex => crate::mpd::mpd_client::FilterKind::Regex,
+        }
+    }
+}
+
+impl From<PlaylistSaveMode> for crate::mpd::mpd_client::SaveMode {
+    fn from(value: PlaylistSaveMode) -> Self {
+        match value {
+            PlaylistSaveMode::Create => crate::mpd::mpd_client::SaveMode::Create,
+            PlaylistSaveMode::Append => crate::mpd::mpd_client::SaveMode::Append,
+            PlaylistSaveMode::Replace => crate::mpd::mpd_client::SaveMode::Replace,
+        }
+    }
+}
+
+impl From<PlaylistFileFormat> for crate::shared::playlist_file::PlaylistFormat {
+    fn from(value: PlaylistFileFormat) -> Self {
+        match value {
+            PlaylistFileFormat::M3u => crate::shared::playlist_file::PlaylistFormat::M3u,
+            PlaylistFileFormat::M3u8 => crate::shared::playlist_file::PlaylistFormat::M3u8,
+            PlaylistFileFormat::Pls => crate::shared::playlist_file::PlaylistFormat::Pls,
+            PlaylistFileFormat::Xspf => crate::shared::playlist_file::PlaylistFormat::Xspf,
+        }
+    }
+}
+
 pub mod utils {
     use std::{borrow::Cow, path::MAIN_SEPARATOR};
 
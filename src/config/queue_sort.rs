@@ -0,0 +1,273 @@
+use std::{borrow::Cow, cmp::Ordering};
+
+use serde::{Deserialize, Serialize};
+
+use super::{defaults, rating::Rating, smart_playlist::compare_values};
+use crate::mpd::commands::{
+    Song,
+    stickers::{LAST_PLAYED, PLAY_COUNT, SKIP_COUNT},
+};
+
+/// Named list of keys the queue can be sorted by
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueSort {
+    pub name: String,
+    pub keys: Vec<QueueSortKey>,
+    pub reverse: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueueSortKey {
+    Tag(String),
+    Sticker(String),
+    Duration,
+    File,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QueueSortFile {
+    name: String,
+    keys: Vec<QueueSortKeyFile>,
+    #[serde(default = "defaults::default_false")]
+    reverse: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum QueueSortKeyFile {
+    Tag(String),
+    Sticker(String),
+    /// Sticker configured in `rating`
+    Rating,
+    Duration,
+    File,
+}
+
+impl std::fmt::Display for QueueSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl QueueSortFile {
+    fn new(name: &str, keys: Vec<QueueSortKeyFile>, reverse: bool) -> Self {
+        Self { name: name.to_owned(), keys, reverse }
+    }
+
+    pub fn default_presets() -> Vec<Self> {
+        use QueueSortKeyFile as K;
+        let tags = |tags: &[&str]| tags.iter().map(|tag| K::Tag((*tag).to_owned())).collect();
+
+        vec![
+            Self::new("Artist", tags(&["artist", "album", "disc", "track"]), false),
+            Self::new(
+                "Album artist",
+                tags(&["albumartist", "date", "album", "disc", "track"]),
+                false,
+            ),
+            Self::new("Album", tags(&["album", "disc", "track"]), false),
+            Self::new("Track", tags(&["disc", "track"]), false),
+            Self::new("Title", tags(&["title"]), false),
+            Self::new("Date", tags(&["date", "album", "disc", "track"]), false),
+            Self::new("Duration", vec![K::Duration], false),
+            Self::new("File", vec![K::File], false),
+            Self::new("Rating", vec![K::Rating], true),
+            Self::new("Play count", vec![K::Sticker(PLAY_COUNT.to_owned())], true),
+            Self::new("Skip count", vec![K::Sticker(SKIP_COUNT.to_owned())], true),
+            Self::new("Last played", vec![K::Sticker(LAST_PLAYED.to_owned())], true),
+        ]
+    }
+
+    pub(super) fn into_queue_sort(self, rating: &Rating) -> QueueSort {
+        QueueSort {
+            name: self.name,
+            keys: self
+                .keys
+                .into_iter()
+                .map(|key| match key {
+                    QueueSortKeyFile::Tag(tag) => QueueSortKey::Tag(tag),
+                    QueueSortKeyFile::Sticker(name) => QueueSortKey::Sticker(name),
+                    QueueSortKeyFile::Rating => QueueSortKey::Sticker(rating.sticker.clone()),
+                    QueueSortKeyFile::Duration => QueueSortKey::Duration,
+                    QueueSortKeyFile::File => QueueSortKey::File,
+                })
+                .collect(),
+            reverse: self.reverse,
+        }
+    }
+}
+
+impl QueueSort {
+    /// Whether stickers have to be fetched to sort the songs
+    pub fn needs_stickers(&self) -> bool {
+        self.keys.iter().any(|key| matches!(key, QueueSortKey::Sticker(_)))
+    }
+
+    /// Sorts the songs by the keys in order, ties are broken by the next key.
+    /// Songs without a value for the key are always last and songs which
+    /// compare equal keep their relative order.
+    pub fn apply(&self, songs: &mut [Song]) {
+        songs.sort_by(|a, b| {
+            self.keys
+                .iter()
+                .map(|key| match (key.value(a), key.value(b)) {
+                    (Some(a), Some(b)) if self.reverse => compare_values(&b, &a),
+                    (Some(a), Some(b)) => compare_values(&a, &b),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+    }
+}
+
+impl QueueSortKey {
+    /// Tags are compared case insensitively. Only the number part of values
+    /// like `3/12` is used for track and disc tags.
+    fn value<'song>(&self, song: &'song Song) -> Option<Cow<'song, str>> {
+        match self {
+            QueueSortKey::Tag(tag) => {
+                let tag = tag.to_lowercase();
+                let value = song.tag(&tag)?;
+                match tag.as_str() {
+                    "track" | "disc" => {
+                        Some(Cow::Borrowed(value.split_once('/').map_or(value.as_str(), |v| v.0)))
+                    }
+                    _ => Some(Cow::Owned(value.to_lowercase())),
+                }
+            }
+            QueueSortKey::Sticker(name) => song
+                .stickers
+                .as_ref()
+                .and_then(|stickers| stickers.get(name))
+                .map(|value| Cow::Borrowed(value.as_str())),
+            QueueSortKey::Duration => {
+                song.duration.map(|duration| Cow::Owned(duration.as_secs_f64().to_string()))
+            }
+            QueueSortKey::File => Some(Cow::Owned(song.file.to_lowercase())),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::{collections::HashMap, time::Duration};
+
+    use test_case::test_case;
+
+    use super::{QueueSort, QueueSortFile, QueueSortKey};
+    use crate::{
+        config::rating::{Rating, RatingFile},
+        mpd::commands::Song,
+    };
+
+    fn song(file: &str, tags: &[(&str, &str)]) -> Song {
+        Song {
+            file: file.to_owned(),
+            metadata: tags.iter().map(|(k, v)| ((*k).to_owned(), (*v).to_owned().into())).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn files(songs: &[Song]) -> Vec<&str> {
+        songs.iter().map(|song| song.file.as_str()).collect()
+    }
+
+    fn sort(keys: Vec<QueueSortKey>, reverse: bool) -> QueueSort {
+        QueueSort { name: "test".to_owned(), keys, reverse }
+    }
+
+    #[test]
+    fn sorts_by_multiple_keys() {
+        let mut songs = vec![
+            song("a", &[("artist", "b"), ("album", "x"), ("track", "2/10")]),
+            song("b", &[("artist", "A"), ("album", "y"), ("track", "1")]),
+            song("c", &[("artist", "b"), ("album", "x"), ("track", "10/10")]),
+            song("d", &[("artist", "b"), ("album", "x"), ("track", "1/10")]),
+            song("e", &[("album", "x")]),
+        ];
+        let keys = vec![
+            QueueSortKey::Tag("Artist".to_owned()),
+            QueueSortKey::Tag("album".to_owned()),
+            QueueSortKey::Tag("track".to_owned()),
+        ];
+
+        sort(keys, false).apply(&mut songs);
+
+        assert_eq!(files(&songs), ["b", "d", "a", "c", "e"]);
+    }
+
+    #[test_case(false, &["b", "c", "a", "d"])]
+    #[test_case(true, &["a", "c", "b", "d"])]
+    fn sorts_by_sticker(reverse: bool, expected: &[&str]) {
+        let mut songs = ["10", "2", "3", ""]
+            .into_iter()
+            .zip(["a", "b", "c", "d"])
+            .map(|(rating, file)| Song {
+                stickers: Some(if rating.is_empty() {
+                    HashMap::new()
+                } else {
+                    HashMap::from([("rating".to_owned(), rating.to_owned())])
+                }),
+                ..song(file, &[])
+            })
+            .collect::<Vec<_>>();
+
+        sort(vec![QueueSortKey::Sticker("rating".to_owned())], reverse).apply(&mut songs);
+
+        assert_eq!(files(&songs), expected);
+    }
+
+    #[test]
+    fn sorts_by_duration_and_keeps_order_of_equal_songs() {
+        let mut songs = [Some(90), Some(5), None, Some(5)]
+            .into_iter()
+            .zip(["a", "b", "c", "d"])
+            .map(|(secs, file)| Song { duration: secs.map(Duration::from_secs), ..song(file, &[]) })
+            .collect::<Vec<_>>();
+
+        sort(vec![QueueSortKey::Duration], false).apply(&mut songs);
+
+        assert_eq!(files(&songs), ["b", "d", "a", "c"]);
+    }
+
+    #[test]
+    fn parses_sort() {
+        let result: QueueSortFile = ron::de::from_str(
+            r#"(name: "Genre", keys: [Tag("genre"), Sticker("rating"), Duration, File])"#,
+        )
+        .unwrap();
+        let result = result.into_queue_sort(&Rating::default());
+
+        assert_eq!(result, QueueSort {
+            name: "Genre".to_owned(),
+            ..sort(
+                vec![
+                    QueueSortKey::Tag("genre".to_owned()),
+                    QueueSortKey::Sticker("rating".to_owned()),
+                    QueueSortKey::Duration,
+                    QueueSortKey::File,
+                ],
+                false
+            )
+        });
+        assert!(result.needs_stickers());
+    }
+
+    #[test]
+    fn rating_key_uses_configured_sticker() {
+        let rating: Rating =
+            RatingFile { sticker: "stars".to_owned(), ..RatingFile::default() }.into();
+        let result: QueueSortFile =
+            ron::de::from_str(r#"(name: "Rating", keys: [Rating], reverse: true)"#).unwrap();
+
+        let result = result.into_queue_sort(&rating);
+
+        assert_eq!(result, QueueSort {
+            name: "Rating".to_owned(),
+            ..sort(vec![QueueSortKey::Sticker("stars".to_owned())], true)
+        });
+    }
+}
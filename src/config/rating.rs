@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+use super::defaults;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rating {
+    pub sticker: String,
+    pub max: u8,
+    pub like_sticker: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RatingFile {
+    #[serde(default = "defaults::default_rating_sticker")]
+    pub sticker: String,
+    #[serde(default = "defaults::default_rating_max")]
+    pub max: u8,
+    #[serde(default = "defaults::default_like_sticker")]
+    pub like_sticker: String,
+}
+
+impl Default for RatingFile {
+    fn default() -> Self {
+        Self {
+            sticker: defaults::default_rating_sticker(),
+            max: defaults::default_rating_max(),
+            like_sticker: defaults::default_like_sticker(),
+        }
+    }
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        RatingFile::default().into()
+    }
+}
+
+impl From<RatingFile> for Rating {
+    fn from(value: RatingFile) -> Self {
+        Self { sticker: value.sticker, max: value.max, like_sticker: value.like_sticker }
+    }
+}
+
+/// Change of a song's rating requested by the user
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RatingChange {
+    Set(u8),
+    Increase,
+    Decrease,
+}
+
+impl RatingChange {
+    /// Resolves the new rating from the current one, missing or invalid
+    /// current rating is treated as zero. The result never exceeds `max`.
+    pub fn apply(self, current: Option<&str>, max: u8) -> u8 {
+        let current = current.and_then(|v| v.parse::<u8>().ok()).unwrap_or(0).min(max);
+        match self {
+            RatingChange::Set(value) => value.min(max),
+            RatingChange::Increase => current.saturating_add(1).min(max),
+            RatingChange::Decrease => current.saturating_sub(1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::RatingChange;
+
+    #[test_case(RatingChange::Set(7), None, 7)]
+    #[test_case(RatingChange::Set(15), Some("3"), 10)]
+    #[test_case(RatingChange::Increase, None, 1)]
+    #[test_case(RatingChange::Increase, Some("10"), 10)]
+    #[test_case(RatingChange::Increase, Some("garbage"), 1)]
+    #[test_case(RatingChange::Decrease, Some("4"), 3)]
+    #[test_case(RatingChange::Decrease, Some("0"), 0)]
+    #[test_case(RatingChange::Decrease, Some("20"), 9)]
+    fn apply(change: RatingChange, current: Option<&str>, expected: u8) {
+        assert_eq!(change.apply(current, 10), expected);
+    }
+}
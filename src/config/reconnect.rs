@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::defaults;
+
+/// Delays between attempts to reconnect to MPD after the connection was lost
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reconnect {
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReconnectFile {
+    #[serde(default = "defaults::default_reconnect_initial_delay_ms")]
+    pub initial_delay_ms: u64,
+    #[serde(default = "defaults::default_reconnect_max_delay_ms")]
+    pub max_delay_ms: u64,
+}
+
+impl Default for ReconnectFile {
+    fn default() -> Self {
+        Self {
+            initial_delay_ms: defaults::default_reconnect_initial_delay_ms(),
+            max_delay_ms: defaults::default_reconnect_max_delay_ms(),
+        }
+    }
+}
+
+impl Default for Reconnect {
+    fn default() -> Self {
+        ReconnectFile::default().into()
+    }
+}
+
+impl From<ReconnectFile> for Reconnect {
+    fn from(value: ReconnectFile) -> Self {
+        Self {
+            initial_delay: Duration::from_millis(value.initial_delay_ms),
+            max_delay: Duration::from_millis(value.max_delay_ms),
+        }
+    }
+}
+
+impl Reconnect {
+    /// Delay before the given reconnect attempt, counted from one. Starts at
+    /// the initial delay and doubles with every failed attempt up to the max
+    /// delay.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use test_case::test_case;
+
+    use super::Reconnect;
+
+    #[test_case(1, 1000)]
+    #[test_case(2, 2000)]
+    #[test_case(3, 4000)]
+    #[test_case(5, 16000)]
+    #[test_case(6, 30000; "capped at max delay")]
+    #[test_case(100, 30000; "does not overflow")]
+    fn doubles_delay(attempt: u32, expected_ms: u64) {
+        let reconnect =
+            Reconnect { initial_delay: Duration::from_secs(1), max_delay: Duration::from_secs(30) };
+
+        assert_eq!(reconnect.delay(attempt), Duration::from_millis(expected_ms));
+    }
+}
@@ -8,6 +8,9 @@ pub struct Search {
     pub case_sensitive: bool,
     pub mode: FilterKind,
     pub tags: Vec<SearchableTag>,
+    /// Stickers searched by conditions like `>=5` which are applied to the
+    /// songs found by the tags
+    pub stickers: Vec<SearchableTag>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -15,6 +18,8 @@ pub struct SearchFile {
     case_sensitive: bool,
     mode: FilterKindFile,
     tags: Vec<SearchableTagFile>,
+    #[serde(default)]
+    stickers: Vec<SearchableTagFile>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -43,6 +48,11 @@ impl From<SearchFile> for Search {
                     .map(|SearchableTagFile { value, label }| SearchableTag { label, value })
                     .collect_vec()
             },
+            stickers: value
+                .stickers
+                .into_iter()
+                .map(|SearchableTagFile { value, label }| SearchableTag { label, value })
+                .collect_vec(),
         }
     }
 }
@@ -65,12 +75,13 @@ impl Default for SearchFile {
                 SearchableTagFile { value: "genre".to_string(), label: "Genre".to_string() },
             ]
             .to_vec(),
+            stickers: Vec::new(),
         }
     }
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
-enum FilterKindFile {
+pub(super) enum FilterKindFile {
     Exact,
     StartsWith,
     #[default]
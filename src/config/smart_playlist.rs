@@ -0,0 +1,479 @@
+use std::{cmp::Ordering, collections::HashSet};
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::{defaults, search::FilterKindFile};
+use crate::mpd::{
+    commands::{Song, stickers::StickersWithFile},
+    mpd_client::{Filter, FilterKind, Tag},
+};
+
+/// Playlist defined by query rules in the config which is resolved every time
+/// it is opened
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmartPlaylist {
+    pub name: String,
+    pub filters: Vec<SmartPlaylistFilter>,
+    pub stickers: Vec<StickerCondition>,
+    pub case_sensitive: bool,
+    pub sort: Option<SmartPlaylistSort>,
+    pub reverse: bool,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SmartPlaylistFile {
+    name: String,
+    #[serde(default)]
+    filters: Vec<SmartPlaylistFilterFile>,
+    #[serde(default)]
+    stickers: Vec<StickerConditionFile>,
+    #[serde(default = "defaults::default_false")]
+    case_sensitive: bool,
+    #[serde(default)]
+    sort: Option<SmartPlaylistSort>,
+    #[serde(default = "defaults::default_false")]
+    reverse: bool,
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmartPlaylistFilter {
+    pub tag: String,
+    pub value: String,
+    pub kind: FilterKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SmartPlaylistFilterFile {
+    tag: String,
+    value: String,
+    #[serde(default = "defaults::default_smart_playlist_filter_kind")]
+    kind: FilterKindFile,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StickerCondition {
+    pub sticker: String,
+    pub op: StickerOperator,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StickerConditionFile {
+    sticker: String,
+    op: StickerOperator,
+    value: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum StickerOperator {
+    Equal,
+    NotEqual,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SmartPlaylistSort {
+    Tag(String),
+    Sticker(String),
+}
+
+impl From<SmartPlaylistFile> for SmartPlaylist {
+    fn from(value: SmartPlaylistFile) -> Self {
+        Self {
+            name: value.name,
+            filters: value
+                .filters
+                .into_iter()
+                .map(|filter| SmartPlaylistFilter {
+                    tag: filter.tag,
+                    value: filter.value,
+                    kind: filter.kind.into(),
+                })
+                .collect(),
+            stickers: value
+                .stickers
+                .into_iter()
+                .map(|condition| StickerCondition {
+                    sticker: condition.sticker,
+                    op: condition.op,
+                    value: condition.value,
+                })
+                .collect(),
+            case_sensitive: value.case_sensitive,
+            sort: value.sort,
+            reverse: value.reverse,
+            limit: value.limit,
+        }
+    }
+}
+
+impl SmartPlaylist {
+    /// Whether songs have to be looked up through their stickers because there
+    /// are no tag filters to narrow down the library
+    pub fn is_sticker_only(&self) -> bool {
+        self.filters.is_empty() && !self.stickers.is_empty()
+    }
+
+    /// Files matching all sticker conditions. `found` are the results of
+    /// `sticker find` for each of the conditions, in the same order.
+    pub fn sticker_matches(&self, found: Vec<StickersWithFile>) -> Vec<String> {
+        let mut matches = self.stickers.iter().zip(found).map(|(condition, found)| {
+            found
+                .0
+                .into_iter()
+                .filter(|sticker| condition.matches_value(&sticker.value))
+                .map(|sticker| sticker.file)
+                .collect::<HashSet<_>>()
+        });
+        let first = matches.next().unwrap_or_default();
+        matches
+            .fold(first, |acc, files| acc.intersection(&files).cloned().collect())
+            .into_iter()
+            .sorted()
+            .collect()
+    }
+
+    /// Filters to pass to MPD's `find` or `search`. Matches the whole library
+    /// if the playlist has no tag filters.
+    pub fn mpd_filters(&self) -> Vec<Filter<'_>> {
+        if self.filters.is_empty() {
+            return vec![Filter::new_with_kind(Tag::File, "", FilterKind::StartsWith)];
+        }
+        self.filters
+            .iter()
+            .map(|filter| Filter::new_with_kind(filter.tag.clone(), &filter.value, filter.kind))
+            .collect_vec()
+    }
+
+    /// Whether stickers have to be fetched to resolve the playlist
+    pub fn needs_stickers(&self) -> bool {
+        !self.stickers.is_empty() || matches!(self.sort, Some(SmartPlaylistSort::Sticker(_)))
+    }
+
+    /// Applies sticker conditions, sort and limit to the songs found by MPD.
+    /// Songs without the value to sort by are always last.
+    pub fn apply(&self, mut songs: Vec<Song>) -> Vec<Song> {
+        songs.retain(|song| self.stickers.iter().all(|condition| condition.matches(song)));
+
+        if let Some(sort) = &self.sort {
+            songs.sort_by(|a, b| match (sort.value(a), sort.value(b)) {
+                (Some(a), Some(b)) if self.reverse => compare_values(b, a),
+                (Some(a), Some(b)) => compare_values(a, b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            });
+        } else if self.reverse {
+            songs.reverse();
+        }
+
+        if let Some(limit) = self.limit {
+            songs.truncate(limit);
+        }
+
+        songs
+    }
+}
+
+impl SmartPlaylistSort {
+    fn value<'song>(&self, song: &'song Song) -> Option<&'song str> {
+        match self {
+            SmartPlaylistSort::Tag(tag) => song.tag(&tag.to_lowercase()).map(String::as_str),
+            SmartPlaylistSort::Sticker(name) => {
+                song.stickers.as_ref().and_then(|stickers| stickers.get(name)).map(String::as_str)
+            }
+        }
+    }
+}
+
+impl StickerCondition {
+    /// Parses a condition typed by the user like `>=5` or `<3`. Values without
+    /// an operator have to be equal.
+    pub fn parse(sticker: &str, input: &str) -> Self {
+        use StickerOperator as Op;
+        let input = input.trim();
+        let (op, value) = [
+            (">=", Op::GreaterOrEqual),
+            ("<=", Op::LessOrEqual),
+            ("!=", Op::NotEqual),
+            (">", Op::Greater),
+            ("<", Op::Less),
+            ("=", Op::Equal),
+        ]
+        .into_iter()
+        .find_map(|(prefix, op)| input.strip_prefix(prefix).map(|value| (op, value)))
+        .unwrap_or((Op::Equal, input));
+
+        Self { sticker: sticker.to_owned(), op, value: value.trim().to_owned() }
+    }
+
+    /// Songs without the sticker never match
+    fn matches(&self, song: &Song) -> bool {
+        song.stickers
+            .as_ref()
+            .and_then(|stickers| stickers.get(&self.sticker))
+            .is_some_and(|value| self.matches_value(value))
+    }
+
+    fn matches_value(&self, value: &str) -> bool {
+        let ordering = compare_values(value, &self.value);
+        match self.op {
+            StickerOperator::Equal => ordering == Ordering::Equal,
+            StickerOperator::NotEqual => ordering != Ordering::Equal,
+            StickerOperator::Greater => ordering == Ordering::Greater,
+            StickerOperator::GreaterOrEqual => ordering != Ordering::Less,
+            StickerOperator::Less => ordering == Ordering::Less,
+            StickerOperator::LessOrEqual => ordering != Ordering::Greater,
+        }
+    }
+}
+
+/// Compares values numerically if both of them are numbers, as strings if
+/// neither of them is. Numbers are always ordered before other values so the
+/// ordering stays total when both kinds are mixed.
+pub(super) fn compare_values(a: &str, b: &str) -> Ordering {
+    let parse = |value: &str| value.parse::<f64>().ok().filter(|value| !value.is_nan());
+    match (parse(a), parse(b)) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => a.cmp(b),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::HashMap;
+
+    use test_case::test_case;
+
+    use super::{
+        SmartPlaylist,
+        SmartPlaylistFile,
+        SmartPlaylistFilter,
+        SmartPlaylistSort,
+        StickerCondition,
+        StickerOperator,
+        compare_values,
+    };
+    use crate::mpd::{
+        commands::{
+            Song,
+            stickers::{StickerWithFile, StickersWithFile},
+        },
+        mpd_client::FilterKind,
+    };
+
+    fn song(file: &str, date: Option<&str>, rating: Option<&str>) -> Song {
+        Song {
+            file: file.to_owned(),
+            metadata: date
+                .map(|date| HashMap::from([("date".to_owned(), date.to_owned().into())]))
+                .unwrap_or_default(),
+            stickers: Some(
+                rating
+                    .map(|rating| HashMap::from([("rating".to_owned(), rating.to_owned())]))
+                    .unwrap_or_default(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    fn playlist() -> SmartPlaylist {
+        SmartPlaylist {
+            name: "test".to_owned(),
+            filters: Vec::new(),
+            stickers: Vec::new(),
+            case_sensitive: false,
+            sort: None,
+            reverse: false,
+            limit: None,
+        }
+    }
+
+    fn files(songs: &[Song]) -> Vec<&str> {
+        songs.iter().map(|song| song.file.as_str()).collect()
+    }
+
+    #[test_case(StickerOperator::GreaterOrEqual, "8", &["a", "c"])]
+    #[test_case(StickerOperator::Greater, "8", &["c"])]
+    #[test_case(StickerOperator::Less, "8", &["b"])]
+    #[test_case(StickerOperator::Equal, "8", &["a"])]
+    #[test_case(StickerOperator::NotEqual, "8", &["b", "c"])]
+    fn sticker_conditions(op: StickerOperator, value: &str, expected: &[&str]) {
+        let playlist = SmartPlaylist {
+            stickers: vec![StickerCondition {
+                sticker: "rating".to_owned(),
+                op,
+                value: value.to_owned(),
+            }],
+            ..playlist()
+        };
+        let songs = vec![
+            song("a", None, Some("8")),
+            song("b", None, Some("7")),
+            song("c", None, Some("10")),
+            song("d", None, None),
+        ];
+
+        let result = playlist.apply(songs);
+
+        assert_eq!(files(&result), expected);
+    }
+
+    #[test_case("5", StickerOperator::Equal, "5"; "no operator")]
+    #[test_case("=5", StickerOperator::Equal, "5"; "equal")]
+    #[test_case("!=5", StickerOperator::NotEqual, "5")]
+    #[test_case(">5", StickerOperator::Greater, "5")]
+    #[test_case(">= 5", StickerOperator::GreaterOrEqual, "5")]
+    #[test_case("<5", StickerOperator::Less, "5")]
+    #[test_case(" <=5 ", StickerOperator::LessOrEqual, "5")]
+    fn parses_sticker_condition(input: &str, op: StickerOperator, value: &str) {
+        let result = StickerCondition::parse("playCount", input);
+
+        assert_eq!(result, StickerCondition {
+            sticker: "playCount".to_owned(),
+            op,
+            value: value.to_owned()
+        });
+    }
+
+    #[test_case(SmartPlaylistSort::Tag("Date".to_owned()), false, &["b", "a", "c"])]
+    #[test_case(SmartPlaylistSort::Tag("Date".to_owned()), true, &["a", "b", "c"])]
+    #[test_case(SmartPlaylistSort::Sticker("rating".to_owned()), false, &["a", "c", "b"])]
+    fn sorts_songs(sort: SmartPlaylistSort, reverse: bool, expected: &[&str]) {
+        let playlist = SmartPlaylist { sort: Some(sort), reverse, ..playlist() };
+        let songs = vec![
+            song("a", Some("1965"), Some("9")),
+            song("b", Some("1959"), None),
+            song("c", None, Some("10")),
+        ];
+
+        let result = playlist.apply(songs);
+
+        assert_eq!(files(&result), expected);
+    }
+
+    #[test]
+    fn sorts_mixed_numbers_and_strings() {
+        let playlist =
+            SmartPlaylist { sort: Some(SmartPlaylistSort::Tag("date".to_owned())), ..playlist() };
+        let songs = vec![
+            song("a", Some("2 + 2 = 5"), None),
+            song("b", Some("1979"), None),
+            song("c", Some("nan"), None),
+            song("d", Some("22"), None),
+            song("e", Some("abc"), None),
+        ];
+
+        let result = playlist.apply(songs);
+
+        assert_eq!(files(&result), ["d", "b", "a", "e", "c"]);
+    }
+
+    #[test]
+    fn compares_values_in_total_order() {
+        let values = ["22", "1979", "2 + 2 = 5", "nan", "NaN", "-1", "1.5", "abc", ""];
+
+        for a in values {
+            for b in values {
+                assert_eq!(compare_values(a, b), compare_values(b, a).reverse(), "{a} {b}");
+                for c in values {
+                    if compare_values(a, b).is_le() && compare_values(b, c).is_le() {
+                        assert!(compare_values(a, c).is_le(), "{a} {b} {c}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn limits_songs_after_sorting() {
+        let playlist = SmartPlaylist {
+            sort: Some(SmartPlaylistSort::Tag("date".to_owned())),
+            limit: Some(2),
+            ..playlist()
+        };
+        let songs = vec![
+            song("a", Some("1965"), None),
+            song("b", Some("1959"), None),
+            song("c", Some("1961"), None),
+        ];
+
+        let result = playlist.apply(songs);
+
+        assert_eq!(files(&result), ["b", "c"]);
+    }
+
+    #[test]
+    fn sticker_matches_intersect_conditions() {
+        let playlist = SmartPlaylist {
+            stickers: vec![
+                StickerCondition::parse("rating", ">=8"),
+                StickerCondition::parse("playCount", ">0"),
+            ],
+            ..playlist()
+        };
+        let found = |stickers: &[(&str, &str)]| {
+            StickersWithFile(
+                stickers
+                    .iter()
+                    .map(|(file, value)| StickerWithFile {
+                        file: (*file).to_owned(),
+                        key: String::new(),
+                        value: (*value).to_owned(),
+                    })
+                    .collect(),
+            )
+        };
+
+        let result = playlist.sticker_matches(vec![
+            found(&[("c", "10"), ("a", "9"), ("b", "3"), ("d", "8")]),
+            found(&[("a", "2"), ("b", "5"), ("c", "0")]),
+        ]);
+
+        assert_eq!(result, ["a"]);
+    }
+
+    #[test]
+    fn parses_playlist_with_defaults() {
+        let file: SmartPlaylistFile = ron::de::from_str(
+            r#"(
+                name: "Top rated",
+                filters: [(tag: "genre", value: "Jazz")],
+                stickers: [(sticker: "rating", op: GreaterOrEqual, value: "8")],
+                sort: Some(Sticker("rating")),
+                limit: Some(50),
+            )"#,
+        )
+        .unwrap();
+
+        let result: SmartPlaylist = file.into();
+
+        assert_eq!(result, SmartPlaylist {
+            name: "Top rated".to_owned(),
+            filters: vec![SmartPlaylistFilter {
+                tag: "genre".to_owned(),
+                value: "Jazz".to_owned(),
+                kind: FilterKind::Exact,
+            }],
+            stickers: vec![StickerCondition {
+                sticker: "rating".to_owned(),
+                op: StickerOperator::GreaterOrEqual,
+                value: "8".to_owned(),
+            }],
+            case_sensitive: false,
+            sort: Some(SmartPlaylistSort::Sticker("rating".to_owned())),
+            reverse: false,
+            limit: Some(50),
+        });
+    }
+}
@@ -44,6 +44,8 @@ pub enum PaneTypeFile {
     AlbumArtists,
     Playlists,
     Search,
+    History,
+    Messages,
     AlbumArt,
     Lyrics,
     ProgressBar,
@@ -77,6 +79,8 @@ pub enum PaneType {
     Albums,
     Playlists,
     Search,
+    History,
+    Messages,
     AlbumArt,
     Lyrics,
     ProgressBar,
@@ -140,6 +144,8 @@ impl From<PaneTypeFile> for PaneType {
             PaneTypeFile::Albums => PaneType::Albums,
             PaneTypeFile::Playlists => PaneType::Playlists,
             PaneTypeFile::Search => PaneType::Search,
+            PaneTypeFile::History => PaneType::History,
+            PaneTypeFile::Messages => PaneType::Messages,
             PaneTypeFile::AlbumArt => PaneType::AlbumArt,
             PaneTypeFile::Lyrics => PaneType::Lyrics,
             PaneTypeFile::ProgressBar => PaneType::ProgressBar,
@@ -1,6 +1,6 @@
 use ::serde::{Deserialize, Serialize};
 use anyhow::Result;
-use properties::{SongFormat, SongFormatFile};
+use properties::{RatingProperty, SongFormat, SongFormatFile};
 use ratatui::style::{Color, Style};
 
 use self::{
@@ -23,6 +23,7 @@ pub use style::{ConfigColor, StyleFile};
 pub use self::queue_table::{PercentOrLength, SongTableColumn};
 use super::{
     defaults,
+    rating::Rating,
     tabs::{PaneOrSplitFile, SizedPaneOrSplit},
 };
 
@@ -42,6 +43,7 @@ pub struct UiConfig {
     pub highlight_border_style: Style,
     pub column_widths: [u16; 3],
     pub browser_song_format: SongFormat,
+    pub format_tag_separator: String,
     pub symbols: SymbolsConfig,
     pub progress_bar: ProgressBarConfig,
     pub tab_bar: TabBar,
@@ -66,6 +68,8 @@ pub struct UiConfigFile {
     pub(super) browser_column_widths: Vec<u16>,
     #[serde(default)]
     pub(super) browser_song_format: SongFormatFile,
+    #[serde(default = "defaults::default_tag_separator")]
+    pub(super) format_tag_separator: String,
     pub(super) background_color: Option<String>,
     pub(super) text_color: Option<String>,
     pub(super) header_background_color: Option<String>,
@@ -137,6 +141,7 @@ impl Default for UiConfigFile {
             },
             song_table_format: QueueTableColumnsFile::default(),
             browser_song_format: SongFormatFile::default(),
+            format_tag_separator: defaults::default_tag_separator(),
         }
     }
 }
@@ -182,6 +187,25 @@ impl From<SymbolsFile> for SymbolsConfig {
     }
 }
 
+impl UiConfigFile {
+    /// Rating properties of the song table, browser and header which do not
+    /// set their own sticker or maximum use the ones of the `rating` config
+    pub(super) fn use_rating_config(&mut self, rating: &Rating) {
+        self.song_table_format
+            .0
+            .iter_mut()
+            .for_each(|column| column.prop.use_rating_config(rating));
+        self.browser_song_format.0.iter_mut().for_each(|prop| prop.use_rating_config(rating));
+        for row in &mut self.header.rows {
+            row.left
+                .iter_mut()
+                .chain(row.center.iter_mut())
+                .chain(row.right.iter_mut())
+                .for_each(|prop| prop.use_rating_config(rating));
+        }
+    }
+}
+
 impl TryFrom<UiConfigFile> for UiConfig {
     type Error = anyhow::Error;
 
@@ -235,6 +259,7 @@ impl TryFrom<UiConfigFile> for UiConfig {
                     Ok(std::fs::read(path)?.leak())
                 })?,
             browser_song_format: TryInto::<SongFormat>::try_into(value.browser_song_format)?,
+            format_tag_separator: value.format_tag_separator,
         })
     }
 }
@@ -6,7 +6,7 @@ use serde_with::skip_serializing_none;
 use strum::Display;
 
 use super::style::ToConfigOr;
-use crate::config::{defaults, theme::StyleFile};
+use crate::config::{defaults, rating::Rating, theme::StyleFile};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum SongPropertyFile {
@@ -17,7 +17,27 @@ pub enum SongPropertyFile {
     Album,
     Duration,
     Track,
+    Priority,
     Other(String),
+    /// `sticker` and `max` default to the `rating` config
+    Rating {
+        #[serde(default)]
+        sticker: Option<String>,
+        #[serde(default)]
+        max: Option<u8>,
+        #[serde(default = "defaults::default_full_star")]
+        full: String,
+        #[serde(default = "defaults::default_half_star")]
+        half: String,
+        #[serde(default = "defaults::default_empty_star")]
+        empty: String,
+    },
+    PlayCount,
+    SkipCount,
+    LastPlayed {
+        #[serde(default = "defaults::default_last_played_format")]
+        format: String,
+    },
 }
 
 #[derive(Debug, Clone, Display, Hash, Eq, PartialEq)]
@@ -29,7 +49,28 @@ pub enum SongProperty {
     Album,
     Duration,
     Track,
+    /// Priority of the song in the queue, empty for the default priority of
+    /// zero
+    Priority,
     Other(String),
+    /// Rating stored in a sticker rendered as stars, each star is worth two
+    /// points so a half star is shown for odd values
+    Rating {
+        sticker: String,
+        max: u8,
+        full: String,
+        half: String,
+        empty: String,
+    },
+    /// Number of times the song was played through, recorded when
+    /// `record_playback_stats` is enabled
+    PlayCount,
+    /// Number of times the song was skipped
+    SkipCount,
+    /// Time the song was last played through in the given strftime format
+    LastPlayed {
+        format: String,
+    },
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
@@ -106,6 +147,17 @@ pub enum StatusPropertyFile {
     Duration,
     Crossfade,
     Bitrate,
+    ReplayGain,
+    Connection {
+        #[serde(default = "defaults::default_connected_label")]
+        connected_label: String,
+        #[serde(default = "defaults::default_reconnecting_label")]
+        reconnecting_label: String,
+        #[serde(default)]
+        connected_style: Option<StyleFile>,
+        #[serde(default)]
+        reconnecting_style: Option<StyleFile>,
+    },
 }
 
 #[derive(Debug, Clone, Display, Hash, Eq, PartialEq)]
@@ -151,6 +203,13 @@ pub enum StatusProperty {
     Duration,
     Crossfade,
     Bitrate,
+    ReplayGain,
+    Connection {
+        connected_label: String,
+        reconnecting_label: String,
+        connected_style: Option<Style>,
+        reconnecting_style: Option<Style>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -176,6 +235,47 @@ pub struct PropertyFile<T: Clone> {
     pub default: Option<Box<PropertyFile<T>>>,
 }
 
+/// Property which might display the song rating. Rating properties without
+/// their own sticker or maximum use the ones of the `rating` config.
+pub(super) trait RatingProperty {
+    fn use_rating_config(&mut self, rating: &Rating);
+}
+
+impl RatingProperty for SongPropertyFile {
+    fn use_rating_config(&mut self, rating: &Rating) {
+        if let SongPropertyFile::Rating { sticker, max, .. } = self {
+            sticker.get_or_insert_with(|| rating.sticker.clone());
+            max.get_or_insert(rating.max);
+        }
+    }
+}
+
+impl RatingProperty for PropertyKindFile {
+    fn use_rating_config(&mut self, rating: &Rating) {
+        match self {
+            PropertyKindFile::Song(prop) => prop.use_rating_config(rating),
+            PropertyKindFile::Status(_) | PropertyKindFile::Widget(_) => {}
+        }
+    }
+}
+
+impl<T: Clone + RatingProperty> RatingProperty for PropertyFile<T> {
+    fn use_rating_config(&mut self, rating: &Rating) {
+        match &mut self.kind {
+            PropertyKindFileOrText::Text(_) | PropertyKindFileOrText::Sticker(_) => {}
+            PropertyKindFileOrText::Property(prop) => prop.use_rating_config(rating),
+            PropertyKindFileOrText::Group(group) => {
+                for prop in group {
+                    prop.use_rating_config(rating);
+                }
+            }
+        }
+        if let Some(default) = &mut self.default {
+            default.use_rating_config(rating);
+        }
+    }
+}
+
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub enum PropertyKindOrText<T> {
     Text(String),
@@ -184,12 +284,38 @@ pub enum PropertyKindOrText<T> {
     Group(Vec<Property<T>>),
 }
 
-impl<T> PropertyKindOrText<T> {
+/// Property which might need song stickers to be fetched to be displayed
+pub trait StickerProperty {
+    fn contains_stickers(&self) -> bool;
+}
+
+impl StickerProperty for SongProperty {
+    fn contains_stickers(&self) -> bool {
+        matches!(
+            self,
+            SongProperty::Rating { .. }
+                | SongProperty::PlayCount
+                | SongProperty::SkipCount
+                | SongProperty::LastPlayed { .. }
+        )
+    }
+}
+
+impl StickerProperty for PropertyKind {
+    fn contains_stickers(&self) -> bool {
+        match self {
+            PropertyKind::Song(prop) => prop.contains_stickers(),
+            PropertyKind::Status(_) | PropertyKind::Widget(_) => false,
+        }
+    }
+}
+
+impl<T: StickerProperty> PropertyKindOrText<T> {
     pub fn contains_stickers(&self) -> bool {
         match self {
             PropertyKindOrText::Text(_) => false,
             PropertyKindOrText::Sticker(_) => true,
-            PropertyKindOrText::Property(_) => false,
+            PropertyKindOrText::Property(prop) => prop.contains_stickers(),
             PropertyKindOrText::Group(group) => {
                 group.iter().any(|prop| prop.kind.contains_stickers())
             }
@@ -244,6 +370,17 @@ impl TryFrom<SongPropertyFile> for SongProperty {
             SongPropertyFile::Duration => SongProperty::Duration,
             SongPropertyFile::Track => SongProperty::Track,
             SongPropertyFile::Other(name) => SongProperty::Other(name),
+            SongPropertyFile::Rating { sticker, max, full, half, empty } => SongProperty::Rating {
+                sticker: sticker.unwrap_or_else(defaults::default_rating_sticker),
+                max: max.unwrap_or_else(defaults::default_rating_max),
+                full,
+                half,
+                empty,
+            },
+            SongPropertyFile::Priority => SongProperty::Priority,
+            SongPropertyFile::PlayCount => SongProperty::PlayCount,
+            SongPropertyFile::SkipCount => SongProperty::SkipCount,
+            SongPropertyFile::LastPlayed { format } => SongProperty::LastPlayed { format },
         })
     }
 }
@@ -297,6 +434,22 @@ impl TryFrom<StatusPropertyFile> for StatusProperty {
             StatusPropertyFile::Volume => StatusProperty::Volume,
             StatusPropertyFile::Bitrate => StatusProperty::Bitrate,
             StatusPropertyFile::Crossfade => StatusProperty::Crossfade,
+            StatusPropertyFile::ReplayGain => StatusProperty::ReplayGain,
+            StatusPropertyFile::Connection {
+                connected_label,
+                reconnecting_label,
+                connected_style,
+                reconnecting_style,
+            } => StatusProperty::Connection {
+                connected_label,
+                reconnecting_label,
+                connected_style: connected_style
+                    .map(|s| -> Result<_> { s.to_config_or(None, None) })
+                    .transpose()?,
+                reconnecting_style: reconnecting_style
+                    .map(|s| -> Result<_> { s.to_config_or(None, None) })
+                    .transpose()?,
+            },
             StatusPropertyFile::Repeat => StatusProperty::Repeat {
                 on_label: defaults::default_on_label(),
                 off_label: defaults::default_off_label(),
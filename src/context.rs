@@ -1,4 +1,8 @@
-use std::{cell::Cell, collections::HashSet, ops::AddAssign};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashSet,
+    ops::AddAssign,
+};
 
 use anyhow::{Result, bail};
 use bon::bon;
@@ -10,7 +14,7 @@ use crate::{
     MpdQuery,
     MpdQueryResult,
     WorkRequest,
-    config::{Config, album_art::ImageMethod, tabs::PaneType},
+    config::{Config, address::MpdProfile, album_art::ImageMethod, keys::Key, tabs::PaneType},
     core::scheduler::{Scheduler, time_provider::DefaultTimeProvider},
     mpd::{
         client::Client,
@@ -19,17 +23,24 @@ use crate::{
     },
     shared::{
         events::ClientRequest,
+        history::History,
         lrc::{Lrc, LrcIndex, get_lrc_path},
         macros::status_warn,
         mpd_query::MpdQuerySync,
+        queue_history::QueueHistory,
     },
 };
 
 #[derive(derive_more::Debug)]
 pub struct AppContext {
     pub(crate) config: std::sync::Arc<Config>,
+    /// Config before the features the server does not support were disabled
+    pub(crate) user_config: std::sync::Arc<Config>,
     pub(crate) status: Status,
     pub(crate) queue: Vec<Song>,
+    /// Playlist version of MPD the queue corresponds to, changes since this
+    /// version are fetched incrementally. Unknown until the first full fetch.
+    pub(crate) queue_version: Option<u32>,
     pub(crate) supported_commands: HashSet<String>,
     #[debug(skip)]
     pub(crate) app_event_sender: Sender<AppEvent>,
@@ -40,8 +51,16 @@ pub struct AppContext {
     pub(crate) needs_render: Cell<bool>,
     #[debug(skip)]
     pub(crate) lrc_index: LrcIndex,
+    #[debug(skip)]
+    pub(crate) history: History,
+    /// Queue states recorded before edits made from the UI
+    pub(crate) queue_history: RefCell<QueueHistory>,
     pub(crate) rendered_frames: u64,
     pub(crate) should_fetch_stickers: bool,
+    /// Keys pressed so far of a keybind consisting of multiple keys
+    pub(crate) pending_keys: Vec<Key>,
+    /// Count typed before an action, ie. the `5` in `5j`
+    pub(crate) pending_count: Option<usize>,
     #[debug(skip)]
     pub(crate) scheduler: Scheduler<(Sender<AppEvent>, Sender<ClientRequest>), DefaultTimeProvider>,
 }
@@ -58,28 +77,23 @@ impl AppContext {
     ) -> Result<Self> {
         let supported_commands: HashSet<String> = client.commands()?.0.into_iter().collect();
         let sticker_support_needed = config.sticker_support_needed();
-        log::info!(supported_commands:? = supported_commands, sticker_support_needed; "Supported commands by server");
-
-        if sticker_support_needed && !supported_commands.contains("sticker") {
-            bail!(
-                "Rmpc was configured to display stickers but MPD did not report sticker support.\nCheck if you have 'sticker_file' configured your in mpd.conf."
-            );
-        }
+        let user_config = std::sync::Arc::new(config.clone());
+        Self::check_server_support(client, &mut config, &supported_commands)?;
 
-        let status = client.get_status()?;
+        let mut status = client.get_status()?;
+        status.replay_gain = client.replay_gain_status().ok();
         let queue = client.playlist_info(sticker_support_needed)?.unwrap_or_default();
 
-        if !supported_commands.contains("albumart") || !supported_commands.contains("readpicture") {
-            config.album_art.method = ImageMethod::None;
-            status_warn!("Album art is disabled because it is not supported by MPD");
-        }
-
         log::info!(config:? = config; "Resolved config");
 
         scheduler.start();
         Ok(Self {
             lrc_index: LrcIndex::default(),
+            history: History::load(config.cache_dir.as_deref()),
+            queue_history: RefCell::new(QueueHistory::default()),
             config: std::sync::Arc::new(config),
+            user_config,
+            queue_version: status.playlist,
             status,
             queue,
             supported_commands,
@@ -89,10 +103,98 @@ impl AppContext {
             client_request_sender,
             needs_render: Cell::new(false),
             should_fetch_stickers: sticker_support_needed,
+            pending_keys: Vec::new(),
+            pending_count: None,
             rendered_frames: 0,
         })
     }
 
+    /// Disables features of the config which the server does not support and
+    /// subscribes to the configured channels
+    fn check_server_support(
+        client: &mut Client<'_>,
+        config: &mut Config,
+        supported_commands: &HashSet<String>,
+    ) -> Result<()> {
+        Self::disable_unsupported_features(config, supported_commands)?;
+
+        for channel in &config.messages.channels {
+            if let Err(err) = client.subscribe(channel) {
+                status_warn!(err:?; "Failed to subscribe to channel '{channel}'");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Disables features of the config which the server does not support
+    pub(crate) fn disable_unsupported_features(
+        config: &mut Config,
+        supported_commands: &HashSet<String>,
+    ) -> Result<()> {
+        let sticker_support_needed = config.sticker_support_needed();
+        log::info!(supported_commands:? = supported_commands, sticker_support_needed; "Supported commands by server");
+
+        if sticker_support_needed && !supported_commands.contains("sticker") {
+            bail!(
+                "Rmpc was configured to display stickers but MPD did not report sticker support.\nCheck if you have 'sticker_file' configured your in mpd.conf."
+            );
+        }
+
+        if !supported_commands.contains("albumart") || !supported_commands.contains("readpicture") {
+            config.album_art.method = ImageMethod::None;
+            status_warn!("Album art is disabled because it is not supported by MPD");
+        }
+
+        if config.record_playback_stats && !supported_commands.contains("sticker") {
+            config.record_playback_stats = false;
+            status_warn!("Playback statistics are disabled because MPD does not support stickers");
+        }
+
+        Ok(())
+    }
+
+    /// Points the context to the server of the given profile which the client
+    /// is already connected to. The status is not fetched here, it is left to
+    /// the regular status update so that the playback state transitions are
+    /// handled as usual.
+    pub(crate) fn switch_server(
+        &mut self,
+        client: &mut Client<'_>,
+        profile: MpdProfile,
+    ) -> Result<()> {
+        // Start from the user's config, features disabled for the previous server might
+        // be supported by this one
+        let mut user_config = self.user_config.as_ref().clone();
+        user_config.address = profile.address;
+        user_config.password = profile.password;
+        user_config.active_profile = Some(profile.name);
+
+        let mut config = user_config.clone();
+        let supported_commands: HashSet<String> = client.commands()?.0.into_iter().collect();
+        let should_fetch_stickers = config.sticker_support_needed();
+        Self::check_server_support(client, &mut config, &supported_commands)?;
+        let queue_version = client.get_status()?.playlist;
+        let queue = client.playlist_info(should_fetch_stickers)?.unwrap_or_default();
+
+        self.config = std::sync::Arc::new(config);
+        self.user_config = std::sync::Arc::new(user_config);
+        self.should_fetch_stickers = should_fetch_stickers;
+        self.supported_commands = supported_commands;
+        self.queue = queue;
+        self.queue_version = queue_version;
+        // Undo history refers to the queue of the previous server
+        self.queue_history.replace(QueueHistory::default());
+
+        Ok(())
+    }
+
+    /// How many times the current action should be repeated, 1 if no count
+    /// was typed before it
+    pub(crate) fn count(&self) -> usize {
+        self.pending_count.unwrap_or(1)
+    }
+
     pub(crate) fn render(&self) -> Result<(), SendError<AppEvent>> {
         if self.needs_render.get() {
             return Ok(());
@@ -160,6 +262,11 @@ impl AppContext {
         }
     }
 
+    /// Records the current queue so the edit about to be made can be undone
+    pub(crate) fn record_queue_edit(&self) {
+        self.queue_history.borrow_mut().record(&self.queue);
+    }
+
     pub(crate) fn find_current_song_in_queue(&self) -> Option<(usize, &Song)> {
         if self.status.state == State::Stop {
             return None;
@@ -210,3 +317,40 @@ impl Config {
             })
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rstest::rstest;
+
+    use super::AppContext;
+    use crate::{
+        config::{Config, album_art::ImageMethod},
+        tests::fixtures::config,
+    };
+
+    #[rstest]
+    fn disables_features_unsupported_by_server(mut config: Config) {
+        config.album_art.method = ImageMethod::Kitty;
+        config.record_playback_stats = true;
+
+        AppContext::disable_unsupported_features(&mut config, &HashSet::new()).unwrap();
+
+        assert_eq!(config.album_art.method, ImageMethod::None);
+        assert!(!config.record_playback_stats);
+    }
+
+    #[rstest]
+    fn keeps_features_supported_by_server(mut config: Config) {
+        config.album_art.method = ImageMethod::Kitty;
+        config.record_playback_stats = true;
+        let supported_commands = ["albumart", "readpicture", "sticker"].map(str::to_owned).into();
+
+        AppContext::disable_unsupported_features(&mut config, &supported_commands).unwrap();
+
+        assert_eq!(config.album_art.method, ImageMethod::Kitty);
+        assert!(config.record_playback_stats);
+    }
+}
@@ -1,8 +1,12 @@
 use std::{
     collections::VecDeque,
     io::{self, Write},
-    sync::Arc,
-    thread::Builder,
+    sync::{
+        Arc,
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::{Builder, JoinHandle},
 };
 
 use anyhow::Result;
@@ -14,27 +18,105 @@ use drop_guard::{ClientDropGuard, DropGuard};
 
 use crate::{
     config::Config,
-    mpd::{client::Client, commands::idle::IdleEvent, errors::MpdError, mpd_client::MpdClient},
+    mpd::{
+        client::{Client, TcpOrUnixStream},
+        commands::idle::IdleEvent,
+        errors::MpdError,
+        mpd_client::MpdClient,
+    },
     shared::{
         events::{AppEvent, ClientRequest, WorkDone},
-        macros::{status_error, try_break, try_skip},
+        macros::{status_error, status_info, try_break, try_skip},
     },
 };
 
+/// Handle to the thread owning the connection to MPD. Requests are read from
+/// the shared request channel so the thread can be replaced by a new one with
+/// a different connection, ie. when switching to another server.
+#[derive(Debug)]
+pub struct ClientThread {
+    client_rx: Receiver<ClientRequest>,
+    event_tx: Sender<AppEvent>,
+    control_tx: Sender<ClientControl>,
+    handle: Option<JoinHandle<()>>,
+}
+
+/// Requests sent to the client thread from the outside
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ClientControl {
+    /// Close the connection and end the thread
+    Stop,
+    /// Skip the remaining backoff delay and try to reconnect right away. Has
+    /// no effect while connected.
+    Reconnect,
+}
+
 pub fn init(
     client_rx: Receiver<ClientRequest>,
     event_tx: Sender<AppEvent>,
     client: Client<'static>,
     config: Arc<Config>,
-) -> io::Result<std::thread::JoinHandle<()>> {
+) -> io::Result<ClientThread> {
+    let (control_tx, control_rx) = unbounded();
+    let handle = spawn(client_rx.clone(), event_tx.clone(), control_rx, client, config)?;
+
+    Ok(ClientThread { client_rx, event_tx, control_tx, handle: Some(handle) })
+}
+
+impl ClientThread {
+    /// Tears down the current connection and its threads and starts serving
+    /// requests with the given client instead. Requests which were not yet
+    /// handled are dropped because they were meant for the old connection.
+    pub fn restart(&mut self, client: Client<'static>, config: Arc<Config>) -> io::Result<()> {
+        self.stop();
+        let _ = self.client_rx.try_iter().collect::<Vec<_>>();
+
+        let (control_tx, control_rx) = unbounded();
+        self.control_tx = control_tx;
+        self.handle =
+            Some(spawn(self.client_rx.clone(), self.event_tx.clone(), control_rx, client, config)?);
+
+        Ok(())
+    }
+
+    /// Interrupts the wait between reconnect attempts and tries to reconnect
+    /// immediately
+    pub fn reconnect_now(&self) {
+        try_skip!(
+            self.control_tx.send(ClientControl::Reconnect),
+            "Failed to request immediate reconnect"
+        );
+    }
+
+    fn stop(&mut self) {
+        try_skip!(
+            self.control_tx.send(ClientControl::Stop),
+            "Failed to request client thread stop"
+        );
+        if let Some(handle) = self.handle.take() {
+            if let Err(err) = handle.join() {
+                log::error!(err:?; "Client thread panicked");
+            }
+        }
+    }
+}
+
+fn spawn(
+    client_rx: Receiver<ClientRequest>,
+    event_tx: Sender<AppEvent>,
+    control_rx: Receiver<ClientControl>,
+    client: Client<'static>,
+    config: Arc<Config>,
+) -> io::Result<JoinHandle<()>> {
     std::thread::Builder::new()
         .name("client task".to_owned())
-        .spawn(move || client_task(&client_rx, &event_tx, client, &config))
+        .spawn(move || client_task(&client_rx, &event_tx, &control_rx, client, &config))
 }
 
 fn client_task(
     client_rx: &Receiver<ClientRequest>,
     event_tx: &Sender<AppEvent>,
+    control_rx: &Receiver<ClientControl>,
     client: Client<'_>,
     config: &Config,
 ) {
@@ -45,9 +127,15 @@ fn client_task(
 
     let (client_return_tx, client_return_rx) = &bounded::<Client<'_>>(1);
     client_return_tx.send(client).expect("Client init to succeed");
+    let stopped = &AtomicBool::new(false);
+    // Requests taken from the channel but not sent to MPD yet. They outlive the
+    // worker threads so the commands among them can be replayed after the
+    // connection is reestablished.
+    let pending = &Mutex::new(VecDeque::<ClientRequest>::new());
 
     std::thread::scope(|s| {
         let mut first_loop = true;
+        let mut attempt = 0;
         loop {
             log::trace!(first_loop; "Starting worker threads");
 
@@ -65,10 +153,11 @@ fn client_task(
                 }
             };
             let is_client_ok =
-                check_connection(first_loop, &mut client, client_rx, event_tx, config);
+                check_connection(first_loop, &mut client, client_rx, pending, event_tx, config);
             first_loop = false;
 
             if is_client_ok {
+                attempt = 0;
                 let mut client_write =
                     client.stream.try_clone().expect("Client write clone to succeed");
 
@@ -128,9 +217,8 @@ fn client_task(
                             name: "request",
                             tx: thread_end_ctx,
                         };
-                        let mut buffer = VecDeque::new();
 
-                        loop {
+                        'requests: loop {
                             log::trace!("Waiting for client requests");
                             select! {
                                 recv(client_rx) -> msg => {
@@ -138,6 +226,7 @@ fn client_task(
                                         continue;
                                     };
 
+                                    let mut buffer = pending.lock().expect("Pending requests lock not to be poisoned");
                                     buffer.push_back(msg);
 
                                     log::trace!(buffer:?; "Trying to receive client from idle thread");
@@ -165,28 +254,28 @@ fn client_task(
                                         }
 
                                         match handle_client_request(&mut client, request) {
-                                            Ok(result) => {
+                                            Err(err) if is_timeout(&err) => {
+                                                // MPD might have executed the command even though its response was
+                                                // lost, so it is not repeated. The rest of the buffer was never sent
+                                                // and stays pending, it is replayed by the next attempt when
+                                                // reconnecting fails.
+                                                status_error!(err:?; "Reading response from MPD timed out, will try to reconnect");
+                                                match reconnect(&mut client, config) {
+                                                    Ok(stream) => client_write = stream,
+                                                    Err(err) => {
+                                                        log::warn!(error:? = err; "Failed to reconnect after timeout");
+                                                        break 'requests;
+                                                    }
+                                                }
+                                                // Changes made while the connection was broken were not reported by idle
+                                                try_break!(event_tx.send(AppEvent::Reconnected), "Failed to send reconnected event");
+                                            }
+                                            result => {
                                                 try_break!(
-                                                    event_tx.send(AppEvent::WorkDone(Ok(result))),
-                                                    "Failed to send work done success event"
+                                                    event_tx.send(AppEvent::WorkDone(result)),
+                                                    "Failed to send work done event"
                                                 );
                                             }
-                                            Err(err) => {
-                                                match err.downcast_ref::<MpdError>() {
-                                                    Some(MpdError::TimedOut(err)) => {
-                                                        status_error!(err:?; "Reading response from MPD timed out, will try to reconnect");
-                                                        try_break!(client.reconnect(), "Failed to reconnect");
-                                                        try_break!(client.set_write_timeout(Some(config.mpd_write_timeout)), "Failed to set write timeout");
-                                                        client_write = try_break!(client.stream.try_clone(), "Client write clone to succeed");
-                                                    },
-                                                    _ => {
-                                                        try_break!(
-                                                            event_tx.send(AppEvent::WorkDone(Err(err))),
-                                                            "Failed to send work done error event"
-                                                        );
-                                                    },
-                                                }
-                                            }
                                         }
                                     }
 
@@ -194,6 +283,16 @@ fn client_task(
                                     try_break!(req2idle_tx.send(client.consume()), "Failed to request for client idle");
                                     try_break!(idle_entered_rx.recv(), "Idle confirmation failed");
                                 },
+                                recv(control_rx) -> msg => {
+                                    if !matches!(msg, Ok(ClientControl::Stop)) {
+                                        continue;
+                                    }
+                                    log::debug!("Stopping client threads");
+                                    stopped.store(true, Ordering::Relaxed);
+                                    // Unblocks the idle thread waiting for events
+                                    try_skip!(client_write.shutdown(), "Failed to shut down MPD connection");
+                                    break;
+                                }
                                 recv(thread_end_rx) -> _ => {
                                     log::debug!("recv drop idle");
                                     break;
@@ -210,13 +309,28 @@ fn client_task(
                 client_return_tx.send(client).expect("To be able to return the client");
             }
 
-            let wait_time = std::time::Duration::from_secs(1);
-            log::debug!(wait_time:?; "Lost connection to MPD, waiting before trying again");
+            if stopped.load(Ordering::Relaxed) {
+                log::debug!("Client threads stopped");
+                break;
+            }
+
+            attempt += 1;
+            let wait_time = config.reconnect.delay(attempt);
+            log::debug!(wait_time:?, attempt; "Lost connection to MPD, waiting before trying again");
             try_skip!(
-                event_tx.send(AppEvent::LostConnection),
+                event_tx.send(AppEvent::LostConnection { attempt }),
                 "Failed to send lost connection event"
             );
-            std::thread::sleep(wait_time);
+            match control_rx.recv_timeout(wait_time) {
+                Ok(ClientControl::Stop) => {
+                    log::debug!("Client thread stopped while disconnected");
+                    break;
+                }
+                Ok(ClientControl::Reconnect) => {
+                    log::debug!(attempt; "Reconnect requested, skipping the remaining wait");
+                }
+                Err(_) => {}
+            }
         }
     });
 }
@@ -285,28 +399,55 @@ fn check_connection(
     first_loop: bool,
     client: &mut Client<'_>,
     client_rx: &Receiver<ClientRequest>,
+    pending: &Mutex<VecDeque<ClientRequest>>,
     event_tx: &Sender<AppEvent>,
     config: &Config,
 ) -> bool {
     if first_loop {
-        true
-    } else if client.reconnect().is_ok() {
-        // empty the work queue after reconnect as they might no longer be
-        // relevant
-        let _ = client_rx.try_iter().collect::<Vec<_>>();
-        try_skip!(event_tx.send(AppEvent::Reconnected), "Failed to send reconnected event");
-        if let Err(err) = client.set_read_timeout(Some(config.mpd_read_timeout)) {
-            log::error!(error:? = err; "Failed to set read timeout");
-            return false;
-        }
-        if let Err(err) = client.set_write_timeout(Some(config.mpd_write_timeout)) {
-            log::error!(error:? = err; "Failed to set write timeout");
-            return false;
+        return true;
+    }
+    if let Err(err) = reconnect(client, config) {
+        log::debug!(error:? = err; "Failed to reconnect");
+        return false;
+    }
+
+    // Commands which were not sent before the connection was lost and those
+    // issued while disconnected are replayed in order. Queries are dropped because their
+    // results are likely stale, everything is fetched again after the
+    // reconnected event anyway.
+    let mut pending = pending.lock().expect("Pending requests lock not to be poisoned");
+    pending.extend(client_rx.try_iter());
+    pending.retain(|request| matches!(request, ClientRequest::Command(_)));
+    if !pending.is_empty() {
+        log::debug!(count = pending.len(); "Replaying commands issued while disconnected");
+        status_info!("Replaying {} command(s) issued while disconnected", pending.len());
+    }
+    while let Some(command) = pending.pop_front() {
+        match handle_client_request(client, command) {
+            Err(err) if is_timeout(&err) => {
+                status_error!(err:?; "Replaying commands timed out, will try to reconnect");
+                return false;
+            }
+            result => {
+                try_skip!(
+                    event_tx.send(AppEvent::WorkDone(result)),
+                    "Failed to send work done event"
+                );
+            }
         }
-        true
-    } else {
-        false
     }
+
+    try_skip!(event_tx.send(AppEvent::Reconnected), "Failed to send reconnected event");
+    true
+}
+
+/// Replaces the connection of the client with a new one and returns its
+/// stream for writing
+fn reconnect(client: &mut Client<'_>, config: &Config) -> Result<TcpOrUnixStream> {
+    client.reconnect()?;
+    client.set_read_timeout(Some(config.mpd_read_timeout))?;
+    client.set_write_timeout(Some(config.mpd_write_timeout))?;
+    Ok(client.stream.try_clone()?)
 }
 
 fn handle_client_request(client: &mut Client<'_>, request: ClientRequest) -> Result<WorkDone> {
@@ -327,3 +468,98 @@ fn handle_client_request(client: &mut Client<'_>, request: ClientRequest) -> Res
         }
     }
 }
+
+/// Whether reading the response timed out and the connection is likely broken
+fn is_timeout(err: &anyhow::Error) -> bool {
+    matches!(err.downcast_ref::<MpdError>(), Some(MpdError::TimedOut(_)))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
+
+    use crossbeam::channel::unbounded;
+
+    use crate::{
+        config::Config,
+        mpd::{client::Client, mpd_client::MpdClient},
+        shared::{
+            events::{AppEvent, ClientRequest, WorkDone},
+            mpd_query::MpdCommand,
+        },
+        tests::fixtures::mpd_server::FakeMpdServer,
+    };
+
+    /// Answers every command with `OK` except `play` on the first
+    /// connection, which is left unanswered so the client times out
+    fn answer(connection: usize) -> impl FnMut(&str) -> Option<String> + Send + 'static {
+        let mut idle = false;
+        move |line| match line {
+            "idle" => {
+                idle = true;
+                None
+            }
+            "noidle" if !idle => None,
+            "noidle" => {
+                idle = false;
+                Some("OK\n".to_owned())
+            }
+            "play" if connection == 0 => None,
+            _ => Some("OK\n".to_owned()),
+        }
+    }
+
+    #[test]
+    fn timed_out_command_is_not_repeated_but_buffered_commands_are() {
+        let server = FakeMpdServer::start("replay", answer);
+        let client = Client::init(server.address(), None, "test").unwrap();
+        let config = Config {
+            mpd_read_timeout: Duration::from_millis(200),
+            mpd_write_timeout: Duration::from_millis(200),
+            ..Config::default()
+        };
+        let (client_tx, client_rx) = unbounded();
+        let (event_tx, event_rx) = unbounded();
+        let mut thread = super::init(client_rx, event_tx, client, Arc::new(config)).unwrap();
+
+        let plays = Arc::new(AtomicUsize::new(0));
+        let clears = Arc::new(AtomicUsize::new(0));
+        let (plays2, clears2) = (Arc::clone(&plays), Arc::clone(&clears));
+        client_tx
+            .send(ClientRequest::Command(MpdCommand {
+                callback: Box::new(move |client| {
+                    plays2.fetch_add(1, Ordering::Relaxed);
+                    Ok(client.play()?)
+                }),
+            }))
+            .unwrap();
+        client_tx
+            .send(ClientRequest::Command(MpdCommand {
+                callback: Box::new(move |client| {
+                    clears2.fetch_add(1, Ordering::Relaxed);
+                    Ok(client.clear()?)
+                }),
+            }))
+            .unwrap();
+
+        let mut done = 0;
+        while done < 1 {
+            match event_rx.recv_timeout(Duration::from_secs(5)).unwrap() {
+                AppEvent::WorkDone(Ok(WorkDone::None)) => done += 1,
+                AppEvent::WorkDone(Err(err)) => panic!("Command failed: {err}"),
+                _ => {}
+            }
+        }
+        thread.stop();
+
+        assert_eq!(plays.load(Ordering::Relaxed), 1);
+        assert_eq!(clears.load(Ordering::Relaxed), 1);
+    }
+}
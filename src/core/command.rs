@@ -5,18 +5,29 @@ use itertools::Itertools;
 
 use crate::{
     config::{
-        cli::{Command, StickerCmd},
+        cli::{Command, PartitionCmd, PlaylistCmd, QueueCmd, StickerCmd},
         cli_config::CliConfig,
+        rating::RatingChange,
     },
     context::AppContext,
     mpd::{
         client::Client,
         commands::{IdleEvent, mpd_config::MpdConfig, volume::Bound},
-        mpd_client::{Filter, MpdClient, Tag},
+        mpd_client::{
+            Filter,
+            MpdClient,
+            PlaybackRange,
+            QueueMoveTarget,
+            SingleOrRange,
+            Tag,
+            ValueChange,
+        },
     },
     shared::{
+        ext::mpd_client::MpdClientExt,
         lrc::LrcIndex,
         macros::{status_error, status_info},
+        playlist_file::{self, PlaylistFormat},
         ytdlp::YtDlp,
     },
 };
@@ -103,6 +114,48 @@ impl Command {
             Command::Consume { value } => {
                 Ok(Box::new(move |client| Ok(client.consume((value).into())?)))
             }
+            Command::Crossfade { value: Some(value) } => Ok(Box::new(move |client| {
+                let current = client.get_status()?.xfade.unwrap_or(0);
+                let crossfade = match value.parse()? {
+                    ValueChange::Increase(v) => current.saturating_add(v),
+                    ValueChange::Decrease(v) => current.saturating_sub(v),
+                    ValueChange::Set(v) => v,
+                };
+                Ok(client.crossfade(crossfade)?)
+            })),
+            Command::Crossfade { value: None } => Ok(Box::new(|client| {
+                println!("{}", client.get_status()?.xfade.unwrap_or(0));
+                Ok(())
+            })),
+            Command::MixRamp { db: None, delay: None } => Ok(Box::new(|client| {
+                let status = client.get_status()?;
+                println!("db: {}", status.mixrampdb.as_deref().unwrap_or("0"));
+                println!("delay: {}", status.mixrampdelay.as_deref().unwrap_or("off"));
+                Ok(())
+            })),
+            Command::MixRamp { db, delay } => {
+                let delay = match delay.as_deref() {
+                    Some("off") => Some(None),
+                    Some(delay) => Some(Some(delay.parse::<f32>()?)),
+                    None => None,
+                };
+                Ok(Box::new(move |client| {
+                    if let Some(db) = db {
+                        client.mixramp_db(db)?;
+                    }
+                    if let Some(delay) = delay {
+                        client.mixramp_delay(delay)?;
+                    }
+                    Ok(())
+                }))
+            }
+            Command::ReplayGain { mode: Some(mode) } => {
+                Ok(Box::new(move |client| Ok(client.replay_gain_mode(mode.into())?)))
+            }
+            Command::ReplayGain { mode: None } => Ok(Box::new(|client| {
+                println!("{}", client.replay_gain_status()?.to_mpd_value());
+                Ok(())
+            })),
             Command::Seek { value } => {
                 Ok(Box::new(move |client| Ok(client.seek_current(value.parse()?)?)))
             }
@@ -246,6 +299,15 @@ impl Command {
                 println!("{}", serde_json::ser::to_string(&client.decoders()?)?);
                 Ok(())
             })),
+            Command::Stats { group: None } => Ok(Box::new(|client| {
+                println!("{}", serde_json::ser::to_string(&client.stats()?)?);
+                Ok(())
+            })),
+            Command::Stats { group: Some(group) } => Ok(Box::new(move |client| {
+                let counts = client.count(&[], Some(Tag::Custom(group)))?;
+                println!("{}", serde_json::ser::to_string(&counts)?);
+                Ok(())
+            })),
             Command::Outputs => Ok(Box::new(|client| {
                 println!("{}", serde_json::ser::to_string(&client.outputs()?)?);
                 Ok(())
@@ -259,8 +321,239 @@ impl Command {
             Command::DisableOutput { id } => {
                 Ok(Box::new(move |client| Ok(client.disable_output(id)?)))
             }
+            Command::Partition { cmd: PartitionCmd::List } => Ok(Box::new(|client| {
+                println!("{}", serde_json::ser::to_string(&client.list_partitions()?)?);
+                Ok(())
+            })),
+            Command::Partition { cmd: PartitionCmd::New { name } } => {
+                Ok(Box::new(move |client| Ok(client.new_partition(&name)?)))
+            }
+            Command::Partition { cmd: PartitionCmd::Delete { name } } => {
+                Ok(Box::new(move |client| Ok(client.delete_partition(&name)?)))
+            }
+            Command::Partition { cmd: PartitionCmd::MoveOutput { output, partition } } => {
+                Ok(Box::new(move |client| {
+                    // moveoutput only moves to the current partition, switch back afterwards
+                    // so the command does not change the partition of the running instance
+                    let previous = client.partition.clone().unwrap_or_else(|| "default".to_owned());
+                    client.switch_to_partition(&partition)?;
+                    client.move_output(&output)?;
+                    client.switch_to_partition(&previous)?;
+                    Ok(())
+                }))
+            }
+            Command::Playlist { cmd: PlaylistCmd::List } => Ok(Box::new(|client| {
+                println!("{}", serde_json::ser::to_string(&client.list_playlists()?)?);
+                Ok(())
+            })),
+            Command::Playlist { cmd: PlaylistCmd::Show { name } } => Ok(Box::new(move |client| {
+                println!(
+                    "{}",
+                    serde_json::ser::to_string(&client.list_playlist_info(&name, None)?)?
+                );
+                Ok(())
+            })),
+            Command::Playlist { cmd: PlaylistCmd::Load { name } } => {
+                Ok(Box::new(move |client| Ok(client.load_playlist(&name)?)))
+            }
+            Command::Playlist { cmd: PlaylistCmd::Save { name, mode } } => {
+                Ok(Box::new(move |client| {
+                    Ok(client.save_queue_as_playlist(&name, mode.map(Into::into))?)
+                }))
+            }
+            Command::Playlist { cmd: PlaylistCmd::Rename { name, new_name } } => {
+                Ok(Box::new(move |client| Ok(client.rename_playlist(&name, &new_name)?)))
+            }
+            Command::Playlist { cmd: PlaylistCmd::Delete { name } } => {
+                Ok(Box::new(move |client| Ok(client.delete_playlist(&name)?)))
+            }
+            Command::Playlist { cmd: PlaylistCmd::Add { name, uris, position } } => {
+                Ok(Box::new(move |client| {
+                    for (idx, uri) in uris.iter().enumerate() {
+                        client.add_to_playlist(&name, uri, position.map(|pos| pos + idx))?;
+                    }
+                    Ok(())
+                }))
+            }
+            Command::Playlist { cmd: PlaylistCmd::Remove { name, range } } => {
+                let range = range.parse::<SingleOrRange>()?;
+                Ok(Box::new(move |client| Ok(client.delete_from_playlist(&name, &range)?)))
+            }
+            Command::Playlist { cmd: PlaylistCmd::Move { name, range, to } } => {
+                let range = range.parse::<SingleOrRange>()?;
+                Ok(Box::new(move |client| Ok(client.move_in_playlist(&name, &range, to)?)))
+            }
+            Command::Playlist { cmd: PlaylistCmd::Export { name, format, output, absolute } } => {
+                let format = format
+                    .map(PlaylistFormat::from)
+                    .or_else(|| output.as_deref().and_then(PlaylistFormat::from_path))
+                    .unwrap_or(PlaylistFormat::M3u8);
+                Ok(Box::new(move |client| {
+                    let content = client.export_playlist(&name, format, absolute)?;
+                    match output {
+                        Some(output) => std::fs::write(output, content)?,
+                        None => print!("{content}"),
+                    }
+                    Ok(())
+                }))
+            }
+            Command::Playlist { cmd: PlaylistCmd::Import { file, name } } => {
+                let file = std::fs::canonicalize(&file)?;
+                let entries = playlist_file::read(&file)?;
+                let Some(name) =
+                    name.or_else(|| Some(file.file_stem()?.to_string_lossy().into_owned()))
+                else {
+                    bail!("Cannot determine playlist name from '{}'", file.display());
+                };
+                Ok(Box::new(move |client| {
+                    let result = client.import_playlist(&name, &entries, file.parent())?;
+                    println!(
+                        "Imported {} of {} songs into playlist '{}'",
+                        result.added,
+                        entries.len(),
+                        result.name
+                    );
+                    for location in result.missing {
+                        println!("Not found: {location}");
+                    }
+                    Ok(())
+                }))
+            }
+            Command::Search {
+                any,
+                artist,
+                album,
+                album_artist,
+                title,
+                genre,
+                tag,
+                filter,
+                mode,
+                case_sensitive,
+                ignore_case,
+                add,
+                insert_next,
+            } => {
+                let kind = mode.map_or(config.search.mode, Into::into);
+                let case_sensitive = if case_sensitive || ignore_case {
+                    case_sensitive
+                } else {
+                    config.search.case_sensitive
+                };
+                let mut tags = [
+                    (Tag::Any, any),
+                    (Tag::Artist, artist),
+                    (Tag::Album, album),
+                    (Tag::AlbumArtist, album_artist),
+                    (Tag::Title, title),
+                    (Tag::Genre, genre),
+                ]
+                .into_iter()
+                .filter_map(|(tag, value)| Some((tag, value?)))
+                .collect_vec();
+                for tag in tag {
+                    let Some((name, value)) = tag.split_once('=') else {
+                        bail!("Invalid tag filter '{tag}', expected format is 'tag=value'");
+                    };
+                    tags.push((Tag::Custom(name.trim().to_owned()), value.to_owned()));
+                }
+                if tags.is_empty() && filter.is_empty() {
+                    bail!("At least one tag or filter has to be given");
+                }
+
+                Ok(Box::new(move |client| {
+                    let filters = tags
+                        .iter()
+                        .map(|(tag, value)| Filter::new_with_kind(tag.clone(), value, kind))
+                        .chain(filter.iter().map(Filter::raw))
+                        .collect_vec();
+                    match (add, insert_next, case_sensitive) {
+                        (true, _, true) => client.find_add(&filters)?,
+                        (true, _, false) => client.search_add(&filters)?,
+                        (_, true, true) => client.find_add_next(&filters)?,
+                        (_, true, false) => client.search_add_next(&filters)?,
+                        (false, false, true) => {
+                            println!("{}", serde_json::ser::to_string(&client.find(&filters)?)?);
+                        }
+                        (false, false, false) => {
+                            println!("{}", serde_json::ser::to_string(&client.search(&filters)?)?);
+                        }
+                    }
+                    Ok(())
+                }))
+            }
+            Command::Queue { cmd: QueueCmd::List } => Ok(Box::new(|client| {
+                let queue = client.playlist_info(false)?.unwrap_or_default();
+                println!("{}", serde_json::ser::to_string(&queue)?);
+                Ok(())
+            })),
+            Command::Queue { cmd: QueueCmd::Play { id } } => {
+                Ok(Box::new(move |client| Ok(client.play_id(id)?)))
+            }
+            Command::Queue { cmd: QueueCmd::Remove { songs, id: true } } => {
+                let ids = songs
+                    .iter()
+                    .map(|id| id.trim().parse::<u32>())
+                    .try_collect::<_, Vec<_>, _>()?;
+                Ok(Box::new(move |client| {
+                    for id in ids {
+                        client.delete_id(id)?;
+                    }
+                    Ok(())
+                }))
+            }
+            Command::Queue { cmd: QueueCmd::Remove { songs, id: false } } => {
+                let mut ranges = songs
+                    .iter()
+                    .map(|range| range.parse::<SingleOrRange>())
+                    .try_collect::<_, Vec<_>, _>()?;
+                // Delete from the end so the positions of the remaining ranges do not shift
+                ranges.sort_by_key(|range| std::cmp::Reverse(range.start));
+                Ok(Box::new(move |client| {
+                    for range in ranges {
+                        client.delete_from_queue(range)?;
+                    }
+                    Ok(())
+                }))
+            }
+            Command::Queue { cmd: QueueCmd::Move { from, to, id } } => {
+                let to = to.parse::<QueueMoveTarget>()?;
+                if id {
+                    let id = from.trim().parse::<u32>()?;
+                    Ok(Box::new(move |client| Ok(client.move_id(id, to)?)))
+                } else {
+                    let from = from.parse::<SingleOrRange>()?;
+                    Ok(Box::new(move |client| Ok(client.move_in_queue(from, to)?)))
+                }
+            }
+            Command::Queue { cmd: QueueCmd::Swap { first, second, id: true } } => {
+                Ok(Box::new(move |client| Ok(client.swap_id(first, second)?)))
+            }
+            Command::Queue { cmd: QueueCmd::Swap { first, second, id: false } } => {
+                Ok(Box::new(move |client| Ok(client.swap(first as usize, second as usize)?)))
+            }
+            Command::Queue { cmd: QueueCmd::Shuffle { range } } => {
+                let range = range.map(|range| range.parse::<SingleOrRange>()).transpose()?;
+                Ok(Box::new(move |client| Ok(client.shuffle(range)?)))
+            }
+            Command::Queue { cmd: QueueCmd::Crop } => Ok(Box::new(|client| {
+                let status = client.get_status()?;
+                let Some(current) = status.song else {
+                    bail!("No song is currently playing");
+                };
+                let (current, len) = (current as usize, status.playlistlength as usize);
+                if current + 1 < len {
+                    client.delete_from_queue(SingleOrRange::range(current + 1, len))?;
+                }
+                if current > 0 {
+                    client.delete_from_queue(SingleOrRange::range(0, current))?;
+                }
+                Ok(())
+            })),
             Command::Status => Ok(Box::new(|client| {
-                println!("{}", serde_json::ser::to_string(&client.get_status()?)?);
+                let mut status = client.get_status()?;
+                status.replay_gain = client.replay_gain_status().ok();
+                println!("{}", serde_json::ser::to_string(&status)?);
                 Ok(())
             })),
             Command::Song { path: Some(paths) } if paths.len() == 1 => {
@@ -297,6 +590,21 @@ impl Command {
                     std::process::exit(1);
                 }
             })),
+            Command::Rate { value, like: _ } => {
+                let rating = config.rating.clone();
+                Ok(Box::new(move |client| {
+                    let Some(song) = client.get_current_song()? else {
+                        bail!("No song is currently playing");
+                    };
+                    // value and like are mutually exclusive
+                    if let Some(value) = value {
+                        client.rate(&song.file, RatingChange::Set(value), &rating)?;
+                    } else {
+                        client.toggle_like(&song.file, &rating.like_sticker)?;
+                    }
+                    Ok(())
+                }))
+            }
             Command::Mount { name, path } => {
                 Ok(Box::new(move |client| Ok(client.mount(&name, &path)?)))
             }
@@ -305,6 +613,48 @@ impl Command {
                 println!("{}", serde_json::ser::to_string(&client.list_mounts()?)?);
                 Ok(())
             })),
+            Command::ListNeighbors => Ok(Box::new(|client| {
+                println!("{}", serde_json::ser::to_string(&client.list_neighbors()?)?);
+                Ok(())
+            })),
+            Command::SendMessage { channel, message } => {
+                Ok(Box::new(move |client| Ok(client.send_message(&channel, &message)?)))
+            }
+            Command::Channels => Ok(Box::new(|client| {
+                println!("{}", serde_json::ser::to_string(&client.channels()?)?);
+                Ok(())
+            })),
+            Command::Priority { value, id, range } => {
+                let range = range.map(|range| range.parse::<SingleOrRange>()).transpose()?;
+                Ok(Box::new(move |client| {
+                    if let Some(range) = range {
+                        client.set_priority(value, range)?;
+                    } else if !id.is_empty() {
+                        client.set_priority_id(value, &id)?;
+                    } else {
+                        let Some(song) = client.get_current_song()? else {
+                            bail!("No song is currently playing");
+                        };
+                        client.set_priority_id(value, &[song.id])?;
+                    }
+                    Ok(())
+                }))
+            }
+            Command::Range { range, id } => {
+                let range = range.parse::<PlaybackRange>()?;
+                Ok(Box::new(move |client| {
+                    let id = if let Some(id) = id {
+                        id
+                    } else {
+                        let Some(song) = client.get_current_song()? else {
+                            bail!("No song is currently playing");
+                        };
+                        song.id
+                    };
+                    client.set_range_id(id, range)?;
+                    Ok(())
+                }))
+            }
             Command::AlbumArt { output } => Ok(Box::new(move |client| {
                 let Some(song) = client.get_current_song()? else {
                     std::process::exit(3);
@@ -59,7 +59,7 @@ pub(crate) fn init(
                 log::debug!(event:?; "File event");
                 if let Err(err) = ConfigFile::read(&config_path)
                     .and_then(|config| {
-                        config.into_config(Some(&config_path), None, None, None, true)
+                        config.into_config(Some(&config_path), None, None, None, None, true)
                     })
                     .inspect(|config| {
                         theme_name = config.theme_name.as_ref().map(|c| format!("{c}.ron"));
@@ -1,9 +1,9 @@
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     io::Stdout,
     ops::Sub,
     sync::{Arc, LazyLock},
-    time::Duration,
+    time::{Duration, Instant, SystemTime},
 };
 
 use crossbeam::channel::{Receiver, RecvTimeoutError};
@@ -14,47 +14,66 @@ use ratatui::{
     prelude::{Backend, CrosstermBackend},
 };
 
-use super::command::{create_env, run_external};
+use super::{
+    client::ClientThread,
+    command::{create_env, run_external},
+    playback_stats::{PlaybackOutcome, PlaybackStatsTracker},
+};
 use crate::{
     context::AppContext,
     mpd::{
-        commands::{IdleEvent, State},
+        commands::{ConnectionState, IdleEvent, Message, State},
         mpd_client::MpdClient,
     },
     shared::{
         events::{AppEvent, WorkDone},
-        ext::{duration::DurationExt, error::ErrorExt},
+        ext::{duration::DurationExt, error::ErrorExt, mpd_client::MpdClientExt},
+        history::HistoryEntry,
         id::{self, Id},
+        key_event::{InjectedAction, KeyEvent},
         lrc::get_lrc_path,
-        macros::{status_error, status_warn},
+        macros::{status_error, status_info, status_warn},
         mpd_query::{
             EXTERNAL_COMMAND,
+            GLOBAL_MESSAGES,
             GLOBAL_QUEUE_UPDATE,
             GLOBAL_STATUS_UPDATE,
             GLOBAL_VOLUME_UPDATE,
             MpdQueryResult,
             run_status_update,
         },
+        queue_changes,
+    },
+    ui::{
+        KeyHandleResult,
+        Ui,
+        UiEvent,
+        modals::{
+            mounts::{self, REFRESH_MOUNTS},
+            partitions::REFRESH_PARTITIONS,
+        },
     },
-    ui::{KeyHandleResult, Ui, UiEvent},
 };
 
 static ON_RESIZE_SCHEDULE_ID: LazyLock<Id> = LazyLock::new(id::new);
+static KEY_SEQUENCE_SCHEDULE_ID: LazyLock<Id> = LazyLock::new(id::new);
 
 pub fn init(
     context: AppContext,
     event_rx: Receiver<AppEvent>,
     terminal: Terminal<CrosstermBackend<Stdout>>,
+    client_thread: ClientThread,
 ) -> std::io::Result<std::thread::JoinHandle<Terminal<CrosstermBackend<Stdout>>>> {
     std::thread::Builder::new()
         .name("main".to_owned())
-        .spawn(move || main_task(context, event_rx, terminal))
+        .spawn(move || main_task(context, event_rx, terminal, client_thread))
 }
 
 fn main_task<B: Backend + std::io::Write>(
     mut context: AppContext,
     event_rx: Receiver<AppEvent>,
     mut terminal: Terminal<B>,
+    mut client_thread: ClientThread,
 ) -> Terminal<B> {
     let size = terminal.size().expect("To be able to get terminal size");
     let area = Rect::new(0, 0, size.width, size.height);
@@ -65,9 +84,12 @@ fn main_task<B: Backend + std::io::Write>(
     let mut min_frame_duration = Duration::from_secs_f64(1f64 / max_fps);
     let mut last_render = std::time::Instant::now().sub(Duration::from_secs(10));
     let mut additional_evs = HashSet::new();
-    let mut connected = true;
     ui.before_show(area, &mut context).expect("Initial render init to succeed");
     let mut _update_loop_guard = None;
+    let mut playback_stats = PlaybackStatsTracker::default();
+    // Song which was last written to the history. The song already playing when
+    // rmpc starts or switches to another server was logged when it started.
+    let mut history_song_id = context.find_current_song_in_queue().map(|(_, song)| song.id);
 
     // Tmux hooks have to be initialized after ui, because ueberzugpp replaces all
     // hooks on its init instead of simply appending and might break rmpc's hooks
@@ -111,17 +133,37 @@ fn main_task<B: Backend + std::io::Write>(
                     // Techical limitation. Keep the old image backend because it was not rechecked
                     // anyway. Sending the escape sequences to determine image support would mess up
                     // the terminal output at this point.
-                    new_config.album_art.method = context.config.album_art.method;
+                    new_config.album_art.method = context.user_config.album_art.method;
                     if keep_old_theme {
                         new_config.theme = context.config.theme.clone();
                     }
+                    // Reloaded config does not reconnect, keep the address of the server rmpc is
+                    // connected to, which might come from the cli or a switched profile
+                    new_config.address.clone_from(&context.config.address);
+                    new_config.password.clone_from(&context.config.password);
+                    new_config.active_profile.clone_from(&context.config.active_profile);
 
                     if let Err(err) = new_config.validate() {
                         status_error!(error:? = err; "Cannot change config, invalid value: '{err}'");
                         continue;
                     }
+                    let user_config = new_config.clone();
+                    if let Err(err) = AppContext::disable_unsupported_features(
+                        &mut new_config,
+                        &context.supported_commands,
+                    ) {
+                        status_error!(error:? = err; "Cannot change config: '{err}'");
+                        continue;
+                    }
+
+                    sync_subscriptions(
+                        &context.config.messages.channels,
+                        &new_config.messages.channels,
+                        &context,
+                    );
 
                     context.config = Arc::new(new_config);
+                    context.user_config = Arc::new(user_config);
                     let max_fps = f64::from(context.config.max_fps);
                     min_frame_duration = Duration::from_secs_f64(1f64 / max_fps);
 
@@ -141,12 +183,15 @@ fn main_task<B: Backend + std::io::Write>(
                 }
                 AppEvent::ThemeChanged { theme } => {
                     let mut config = context.config.as_ref().clone();
-                    config.theme = theme;
+                    config.theme = theme.clone();
                     if let Err(err) = config.validate() {
                         status_error!(error:? = err; "Cannot change theme, invalid config: '{err}'");
                         continue;
                     }
                     context.config = Arc::new(config);
+                    let mut user_config = context.user_config.as_ref().clone();
+                    user_config.theme = theme;
+                    context.user_config = Arc::new(user_config);
 
                     if let Err(err) = ui.on_event(UiEvent::ConfigChanged, &mut context) {
                         log::error!(error:? = err; "UI failed to handle config changed event");
@@ -160,19 +205,36 @@ fn main_task<B: Backend + std::io::Write>(
                     }
                     render_wanted = true;
                 }
-                AppEvent::UserKeyInput(key) => match ui.handle_key(&mut key.into(), &mut context) {
-                    Ok(KeyHandleResult::None) => continue,
-                    Ok(KeyHandleResult::Quit) => {
-                        if let Err(err) = ui.on_event(UiEvent::Exit, &mut context) {
-                            log::error!(error:? = err, event:?; "UI failed to handle quit event");
-                        }
-                        break;
+                AppEvent::IpcQuery { command, tx } => {
+                    if let Err(err) = tx.send(ui.handle_ipc_query(command, &context)) {
+                        log::error!(error:? = err, command:?; "Failed to send IPC query response");
                     }
-                    Err(err) => {
-                        status_error!(err:?; "Error: {}", err.to_status());
+                }
+                AppEvent::UserKeyInput(mut key) => {
+                    if key.is_sequence_timeout() && context.pending_keys.is_empty() {
+                        // The sequence was already finished by another key
+                        continue;
+                    }
+
+                    let result = ui.handle_key(&mut key, &mut context);
+                    if update_pending_keys(&key, &mut context) {
                         render_wanted = true;
                     }
-                },
+
+                    match result {
+                        Ok(KeyHandleResult::None) => continue,
+                        Ok(KeyHandleResult::Quit) => {
+                            if let Err(err) = ui.on_event(UiEvent::Exit, &mut context) {
+                                log::error!(error:? = err, key:?; "UI failed to handle quit event");
+                            }
+                            break;
+                        }
+                        Err(err) => {
+                            status_error!(err:?; "Error: {}", err.to_status());
+                            render_wanted = true;
+                        }
+                    }
+                }
                 AppEvent::UserMouseInput(ev) => match ui.handle_mouse_event(ev, &mut context) {
                     Ok(()) => {}
                     Err(err) => {
@@ -228,9 +290,25 @@ fn main_task<B: Backend + std::io::Write>(
                             let current_song_id =
                                 context.find_current_song_in_queue().map(|(_, song)| song.id);
                             let current_status = context.status.state;
+                            let replay_gain = context.status.replay_gain;
+                            let connection = context.status.connection;
                             context.status = status;
+                            // ReplayGain mode is only queried when the options change
+                            context.status.replay_gain = context.status.replay_gain.or(replay_gain);
+                            context.status.connection = connection;
                             let mut song_changed = false;
 
+                            if context.config.record_playback_stats {
+                                let outcome = playback_stats.update(
+                                    &context.status,
+                                    context.find_current_song_in_queue().map(|(_, song)| song),
+                                    Instant::now(),
+                                );
+                                if let Some(outcome) = outcome {
+                                    record_playback_outcome(outcome, &context);
+                                }
+                            }
+
                             match context.status.state {
                                 State::Play => {
                                     if current_status != context.status.state {
@@ -254,6 +332,15 @@ fn main_task<B: Backend + std::io::Write>(
                                 }
                             }
 
+                            let mut history_entry = None;
+                            match context.find_current_song_in_queue() {
+                                Some((_, song)) if Some(song.id) != history_song_id => {
+                                    history_song_id = Some(song.id);
+                                    history_entry = Some(HistoryEntry::new(song));
+                                }
+                                Some(_) => {}
+                                None => history_song_id = None,
+                            }
                             if let Some((_, song)) = context.find_current_song_in_queue() {
                                 if Some(song.id) != current_song_id {
                                     if let Some(command) = &context.config.on_song_change {
@@ -270,13 +357,15 @@ fn main_task<B: Backend + std::io::Write>(
                                             .duration
                                             .map_or_else(String::new, |d| d.to_string());
 
+                                        let separator = &context.config.theme.format_tag_separator;
                                         let mut env = song
-                                            .clone()
                                             .metadata
-                                            .into_iter()
-                                            .map(|(mut k, v)| {
-                                                k.make_ascii_uppercase();
-                                                (k, v)
+                                            .iter()
+                                            .map(|(k, v)| {
+                                                (
+                                                    k.to_ascii_uppercase(),
+                                                    v.join(separator).into_owned(),
+                                                )
                                             })
                                             .collect_vec();
 
@@ -294,6 +383,9 @@ fn main_task<B: Backend + std::io::Write>(
                                     song_changed = true;
                                 }
                             }
+                            if let Some(entry) = history_entry {
+                                context.history.push(entry);
+                            }
                             if song_changed {
                                 if let Err(err) = ui.on_event(UiEvent::SongChanged, &mut context) {
                                     status_error!(error:? = err; "UI failed to handle idle event, error: '{}'", err.to_status());
@@ -305,8 +397,43 @@ fn main_task<B: Backend + std::io::Write>(
                             context.status.volume = volume;
                             render_wanted = true;
                         }
-                        ("global_queue_update", None, MpdQueryResult::Queue(queue)) => {
+                        (GLOBAL_QUEUE_UPDATE, None, MpdQueryResult::Queue { version, queue }) => {
                             context.queue = queue.unwrap_or_default();
+                            context.queue_version = version;
+                            render_wanted = true;
+                        }
+                        (GLOBAL_QUEUE_UPDATE, None, MpdQueryResult::QueueChanges(changes)) => {
+                            // Changes relative to an older version than the current one
+                            // can still be applied, they already contain everything
+                            // that changed since. A pending full fetch supersedes them.
+                            let Some(current_version) = context.queue_version else {
+                                continue;
+                            };
+                            if changes.from_version > current_version {
+                                continue;
+                            }
+                            let version = changes.version;
+                            if queue_changes::apply(&mut context.queue, changes) {
+                                context.queue_version = Some(version);
+                                render_wanted = true;
+                            } else {
+                                context.queue_version = None;
+                                fetch_queue(&context);
+                            }
+                        }
+                        (GLOBAL_QUEUE_UPDATE, None, MpdQueryResult::QueueStickers(stickers)) => {
+                            if queue_changes::apply_stickers(&mut context.queue, stickers) {
+                                render_wanted = true;
+                            }
+                        }
+                        (GLOBAL_MESSAGES, None, MpdQueryResult::Messages(messages)) => {
+                            for message in &messages {
+                                handle_message(message, &context);
+                            }
+                            if let Err(err) = ui.on_event(UiEvent::Messages(messages), &mut context)
+                            {
+                                log::error!(error:? = err; "UI failed to handle messages event");
+                            }
                             render_wanted = true;
                         }
                         (
@@ -324,6 +451,47 @@ fn main_task<B: Backend + std::io::Write>(
                             }
                         }
                     },
+                    WorkDone::ProfileConnected { profile, mut client } => {
+                        let name = profile.name.clone();
+                        if let Err(err) = client
+                            .set_read_timeout(Some(context.config.mpd_read_timeout))
+                            .and_then(|()| {
+                                client.set_write_timeout(Some(context.config.mpd_write_timeout))
+                            })
+                        {
+                            status_error!(err:?; "Failed to set timeouts for profile '{name}'");
+                            continue;
+                        }
+                        if let Err(err) = context.switch_server(&mut client, profile) {
+                            status_error!(err:?; "Failed to switch to profile '{name}': {err}");
+                            continue;
+                        }
+                        history_song_id = client
+                            .get_status()
+                            .ok()
+                            .filter(|status| status.state != State::Stop)
+                            .and_then(|status| status.songid);
+                        if let Err(err) = client_thread.restart(client, Arc::clone(&context.config))
+                        {
+                            status_error!(err:?; "Failed to start client for profile '{name}'");
+                            continue;
+                        }
+
+                        // Stop the status updates and recorded playback of the previous server,
+                        // the fresh status decides whether they start again
+                        _update_loop_guard = None;
+                        context.status.state = State::Stop;
+                        playback_stats = PlaybackStatsTracker::default();
+                        for ev in [IdleEvent::Player, IdleEvent::Playlist, IdleEvent::Options] {
+                            handle_idle_event(ev, &context, &mut additional_evs);
+                        }
+                        if let Err(err) = ui.on_event(UiEvent::Reconnected, &mut context) {
+                            log::error!(error:? = err; "UI failed to handle profile switch");
+                        }
+                        status_info!("Switched to profile '{name}'");
+                        context.status.connection = ConnectionState::Connected;
+                        render_wanted = true;
+                    }
                     WorkDone::None => {}
                 },
                 AppEvent::WorkDone(Err(err)) => {
@@ -362,6 +530,8 @@ fn main_task<B: Backend + std::io::Write>(
                     }
                 },
                 AppEvent::Reconnected => {
+                    // Song ids may have been reused by a restarted server
+                    context.queue_version = None;
                     for ev in [IdleEvent::Player, IdleEvent::Playlist, IdleEvent::Options] {
                         handle_idle_event(ev, &context, &mut additional_evs);
                     }
@@ -369,17 +539,42 @@ fn main_task<B: Backend + std::io::Write>(
                         log::error!(error:? = err, event:?; "UI failed to handle resize event");
                     }
                     status_warn!("rmpc reconnected to MPD and will reinitialize");
-                    connected = true;
+                    context.status.connection = ConnectionState::Connected;
+                    render_wanted = true;
                 }
-                AppEvent::LostConnection => {
+                AppEvent::LostConnection { attempt } => {
                     if context.status.state != State::Stop {
                         _update_loop_guard = None;
                         context.status.state = State::Stop;
                     }
-                    if connected {
+                    if context.status.connection == ConnectionState::Connected {
                         status_error!("rmpc lost connection to MPD and will try to reconnect");
                     }
-                    connected = false;
+                    context.status.connection = ConnectionState::Reconnecting { attempt };
+                    render_wanted = true;
+                }
+                AppEvent::PartitionSwitched => {
+                    // Queue, status, mixer and outputs all belong to the partition
+                    // so everything has to be reloaded
+                    context.queue_version = None;
+                    for ev in [IdleEvent::Player, IdleEvent::Playlist, IdleEvent::Mixer] {
+                        handle_idle_event(ev, &context, &mut additional_evs);
+                    }
+                    context
+                        .query()
+                        .id("refresh_outputs")
+                        .replace_id("refresh_outputs")
+                        .query(move |client| Ok(MpdQueryResult::Outputs(client.outputs()?.0)));
+                    render_wanted = true;
+                }
+                AppEvent::ReconnectNow => {
+                    if context.status.connection == ConnectionState::Connected {
+                        status_info!("Already connected to MPD");
+                    } else {
+                        status_info!("Trying to reconnect to MPD");
+                        client_thread.reconnect_now();
+                    }
+                    render_wanted = true;
                 }
                 AppEvent::TmuxHook { hook } => {
                     if let Some(tmux) = &mut tmux {
@@ -429,6 +624,95 @@ fn main_task<B: Backend + std::io::Write>(
     terminal
 }
 
+/// Remembers the key if it started or continued a multi-key keybind or a count,
+/// otherwise the keybind is finished and the pending keys and count are
+/// discarded. Returns true if anything changed.
+fn update_pending_keys(key: &KeyEvent, context: &mut AppContext) -> bool {
+    if let Some(digit) = key.count_digit() {
+        let count = context.pending_count.unwrap_or(0);
+        context.pending_count = Some(count.saturating_mul(10).saturating_add(digit as usize));
+        true
+    } else if key.is_pending_sequence() {
+        context.pending_keys.push(key.key());
+        context.scheduler.schedule_replace(
+            *KEY_SEQUENCE_SCHEDULE_ID,
+            context.config.key_sequence_timeout,
+            |(tx, _)| {
+                tx.send(AppEvent::UserKeyInput(KeyEvent::sequence_timed_out()))?;
+                Ok(())
+            },
+        );
+        true
+    } else if !context.pending_keys.is_empty() || context.pending_count.is_some() {
+        context.pending_keys.clear();
+        context.pending_count = None;
+        true
+    } else {
+        false
+    }
+}
+
+/// Subscribes to the channels added to the config and unsubscribes from the
+/// removed ones
+fn sync_subscriptions(old: &[String], new: &[String], context: &AppContext) {
+    let unsubscribe = old.iter().filter(|channel| !new.contains(channel)).cloned().collect_vec();
+    let subscribe = new.iter().filter(|channel| !old.contains(channel)).cloned().collect_vec();
+    if unsubscribe.is_empty() && subscribe.is_empty() {
+        return;
+    }
+
+    context.command(move |client| {
+        for channel in &unsubscribe {
+            client.unsubscribe(channel)?;
+        }
+        for channel in &subscribe {
+            client.subscribe(channel)?;
+        }
+        Ok(())
+    });
+}
+
+/// Shows the message received on a subscribed channel and runs the action
+/// configured for it
+fn handle_message(message: &Message, context: &AppContext) {
+    log::debug!(message:?; "Received message");
+    if context.config.messages.show_in_status_bar {
+        status_info!("[{}] {}", message.channel, message.message);
+    }
+    if let Some(action) = context.config.messages.actions.get(&message.message) {
+        let event = AppEvent::UserKeyInput(InjectedAction::Global(action.clone()).into());
+        if let Err(err) = context.app_event_sender.send(event) {
+            log::error!(err:?, message:?; "Failed to run action for message");
+        }
+    }
+}
+
+fn record_playback_outcome(outcome: PlaybackOutcome, context: &AppContext) {
+    context.command(move |client| {
+        match &outcome {
+            PlaybackOutcome::Played(file) => {
+                let timestamp = SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or_default();
+                client.record_play(file, timestamp)?;
+            }
+            PlaybackOutcome::Skipped(file) => client.record_skip(file)?,
+        }
+        Ok(())
+    });
+}
+
+/// Fetches the whole queue together with its version, used whenever the
+/// queue cannot be updated incrementally
+fn fetch_queue(context: &AppContext) {
+    let fetch_stickers = context.should_fetch_stickers;
+    context.query().id(GLOBAL_QUEUE_UPDATE).replace_id("playlist").query(move |client| {
+        let version = client.get_status()?.playlist;
+        Ok(MpdQueryResult::Queue { version, queue: client.playlist_info(fetch_stickers)? })
+    });
+}
+
 fn handle_idle_event(event: IdleEvent, context: &AppContext, result_ui_evs: &mut HashSet<UiEvent>) {
     match event {
         IdleEvent::Mixer if context.supported_commands.contains("getvol") => {
@@ -446,11 +730,11 @@ fn handle_idle_event(event: IdleEvent, context: &AppContext, result_ui_evs: &mut
                 .query(move |client| Ok(MpdQueryResult::Status(client.get_status()?)));
         }
         IdleEvent::Options => {
-            context
-                .query()
-                .id(GLOBAL_STATUS_UPDATE)
-                .replace_id("status")
-                .query(move |client| Ok(MpdQueryResult::Status(client.get_status()?)));
+            context.query().id(GLOBAL_STATUS_UPDATE).replace_id("status").query(move |client| {
+                let mut status = client.get_status()?;
+                status.replay_gain = client.replay_gain_status().ok();
+                Ok(MpdQueryResult::Status(status))
+            });
         }
         IdleEvent::Player => {
             context
@@ -459,21 +743,70 @@ fn handle_idle_event(event: IdleEvent, context: &AppContext, result_ui_evs: &mut
                 .replace_id("status")
                 .query(move |client| Ok(MpdQueryResult::Status(client.get_status()?)));
         }
-        IdleEvent::Playlist | IdleEvent::Sticker => {
+        IdleEvent::Playlist => {
+            let Some(version) = context.queue_version else {
+                fetch_queue(context);
+                return;
+            };
             let fetch_stickers = context.should_fetch_stickers;
-            context.query().id(GLOBAL_QUEUE_UPDATE).replace_id("playlist").query(move |client| {
-                Ok(MpdQueryResult::Queue(client.playlist_info(fetch_stickers)?))
-            });
+            let known_positions: HashMap<u32, usize> =
+                context.queue.iter().enumerate().map(|(pos, song)| (song.id, pos)).collect();
+            context.query().id(GLOBAL_QUEUE_UPDATE).replace_id("playlist_changes").query(
+                move |client| {
+                    Ok(MpdQueryResult::QueueChanges(client.queue_changes(
+                        version,
+                        &known_positions,
+                        fetch_stickers,
+                    )?))
+                },
+            );
+        }
+        IdleEvent::Sticker if context.should_fetch_stickers => {
+            // MPD coalesces idle events so the event can also carry changes
+            // made by other clients along with those made by rmpc. Stickers
+            // of the whole queue are fetched again, only the changed ones
+            // are applied.
+            let songs = context.queue.iter().map(|song| (song.id, song.file.clone())).collect_vec();
+            context.query().id(GLOBAL_QUEUE_UPDATE).replace_id("queue_stickers").query(
+                move |client| {
+                    let uris = songs.iter().map(|(_, file)| file.as_str()).collect_vec();
+                    let stickers = client.list_stickers_multiple(&uris)?;
+                    Ok(MpdQueryResult::QueueStickers(
+                        songs.into_iter().map(|(id, _)| id).zip(stickers).collect(),
+                    ))
+                },
+            );
+        }
+        IdleEvent::Sticker => {}
+        IdleEvent::Message => {
+            context
+                .query()
+                .id(GLOBAL_MESSAGES)
+                .replace_id("messages")
+                .query(move |client| Ok(MpdQueryResult::Messages(client.read_messages()?.0)));
         }
         IdleEvent::StoredPlaylist => {}
         IdleEvent::Database => {}
         IdleEvent::Update => {}
-        IdleEvent::Output
-        | IdleEvent::Partition
-        | IdleEvent::Subscription
-        | IdleEvent::Message
-        | IdleEvent::Neighbor
-        | IdleEvent::Mount => {
+        IdleEvent::Subscription => {}
+        IdleEvent::Partition => {
+            // Only the list of partitions changed, switching the partition of
+            // this client is handled by AppEvent::PartitionSwitched
+            context
+                .query()
+                .id(REFRESH_PARTITIONS)
+                .replace_id(REFRESH_PARTITIONS)
+                .query(move |client| Ok(MpdQueryResult::Partitions(client.list_partitions()?.0)));
+        }
+        IdleEvent::Neighbor | IdleEvent::Mount => {
+            let neighbors_supported = context.supported_commands.contains("listneighbors");
+            context
+                .query()
+                .id(REFRESH_MOUNTS)
+                .replace_id(REFRESH_MOUNTS)
+                .query(move |client| Ok(mounts::fetch(client, neighbors_supported)?));
+        }
+        IdleEvent::Output => {
             log::warn!(event:?; "Received unhandled event");
         }
     };
@@ -482,3 +815,71 @@ fn handle_idle_event(event: IdleEvent, context: &AppContext, result_ui_evs: &mut
         result_ui_evs.insert(ev);
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::{
+        collections::HashSet,
+        sync::{Arc, Mutex},
+    };
+
+    use crossbeam::channel::unbounded;
+    use itertools::Itertools;
+    use rstest::rstest;
+
+    use super::handle_idle_event;
+    use crate::{
+        context::AppContext,
+        mpd::{
+            client::Client,
+            commands::{IdleEvent, Song},
+        },
+        shared::{events::ClientRequest, mpd_query::MpdQueryResult},
+        tests::fixtures::{app_context, mpd_server::FakeMpdServer},
+    };
+
+    #[rstest]
+    fn sticker_event_refreshes_stickers_of_whole_queue(mut app_context: AppContext) {
+        // rmpc rated the first song while another client changed the second
+        // one, MPD reports both changes as a single sticker event
+        let (tx, rx) = unbounded();
+        app_context.client_request_sender = tx;
+        app_context.should_fetch_stickers = true;
+        app_context.queue = [(0, "rated.mp3"), (1, "other.mp3")]
+            .into_iter()
+            .map(|(id, file)| Song { id, file: file.to_owned(), ..Default::default() })
+            .collect();
+        // Every `sticker list` is answered with a rating, the songs asked about
+        // are recorded
+        let asked = Arc::new(Mutex::new(Vec::new()));
+        let asked2 = Arc::clone(&asked);
+        let server = FakeMpdServer::start("stickers", move |_| {
+            let asked = Arc::clone(&asked2);
+            move |line: &str| {
+                if let Some(uri) = line.strip_prefix("sticker list song ") {
+                    asked.lock().unwrap().push(uri.trim_matches('"').to_owned());
+                    Some("sticker: rating=5\nlist_OK\n".to_owned())
+                } else if line == "command_list_ok_begin" {
+                    None
+                } else {
+                    Some("OK\n".to_owned())
+                }
+            }
+        });
+
+        handle_idle_event(IdleEvent::Sticker, &app_context, &mut HashSet::new());
+
+        let Ok(ClientRequest::Query(query)) = rx.try_recv() else {
+            panic!("Expected a query to refresh stickers");
+        };
+        let mut client = Client::init(server.address(), None, "test").unwrap();
+        let result = (query.callback)(&mut client).unwrap();
+
+        let MpdQueryResult::QueueStickers(stickers) = result else {
+            panic!("Expected queue stickers");
+        };
+        assert_eq!(*asked.lock().unwrap(), ["rated.mp3", "other.mp3"]);
+        assert_eq!(stickers.iter().map(|(id, _)| *id).collect_vec(), [0, 1]);
+    }
+}
@@ -22,7 +22,7 @@ fn input_poll_task(event_tx: &Sender<AppEvent>) {
                     }
                 }
                 Ok(Event::Key(key)) => {
-                    if let Err(err) = event_tx.send(AppEvent::UserKeyInput(key)) {
+                    if let Err(err) = event_tx.send(AppEvent::UserKeyInput(key.into())) {
                         log::error!(error:? = err; "Failed to send user input");
                     }
                 }
@@ -0,0 +1,205 @@
+use std::time::{Duration, Instant};
+
+use crate::mpd::commands::{Song, State, Status};
+
+/// Song counts as played once at least half of it or this much of it was
+/// played, whichever comes first. It counts as skipped otherwise.
+const PLAYED_DURATION: Duration = Duration::from_secs(240);
+/// Jumps back in the elapsed time smaller than this are not considered a
+/// restart of the song
+const RESTART_TOLERANCE: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlaybackOutcome {
+    Played(String),
+    Skipped(String),
+}
+
+#[derive(Debug)]
+struct TrackedSong {
+    id: u32,
+    file: String,
+    duration: Duration,
+    elapsed: Duration,
+    observed_at: Instant,
+    playing: bool,
+}
+
+impl TrackedSong {
+    fn new(song: &Song, status: &Status, now: Instant) -> Self {
+        Self {
+            id: song.id,
+            file: song.file.clone(),
+            duration: song.duration.unwrap_or(status.duration),
+            elapsed: status.elapsed,
+            observed_at: now,
+            playing: status.state == State::Play,
+        }
+    }
+
+    /// Estimated position in the song. Status is not necessarily polled while
+    /// playing so the time since the last observation is accounted for.
+    fn position(&self, now: Instant) -> Duration {
+        let position = if self.playing {
+            self.elapsed + now.saturating_duration_since(self.observed_at)
+        } else {
+            self.elapsed
+        };
+        position.min(self.duration)
+    }
+
+    fn was_played(&self, now: Instant) -> bool {
+        let position = self.position(now);
+        position >= PLAYED_DURATION || position >= self.duration / 2
+    }
+
+    /// Streams have no duration and are not tracked
+    fn outcome(self, now: Instant) -> Option<PlaybackOutcome> {
+        if self.duration.is_zero() {
+            None
+        } else if self.was_played(now) {
+            Some(PlaybackOutcome::Played(self.file))
+        } else {
+            Some(PlaybackOutcome::Skipped(self.file))
+        }
+    }
+}
+
+/// Follows the playback through status updates and decides whether a song was
+/// played or skipped once it stops being the current song
+#[derive(Debug, Default)]
+pub struct PlaybackStatsTracker {
+    current: Option<TrackedSong>,
+}
+
+impl PlaybackStatsTracker {
+    /// Updates the tracked song with a new status. Returns the outcome of the
+    /// previously tracked song when a different song starts playing, the
+    /// playback stops or the same song starts over after being played.
+    pub fn update(
+        &mut self,
+        status: &Status,
+        song: Option<&Song>,
+        now: Instant,
+    ) -> Option<PlaybackOutcome> {
+        let song = song.filter(|_| status.state != State::Stop);
+        let Some(song) = song else {
+            return self.current.take().and_then(|current| current.outcome(now));
+        };
+
+        match &mut self.current {
+            Some(current) if current.id == song.id => {
+                let restarted = status.elapsed + RESTART_TOLERANCE < current.position(now)
+                    && current.was_played(now);
+                if restarted {
+                    self.current.replace(TrackedSong::new(song, status, now))?.outcome(now)
+                } else {
+                    current.elapsed = status.elapsed;
+                    current.observed_at = now;
+                    current.playing = status.state == State::Play;
+                    None
+                }
+            }
+            _ => self.current.replace(TrackedSong::new(song, status, now))?.outcome(now),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        time::{Duration, Instant},
+    };
+
+    use test_case::test_case;
+
+    use super::{PlaybackOutcome, PlaybackStatsTracker};
+    use crate::mpd::commands::{Song, State, Status};
+
+    fn song(id: u32, secs: u64) -> Song {
+        Song {
+            id,
+            file: format!("song{id}"),
+            duration: Some(Duration::from_secs(secs)),
+            metadata: HashMap::default(),
+            stickers: None,
+            priority: 0,
+        }
+    }
+
+    fn status(state: State, elapsed: u64) -> Status {
+        Status { state, elapsed: Duration::from_secs(elapsed), ..Default::default() }
+    }
+
+    #[test_case(150, true; "past half")]
+    #[test_case(20, false; "before half")]
+    fn song_change(played_secs: u64, played: bool) {
+        let now = Instant::now();
+        let mut tracker = PlaybackStatsTracker::default();
+        let first = song(1, 200);
+        let second = song(2, 200);
+
+        assert_eq!(tracker.update(&status(State::Play, 0), Some(&first), now), None);
+
+        let later = now + Duration::from_secs(played_secs);
+        let result = tracker.update(&status(State::Play, 0), Some(&second), later);
+
+        let file = "song1".to_owned();
+        let expected =
+            if played { PlaybackOutcome::Played(file) } else { PlaybackOutcome::Skipped(file) };
+        assert_eq!(result, Some(expected));
+    }
+
+    #[test]
+    fn long_song_played_after_four_minutes() {
+        let now = Instant::now();
+        let mut tracker = PlaybackStatsTracker::default();
+        let first = song(1, 3600);
+
+        tracker.update(&status(State::Play, 0), Some(&first), now);
+        let result = tracker.update(&status(State::Stop, 0), None, now + Duration::from_secs(250));
+
+        assert_eq!(result, Some(PlaybackOutcome::Played("song1".to_owned())));
+    }
+
+    #[test]
+    fn paused_time_is_not_counted() {
+        let now = Instant::now();
+        let mut tracker = PlaybackStatsTracker::default();
+        let first = song(1, 200);
+        let second = song(2, 200);
+
+        tracker.update(&status(State::Play, 0), Some(&first), now);
+        tracker.update(&status(State::Pause, 10), Some(&first), now + Duration::from_secs(10));
+        let result =
+            tracker.update(&status(State::Play, 0), Some(&second), now + Duration::from_secs(1000));
+
+        assert_eq!(result, Some(PlaybackOutcome::Skipped("song1".to_owned())));
+    }
+
+    #[test]
+    fn repeated_song_is_counted_again() {
+        let now = Instant::now();
+        let mut tracker = PlaybackStatsTracker::default();
+        let first = song(1, 200);
+
+        tracker.update(&status(State::Play, 0), Some(&first), now);
+        let result =
+            tracker.update(&status(State::Play, 1), Some(&first), now + Duration::from_secs(201));
+
+        assert_eq!(result, Some(PlaybackOutcome::Played("song1".to_owned())));
+    }
+
+    #[test]
+    fn streams_are_ignored() {
+        let now = Instant::now();
+        let mut tracker = PlaybackStatsTracker::default();
+        let stream = Song { duration: None, ..song(1, 0) };
+
+        tracker.update(&status(State::Play, 0), Some(&stream), now);
+        let result = tracker.update(&status(State::Stop, 0), None, now + Duration::from_secs(100));
+
+        assert_eq!(result, None);
+    }
+}
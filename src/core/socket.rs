@@ -40,7 +40,7 @@ pub(crate) fn init(
 
             log::debug!(command:?, addr:?; "Got command from unix socket");
             try_cont!(
-                command.execute(&event_tx, &work_tx, &config),
+                command.execute(reader.get_mut(), &event_tx, &work_tx, &config),
                 "Socket command execution failed"
             );
         }
@@ -1,10 +1,11 @@
 use std::{path::PathBuf, sync::Arc};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use crossbeam::channel::{Receiver, Sender};
 
 use crate::{
     config::{Config, cli_config::CliConfig},
+    mpd::client::Client,
     shared::{
         events::{AppEvent, ClientRequest, WorkDone, WorkRequest},
         lrc::LrcIndex,
@@ -52,5 +53,10 @@ fn handle_work_request(
         WorkRequest::IndexSingleLrc { path } => {
             Ok(WorkDone::SingleLrcIndexed { lrc_entry: LrcIndex::index_single(path)? })
         }
+        WorkRequest::ConnectProfile { profile } => {
+            let client = Client::init(profile.address.clone(), profile.password.clone(), "command")
+                .with_context(|| format!("Failed to connect to profile '{}'", profile.name))?;
+            Ok(WorkDone::ProfileConnected { profile, client })
+        }
     }
 }
@@ -18,7 +18,7 @@ use crate::{
         ConfigFile,
         cli::{Args, Command},
     },
-    mpd::client::Client,
+    mpd::{client::Client, mpd_client::MpdClient},
     shared::{
         dependencies::{DEPENDENCIES, FFMPEG, FFPROBE, PYTHON3, PYTHON3MUTAGEN, UEBERZUGPP, YTDLP},
         env::ENV,
@@ -87,6 +87,7 @@ fn main() -> Result<()> {
                 args.theme.as_deref(),
                 std::mem::take(&mut args.address),
                 std::mem::take(&mut args.password),
+                args.profile.as_deref(),
                 false,
             )?;
             let mut mpd_host = ENV.var("MPD_HOST").unwrap_or_else(|_| "unset".to_string());
@@ -106,6 +107,7 @@ fn main() -> Result<()> {
             println!("\nMPD:");
             println!("{:<20} {:?}", "Address", config_file.address);
             println!("{:<20} {:?}", "Resolved Address", config.address);
+            println!("{:<20} {:?}", "Profile", config.active_profile);
             println!("{:<20} {mpd_host}", "MPD_HOST");
             println!("{:<20} {mpd_port}", "MPD_PORT");
 
@@ -133,13 +135,21 @@ fn main() -> Result<()> {
         Some(Command::Remote { command, pid }) => {
             if let Some(pid) = pid {
                 let path = get_socket_path(pid);
-                command.write_to_socket(&path)?;
+                if let Some(response) = command.write_to_socket(&path)? {
+                    println!("{}", serde_json::ser::to_string(&response)?);
+                }
                 eprintln!("Successfully sent remote command to {path:?}");
             } else {
                 for path in list_all_socket_paths()? {
-                    if let Err(err) = command.clone().write_to_socket(&path) {
-                        eprintln!("Failed to send remote command. Error: '{err:?}'");
-                        continue;
+                    match command.clone().write_to_socket(&path) {
+                        Ok(Some(response)) => {
+                            println!("{}", serde_json::ser::to_string(&response)?);
+                        }
+                        Ok(None) => {}
+                        Err(err) => {
+                            eprintln!("Failed to send remote command. Error: '{err:?}'");
+                            continue;
+                        }
                     }
                     eprintln!("Successfully sent remote command to {path:?}");
                 }
@@ -151,13 +161,17 @@ fn main() -> Result<()> {
                 Ok(cfg) => cfg,
                 Err(_err) => ConfigFile::default().into(),
             };
-            let mut config = config.into_config(args.address, args.password);
+            let mut config =
+                config.into_config(args.address, args.password, args.profile.as_deref())?;
             let mut client = Client::init(
                 std::mem::take(&mut config.address),
                 std::mem::take(&mut config.password),
                 "main",
             )?;
             client.set_read_timeout(None)?;
+            if let Some(partition) = &args.partition {
+                client.switch_to_partition(partition)?;
+            }
             (cmd.execute(&config)?)(&mut client)?;
         }
         None => {
@@ -177,6 +191,7 @@ fn main() -> Result<()> {
                     args.theme.as_deref(),
                     std::mem::take(&mut args.address),
                     std::mem::take(&mut args.password),
+                    args.profile.as_deref(),
                     false,
                 )?,
                 Err(err) => {
@@ -186,6 +201,7 @@ fn main() -> Result<()> {
                         None,
                         std::mem::take(&mut args.address),
                         std::mem::take(&mut args.password),
+                        args.profile.as_deref(),
                         false,
                     )?
                 }
@@ -205,6 +221,11 @@ fn main() -> Result<()> {
                     .context("Failed to connect to MPD")?;
             client.set_read_timeout(Some(config.mpd_read_timeout))?;
             client.set_write_timeout(Some(config.mpd_write_timeout))?;
+            if let Some(partition) = &args.partition {
+                client
+                    .switch_to_partition(partition)
+                    .with_context(|| format!("Failed to switch to partition '{partition}'"))?;
+            }
 
             let tx_clone = event_tx.clone();
 
@@ -221,7 +242,7 @@ fn main() -> Result<()> {
             let enable_mouse = context.config.enable_mouse;
             let terminal = ui::setup_terminal(enable_mouse).context("Failed to setup terminal")?;
 
-            core::client::init(
+            let client_thread = core::client::init(
                 client_rx.clone(),
                 event_tx.clone(),
                 client,
@@ -251,7 +272,8 @@ fn main() -> Result<()> {
                 ))
                 .transpose()?;
 
-            let event_loop_handle = core::event_loop::init(context, event_rx, terminal)?;
+            let event_loop_handle =
+                core::event_loop::init(context, event_rx, terminal, client_thread)?;
 
             let original_hook = std::panic::take_hook();
             std::panic::set_hook(Box::new(move |panic| {
@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeSet,
     io::{BufRead, BufReader, Write},
     net::TcpStream,
     os::unix::net::UnixStream,
@@ -31,6 +32,8 @@ pub struct Client<'name> {
     password: Option<MpdPassword>,
     pub version: Version,
     pub config: Option<MpdConfig>,
+    pub partition: Option<String>,
+    pub subscriptions: BTreeSet<String>,
 }
 
 impl std::fmt::Debug for Client<'_> {
@@ -69,6 +72,15 @@ impl TcpOrUnixStream {
         Ok(())
     }
 
+    /// Shuts down both halves of the connection, which also unblocks any
+    /// pending reads on its clones
+    pub fn shutdown(&self) -> std::io::Result<()> {
+        match self {
+            TcpOrUnixStream::Unix(s) => s.shutdown(std::net::Shutdown::Both),
+            TcpOrUnixStream::Tcp(s) => s.shutdown(std::net::Shutdown::Both),
+        }
+    }
+
     pub fn try_clone(&self) -> std::io::Result<Self> {
         Ok(match self {
             TcpOrUnixStream::Unix(s) => TcpOrUnixStream::Unix(s.try_clone()?),
@@ -138,7 +150,17 @@ impl<'name> Client<'name> {
             );
         }
 
-        let mut client = Self { name, rx, stream, addr, password, version, config: None };
+        let mut client = Self {
+            name,
+            rx,
+            stream,
+            addr,
+            password,
+            version,
+            config: None,
+            partition: None,
+            subscriptions: BTreeSet::new(),
+        };
 
         if let Some(MpdPassword(ref password)) = client.password.clone() {
             debug!("Used password auth to MPD");
@@ -190,6 +212,20 @@ impl<'name> Client<'name> {
 
         self.binary_limit(1024 * 1024 * 5)?;
 
+        if let Some(partition) = self.partition.take() {
+            debug!(partition = partition.as_str(); "Restoring partition after reconnect");
+            if let Err(err) = self.switch_to_partition(&partition) {
+                status_warn!(err:?; "Failed to switch back to partition '{partition}', using the default one");
+            }
+        }
+
+        for channel in std::mem::take(&mut self.subscriptions) {
+            debug!(channel = channel.as_str(); "Restoring channel subscription after reconnect");
+            if let Err(err) = self.subscribe(&channel) {
+                status_warn!(err:?; "Failed to subscribe to channel '{channel}' again");
+            }
+        }
+
         Ok(self)
     }
 
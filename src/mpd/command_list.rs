@@ -0,0 +1,294 @@
+use itertools::Itertools;
+
+use super::{
+    FromMpd,
+    LineHandled,
+    errors::MpdError,
+    mpd_client::{PlaybackRange, QueueMoveTarget, SingleOrRange, StrExt},
+    proto_client::{ProtoClient, SocketClient},
+};
+
+type MpdResult<T> = Result<T, MpdError>;
+
+/// Half of MPD's default `max_command_list_size`. Longer lists are split and
+/// sent in multiple parts.
+const MAX_LIST_BYTES: usize = 1024 * 1024;
+
+/// Commands sent to MPD together in a single `command_list_ok_begin` block.
+/// All of the commands are written at once and their responses are read
+/// afterwards, so the whole list costs a single round trip instead of one per
+/// command.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CommandList {
+    commands: Vec<String>,
+}
+
+/// Response of commands which only reply with `OK`
+impl FromMpd for () {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        Err(MpdError::Generic(format!("Expected 'OK' but got '{key}: {value}'")))
+    }
+}
+
+impl CommandList {
+    pub fn add(&mut self, uri: &str) -> &mut Self {
+        self.commands.push(format!("add {}", uri.quote_and_escape()));
+        self
+    }
+
+    pub fn add_to_position(&mut self, uri: &str, position: QueueMoveTarget) -> &mut Self {
+        self.commands.push(format!("add {} {}", uri.quote_and_escape(), position.as_mpd_str()));
+        self
+    }
+
+    pub fn clear_queue(&mut self) -> &mut Self {
+        self.commands.push("clear".to_owned());
+        self
+    }
+
+    pub fn delete_id(&mut self, id: u32) -> &mut Self {
+        self.commands.push(format!("deleteid {id}"));
+        self
+    }
+
+    pub fn delete_from_queue(&mut self, songs: SingleOrRange) -> &mut Self {
+        self.commands.push(format!("delete {}", songs.as_mpd_range()));
+        self
+    }
+
+    pub fn move_in_queue(&mut self, from: SingleOrRange, to: QueueMoveTarget) -> &mut Self {
+        self.commands.push(format!("move {} {}", from.as_mpd_range(), to.as_mpd_str()));
+        self
+    }
+
+    pub fn move_id(&mut self, id: u32, to: QueueMoveTarget) -> &mut Self {
+        self.commands.push(format!("moveid {id} {}", to.as_mpd_str()));
+        self
+    }
+
+    pub fn set_priority_id(&mut self, priority: u8, ids: &[u32]) -> &mut Self {
+        if !ids.is_empty() {
+            self.commands.push(format!("prioid {priority} {}", ids.iter().join(" ")));
+        }
+        self
+    }
+
+    pub fn set_range_id(&mut self, id: u32, range: PlaybackRange) -> &mut Self {
+        self.commands.push(format!("rangeid {id} {}", range.as_mpd_range()));
+        self
+    }
+
+    pub fn playlist_id(&mut self, id: u32) -> &mut Self {
+        self.commands.push(format!("playlistid {id}"));
+        self
+    }
+
+    pub fn lsinfo(&mut self, uri: &str) -> &mut Self {
+        self.commands.push(format!("lsinfo {}", uri.quote_and_escape()));
+        self
+    }
+
+    pub fn list_stickers(&mut self, uri: &str) -> &mut Self {
+        self.commands.push(format!("sticker list song {}", uri.quote_and_escape()));
+        self
+    }
+
+    /// Sends the commands and returns the result of each of them in the same
+    /// order. MPD aborts the list on the first failed command, the commands
+    /// after it are sent again in a new list so a single failure does not
+    /// affect the rest. The outer error is returned only when the connection
+    /// itself failed.
+    pub(super) fn execute<T, C>(&self, client: &mut C) -> MpdResult<Vec<MpdResult<T>>>
+    where
+        T: FromMpd + Default,
+        C: SocketClient,
+    {
+        let mut results = Vec::with_capacity(self.commands.len());
+
+        while results.len() < self.commands.len() {
+            let pending = &self.commands[results.len()..];
+            let chunk = &pending[..chunk_len(pending)];
+
+            let mut buf = String::from("command_list_ok_begin\n");
+            for command in chunk {
+                buf.push_str(command);
+                buf.push('\n');
+            }
+            buf.push_str("command_list_end\n");
+
+            log::trace!(count = chunk.len(), remaining = pending.len(); "Executing command list");
+            client.write(buf.as_bytes())?;
+
+            let mut proto = ProtoClient::new_read_only(client);
+            let mut aborted = false;
+            for command in chunk {
+                match proto.read_response::<T>() {
+                    Ok(value) => results.push(Ok(value)),
+                    Err(err @ MpdError::Mpd(_)) => {
+                        log::debug!(err:?, command = command.as_str(); "Command in command list failed");
+                        results.push(Err(err));
+                        aborted = true;
+                        break;
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+
+            // Every command is acknowledged with list_OK and the whole list
+            // with a final OK unless it was aborted by an error
+            if !aborted {
+                proto.read_ok()?;
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+/// Number of commands from the start of the given slice that fit into a
+/// single list, at least one
+fn chunk_len(commands: &[String]) -> usize {
+    let mut bytes = 0;
+    commands
+        .iter()
+        .take_while(|command| {
+            bytes += command.len() + 1;
+            bytes <= MAX_LIST_BYTES
+        })
+        .count()
+        .max(1)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::io::{BufReader, Cursor};
+
+    use super::{CommandList, MAX_LIST_BYTES, chunk_len};
+    use crate::mpd::{
+        commands::stickers::Stickers,
+        errors::{ErrorCode, MpdError, MpdFailureResponse},
+        mpd_client::{QueueMoveTarget, SingleOrRange},
+        proto_client::SocketClient,
+    };
+
+    struct TestClient {
+        read: BufReader<Cursor<Vec<u8>>>,
+        written: Vec<u8>,
+    }
+
+    impl TestClient {
+        fn new(buf: &[u8]) -> Self {
+            Self { read: BufReader::new(Cursor::new(buf.to_vec())), written: Vec::new() }
+        }
+
+        fn written(&self) -> String {
+            String::from_utf8(self.written.clone()).unwrap()
+        }
+    }
+
+    impl SocketClient for TestClient {
+        fn write(&mut self, bytes: &[u8]) -> std::io::Result<()> {
+            self.written.extend_from_slice(bytes);
+            Ok(())
+        }
+
+        fn read(&mut self) -> &mut impl std::io::BufRead {
+            &mut self.read
+        }
+
+        fn clear_read_buf(&mut self) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn sends_all_commands_in_one_list() {
+        let mut client = TestClient::new(b"list_OK\nlist_OK\nOK\n");
+        let mut list = CommandList::default();
+        list.add("a.mp3").delete_from_queue(SingleOrRange::range(2, 5));
+
+        let result = list.execute::<(), _>(&mut client).unwrap();
+
+        assert_eq!(result, vec![Ok(()), Ok(())]);
+        assert_eq!(
+            client.written(),
+            "command_list_ok_begin\nadd \"a.mp3\"\ndelete \"2:5\"\ncommand_list_end\n"
+        );
+    }
+
+    #[test]
+    fn reads_typed_responses() {
+        let mut client = TestClient::new(
+            b"sticker: rating=5\nlist_OK\nlist_OK\nsticker: rating=2\nlist_OK\nOK\n",
+        );
+        let mut list = CommandList::default();
+        list.list_stickers("a.mp3").list_stickers("b.mp3").list_stickers("c.mp3");
+
+        let result = list.execute::<Stickers, _>(&mut client).unwrap();
+
+        let ratings: Vec<_> =
+            result.into_iter().map(|stickers| stickers.unwrap().0.get("rating").cloned()).collect();
+        assert_eq!(ratings, vec![Some("5".to_owned()), None, Some("2".to_owned())]);
+    }
+
+    #[test]
+    fn resends_commands_after_failed_one() {
+        let mut client =
+            TestClient::new(b"list_OK\nACK [50@1] {add} No such directory\nlist_OK\nOK\n");
+        let mut list = CommandList::default();
+        list.add("a.mp3").add("missing.mp3").add("c.mp3");
+
+        let result = list.execute::<(), _>(&mut client).unwrap();
+
+        assert_eq!(result, vec![
+            Ok(()),
+            Err(MpdError::Mpd(MpdFailureResponse {
+                code: ErrorCode::NoExist,
+                command_list_index: 1,
+                command: "add".to_owned(),
+                message: "No such directory".to_owned(),
+            })),
+            Ok(()),
+        ]);
+        assert_eq!(
+            client.written(),
+            "command_list_ok_begin\nadd \"a.mp3\"\nadd \"missing.mp3\"\nadd \"c.mp3\"\ncommand_list_end\ncommand_list_ok_begin\nadd \"c.mp3\"\ncommand_list_end\n"
+        );
+    }
+
+    #[test]
+    fn sends_queue_edits_by_id() {
+        let mut client = TestClient::new(b"list_OK\nlist_OK\nlist_OK\nOK\n");
+        let mut list = CommandList::default();
+        list.delete_id(4)
+            .add_to_position("a.mp3", QueueMoveTarget::Absolute(0))
+            .move_id(7, QueueMoveTarget::Absolute(1));
+
+        list.execute::<(), _>(&mut client).unwrap();
+
+        assert_eq!(
+            client.written(),
+            "command_list_ok_begin\ndeleteid 4\nadd \"a.mp3\" 0\nmoveid 7 1\ncommand_list_end\n"
+        );
+    }
+
+    #[test]
+    fn empty_list_sends_nothing() {
+        let mut client = TestClient::new(b"");
+
+        let result = CommandList::default().execute::<(), _>(&mut client).unwrap();
+
+        assert!(result.is_empty());
+        assert!(client.written.is_empty());
+    }
+
+    #[test]
+    fn splits_long_lists() {
+        let command = "a".repeat(MAX_LIST_BYTES / 4);
+        let commands = vec![command; 10];
+
+        assert_eq!(chunk_len(&commands), 3);
+        assert_eq!(chunk_len(&["a".repeat(MAX_LIST_BYTES * 2)]), 1);
+    }
+}
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, time::Duration};
+use std::{borrow::Cow, collections::HashMap, time::Duration};
 
 use serde::Serialize;
 
@@ -9,8 +9,10 @@ pub struct Song {
     pub id: u32,
     pub file: String,
     pub duration: Option<Duration>,
-    pub metadata: HashMap<String, String>,
+    pub metadata: HashMap<String, MetadataTag>,
     pub stickers: Option<HashMap<String, String>>,
+    /// Priority of the song in the queue, only used by MPD in random mode
+    pub priority: u8,
 }
 
 impl std::fmt::Debug for Song {
@@ -22,22 +24,76 @@ impl std::fmt::Debug for Song {
             self.title(),
             self.artist(),
             self.id,
-            self.metadata.get("track")
+            self.tag("track")
         )
     }
 }
 
+/// Value of a metadata tag. MPD sends one line per value so a tag like
+/// `Artist` can appear multiple times for a single song.
+#[derive(Debug, Serialize, PartialEq, Eq, Clone)]
+#[serde(untagged)]
+pub enum MetadataTag {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl MetadataTag {
+    /// First value of the tag, `None` only for an empty `Multiple`
+    pub fn first(&self) -> Option<&String> {
+        match self {
+            MetadataTag::Single(value) => Some(value),
+            MetadataTag::Multiple(values) => values.first(),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &String> {
+        match self {
+            MetadataTag::Single(value) => std::slice::from_ref(value).iter(),
+            MetadataTag::Multiple(values) => values.iter(),
+        }
+    }
+
+    /// All values of the tag joined by the given separator
+    pub fn join(&self, separator: &str) -> Cow<'_, str> {
+        match self {
+            MetadataTag::Single(value) => Cow::Borrowed(value),
+            MetadataTag::Multiple(values) => Cow::Owned(values.join(separator)),
+        }
+    }
+
+    fn push(&mut self, value: String) {
+        match self {
+            MetadataTag::Single(current) => {
+                *self = MetadataTag::Multiple(vec![std::mem::take(current), value]);
+            }
+            MetadataTag::Multiple(values) => values.push(value),
+        }
+    }
+}
+
+impl From<String> for MetadataTag {
+    fn from(value: String) -> Self {
+        MetadataTag::Single(value)
+    }
+}
+
 impl Song {
     pub fn title(&self) -> Option<&String> {
-        self.metadata.get("title")
+        self.metadata.get("title").and_then(MetadataTag::first)
     }
 
     pub fn artist(&self) -> Option<&String> {
-        self.metadata.get("artist")
+        self.metadata.get("artist").and_then(MetadataTag::first)
     }
 
     pub fn album(&self) -> Option<&String> {
-        self.metadata.get("album")
+        self.metadata.get("album").and_then(MetadataTag::first)
+    }
+
+    /// First value of the given tag
+    pub fn tag(&self, name: &str) -> Option<&String> {
+        self.metadata.get(name).and_then(MetadataTag::first)
     }
 }
 
@@ -49,11 +105,85 @@ impl FromMpd for Song {
             "duration" => {
                 self.duration = Some(Duration::from_secs_f64(value.parse().logerr(key, &value)?));
             }
+            "prio" => self.priority = value.parse().logerr(key, &value)?,
             "time" | "format" => {} // deprecated or ignored
-            key => {
-                self.metadata.insert(key.to_owned(), value);
-            }
+            key => match self.metadata.get_mut(key) {
+                Some(tag) => tag.push(value),
+                None => {
+                    self.metadata.insert(key.to_owned(), value.into());
+                }
+            },
         }
         Ok(LineHandled::Yes)
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{FromMpd, MetadataTag, Song};
+
+    fn parse(input: &str) -> Song {
+        let mut result = Song::default();
+        for line in input.lines() {
+            let (key, value) = line.split_once(": ").unwrap();
+            result.next_internal(key.to_lowercase().as_str(), value.to_owned()).unwrap();
+        }
+        result
+    }
+
+    #[test]
+    fn keeps_all_values_of_repeated_tag() {
+        let song = parse(
+            "file: song.flac
+Artist: first
+Title: title
+Artist: second
+Genre: rock
+Artist: third",
+        );
+
+        assert_eq!(
+            song.metadata.get("artist"),
+            Some(&MetadataTag::Multiple(vec![
+                "first".to_owned(),
+                "second".to_owned(),
+                "third".to_owned()
+            ]))
+        );
+        assert_eq!(song.metadata.get("genre"), Some(&MetadataTag::Single("rock".to_owned())));
+        assert_eq!(song.artist(), Some(&"first".to_owned()));
+    }
+
+    #[test]
+    fn parses_priority() {
+        let song = parse(
+            "file: song.flac
+Id: 5
+Prio: 42
+Title: title",
+        );
+
+        assert_eq!(song.priority, 42);
+        assert_eq!(song.id, 5);
+        assert!(!song.metadata.contains_key("prio"));
+    }
+
+    #[test]
+    fn empty_multiple_tag_has_no_first_value() {
+        let song = Song {
+            metadata: [("artist".to_owned(), MetadataTag::Multiple(Vec::new()))].into(),
+            ..Default::default()
+        };
+
+        assert_eq!(song.artist(), None);
+    }
+
+    #[test]
+    fn joins_values_with_separator() {
+        let tag = MetadataTag::Multiple(vec!["a".to_owned(), "b".to_owned()]);
+
+        assert_eq!(tag.join(" | "), "a | b");
+        assert_eq!(MetadataTag::Single("a".to_owned()).join(" | "), "a");
+    }
+}
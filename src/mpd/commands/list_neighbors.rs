@@ -0,0 +1,68 @@
+use anyhow::{Context, anyhow};
+use derive_more::{AsMut, AsRef, Into, IntoIterator};
+use serde::Serialize;
+
+use crate::mpd::{FromMpd, LineHandled, errors::MpdError};
+
+#[derive(Debug, Serialize, Default, IntoIterator, AsRef, AsMut, Into)]
+pub struct Neighbors(pub Vec<Neighbor>);
+
+/// Storage discovered on the network by one of MPD's neighbor plugins
+#[derive(Debug, Default, Serialize, Clone, PartialEq, Eq)]
+pub struct Neighbor {
+    /// Uri which can be passed to `mount`
+    pub neighbor: String,
+    pub name: String,
+}
+
+impl FromMpd for Neighbors {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        if key == "neighbor" {
+            self.0.push(Neighbor::default());
+        }
+
+        self.0
+            .last_mut()
+            .context(anyhow!(
+                "No element in accumulator while parsing Neighbors. Key '{}' Value :'{}'",
+                key,
+                value
+            ))?
+            .next_internal(key, value)
+    }
+}
+
+impl FromMpd for Neighbor {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        match key {
+            "neighbor" => self.neighbor = value,
+            "name" => self.name = value,
+            _ => return Ok(LineHandled::No { value }),
+        }
+        Ok(LineHandled::Yes)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{FromMpd, Neighbor, Neighbors};
+
+    #[test]
+    fn parses_neighbors() {
+        let mut result = Neighbors::default();
+        for (key, value) in [
+            ("neighbor", "smb://nas"),
+            ("name", "nas (Samba 4.19.5)"),
+            ("neighbor", "upnp://uuid:1234/0"),
+            ("name", "Media Server"),
+        ] {
+            result.next_internal(key, value.to_owned()).unwrap();
+        }
+
+        assert_eq!(result.0, [
+            Neighbor { neighbor: "smb://nas".to_owned(), name: "nas (Samba 4.19.5)".to_owned() },
+            Neighbor { neighbor: "upnp://uuid:1234/0".to_owned(), name: "Media Server".to_owned() },
+        ]);
+    }
+}
@@ -1,8 +1,9 @@
 use anyhow::{Context, anyhow};
+use serde::Serialize;
 
 use crate::mpd::{FromMpd, LineHandled, errors::MpdError};
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Serialize)]
 pub struct Playlist {
     pub name: String,
     pub last_modified: String,
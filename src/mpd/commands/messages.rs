@@ -0,0 +1,92 @@
+use anyhow::{Context, anyhow};
+use derive_more::{AsMut, AsRef, Into, IntoIterator};
+use serde::Serialize;
+
+use crate::mpd::{FromMpd, LineHandled, errors::MpdError};
+
+#[derive(Debug, Serialize, Default, IntoIterator, AsRef, AsMut, Into)]
+pub struct Channels(pub Vec<String>);
+
+#[derive(Debug, Serialize, Default, IntoIterator, AsRef, AsMut, Into)]
+pub struct Messages(pub Vec<Message>);
+
+#[derive(Debug, Serialize, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Message {
+    pub channel: String,
+    pub message: String,
+}
+
+impl FromMpd for Channels {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        match key {
+            "channel" => self.0.push(value),
+            _ => return Ok(LineHandled::No { value }),
+        }
+        Ok(LineHandled::Yes)
+    }
+}
+
+impl FromMpd for Messages {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        if key == "channel" {
+            self.0.push(Message::default());
+        }
+
+        self.0
+            .last_mut()
+            .context(anyhow!(
+                "No element in accumulator while parsing Messages. Key '{}' Value :'{}'",
+                key,
+                value
+            ))?
+            .next_internal(key, value)
+    }
+}
+
+impl FromMpd for Message {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        match key {
+            "channel" => self.channel = value,
+            "message" => self.message = value,
+            _ => return Ok(LineHandled::No { value }),
+        }
+        Ok(LineHandled::Yes)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{Channels, FromMpd, Message, Messages};
+
+    fn parse<T: FromMpd + Default>(input: &str) -> T {
+        let mut result = T::default();
+        for line in input.lines() {
+            let (key, value) = line.split_once(": ").unwrap();
+            result.next_internal(key, value.to_owned()).unwrap();
+        }
+        result
+    }
+
+    #[test]
+    fn can_parse_messages() {
+        let input = r"channel: rmpc
+message: next
+channel: other
+message: hello: world";
+
+        let result: Messages = parse(input);
+
+        assert_eq!(result.0, vec![
+            Message { channel: "rmpc".to_owned(), message: "next".to_owned() },
+            Message { channel: "other".to_owned(), message: "hello: world".to_owned() },
+        ]);
+    }
+
+    #[test]
+    fn can_parse_channels() {
+        let result: Channels = parse("channel: rmpc\nchannel: other");
+
+        assert_eq!(result.0, vec!["rmpc".to_owned(), "other".to_owned()]);
+    }
+}
@@ -5,12 +5,18 @@ pub mod idle;
 pub mod list;
 pub mod list_files;
 pub mod list_mounts;
+pub mod list_neighbors;
 pub mod list_playlist;
 pub mod list_playlists;
 pub mod lsinfo;
+pub mod messages;
 pub mod mpd_config;
 pub mod outputs;
+pub mod partitions;
 pub mod playlist_info;
+pub mod plchanges;
+pub mod replay_gain;
+pub mod stats;
 pub mod status;
 pub mod stickers;
 pub mod update;
@@ -18,15 +24,19 @@ pub mod volume;
 
 pub use self::{
     current_index::CurrentIndex,
-    current_song::Song,
+    current_song::{MetadataTag, Song},
     decoders::Decoder,
     idle::IdleEvent,
     list_files::ListFiles,
     list_mounts::Mounts,
+    list_neighbors::Neighbors,
     list_playlists::Playlist,
     lsinfo::LsInfo,
+    messages::Message,
     outputs::Output,
-    status::{State, Status},
+    partitions::Partition,
+    stats::{Stats, TagCount},
+    status::{ConnectionState, State, Status},
     update::Update,
     volume::Volume,
 };
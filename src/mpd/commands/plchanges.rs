@@ -0,0 +1,54 @@
+use anyhow::{Context, anyhow};
+
+use crate::mpd::{FromMpd, LineHandled, ParseErrorExt, errors::MpdError};
+
+/// Position and id of a song in the queue which changed since a given
+/// playlist version, as returned by `plchangesposid`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PosId {
+    pub pos: usize,
+    pub id: u32,
+}
+
+impl FromMpd for PosId {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        match key {
+            "cpos" => self.pos = value.parse().logerr(key, &value)?,
+            "id" => self.id = value.parse().logerr(key, &value)?,
+            _ => return Ok(LineHandled::No { value }),
+        }
+        Ok(LineHandled::Yes)
+    }
+}
+
+impl FromMpd for Vec<PosId> {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        if key == "cpos" {
+            self.push(PosId::default());
+        }
+
+        self.last_mut()
+            .context(anyhow!(
+                "No element in accumulator while parsing PosIds. Key '{}' Value :'{}'",
+                key,
+                value
+            ))?
+            .next_internal(key, value)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{FromMpd, PosId};
+
+    #[test]
+    fn parses_changed_positions() {
+        let mut result: Vec<PosId> = Vec::new();
+        for (key, value) in [("cpos", "3"), ("Id", "120"), ("cpos", "4"), ("Id", "7")] {
+            result.next_internal(&key.to_lowercase(), value.to_owned()).unwrap();
+        }
+
+        assert_eq!(result, [PosId { pos: 3, id: 120 }, PosId { pos: 4, id: 7 }]);
+    }
+}
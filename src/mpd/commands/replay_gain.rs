@@ -0,0 +1,113 @@
+use anyhow::anyhow;
+use serde::Serialize;
+
+use crate::mpd::{FromMpd, LineHandled, ParseErrorExt, errors::MpdError};
+
+#[derive(Debug, Serialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReplayGainMode {
+    #[default]
+    Off,
+    Track,
+    Album,
+    /// Album gain is used if the queue is played in order, track gain
+    /// otherwise
+    Auto,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct ReplayGainStatus {
+    pub mode: ReplayGainMode,
+}
+
+impl ReplayGainMode {
+    pub fn cycle(self) -> Self {
+        match self {
+            ReplayGainMode::Off => ReplayGainMode::Track,
+            ReplayGainMode::Track => ReplayGainMode::Album,
+            ReplayGainMode::Album => ReplayGainMode::Auto,
+            ReplayGainMode::Auto => ReplayGainMode::Off,
+        }
+    }
+
+    pub fn to_mpd_value(self) -> &'static str {
+        match self {
+            ReplayGainMode::Off => "off",
+            ReplayGainMode::Track => "track",
+            ReplayGainMode::Album => "album",
+            ReplayGainMode::Auto => "auto",
+        }
+    }
+}
+
+impl std::fmt::Display for ReplayGainMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            ReplayGainMode::Off => "Off",
+            ReplayGainMode::Track => "Track",
+            ReplayGainMode::Album => "Album",
+            ReplayGainMode::Auto => "Auto",
+        })
+    }
+}
+
+impl std::str::FromStr for ReplayGainMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(ReplayGainMode::Off),
+            "track" => Ok(ReplayGainMode::Track),
+            "album" => Ok(ReplayGainMode::Album),
+            "auto" => Ok(ReplayGainMode::Auto),
+            val => Err(anyhow!("Received unknown value for ReplayGainMode '{}'", val)),
+        }
+    }
+}
+
+impl FromMpd for ReplayGainStatus {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        match key {
+            "replay_gain_mode" => self.mode = value.parse().logerr(key, &value)?,
+            _ => return Ok(LineHandled::No { value }),
+        }
+        Ok(LineHandled::Yes)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use test_case::test_case;
+
+    use super::{FromMpd, ReplayGainMode, ReplayGainStatus};
+
+    #[test_case("off", ReplayGainMode::Off)]
+    #[test_case("track", ReplayGainMode::Track)]
+    #[test_case("album", ReplayGainMode::Album)]
+    #[test_case("auto", ReplayGainMode::Auto)]
+    fn parses_replay_gain_status(value: &str, expected: ReplayGainMode) {
+        let mut result = ReplayGainStatus::default();
+
+        result.next_internal("replay_gain_mode", value.to_owned()).unwrap();
+
+        assert_eq!(result.mode, expected);
+    }
+
+    #[test]
+    fn cycles_through_all_modes() {
+        let mut mode = ReplayGainMode::Off;
+        let mut visited = Vec::new();
+        for _ in 0..4 {
+            mode = mode.cycle();
+            visited.push(mode);
+        }
+
+        assert_eq!(visited, [
+            ReplayGainMode::Track,
+            ReplayGainMode::Album,
+            ReplayGainMode::Auto,
+            ReplayGainMode::Off
+        ]);
+    }
+}
@@ -0,0 +1,136 @@
+use anyhow::{Context, anyhow};
+use serde::Serialize;
+
+use crate::mpd::{FromMpd, LineHandled, ParseErrorExt, errors::MpdError};
+
+/// Statistics of the database and of the running MPD instance. Times are in
+/// seconds.
+#[derive(Debug, Serialize, Default, Clone, PartialEq, Eq)]
+pub struct Stats {
+    pub artists: u32,
+    pub albums: u32,
+    pub songs: u32,
+    /// Time MPD has been running
+    pub uptime: u64,
+    /// Time MPD has been playing music
+    pub playtime: u64,
+    /// Sum of durations of all songs in the database
+    pub db_playtime: u64,
+    /// Unix timestamp of the last database update
+    pub db_update: u64,
+}
+
+/// Number of songs and their total duration in seconds, for a single value of
+/// the grouped tag. The value is empty when the songs were not grouped.
+#[derive(Debug, Serialize, Default, Clone, PartialEq, Eq)]
+pub struct TagCount {
+    pub value: String,
+    pub songs: u32,
+    pub playtime: u64,
+}
+
+impl FromMpd for Stats {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        match key {
+            "artists" => self.artists = value.parse().logerr(key, &value)?,
+            "albums" => self.albums = value.parse().logerr(key, &value)?,
+            "songs" => self.songs = value.parse().logerr(key, &value)?,
+            "uptime" => self.uptime = value.parse().logerr(key, &value)?,
+            "playtime" => self.playtime = value.parse().logerr(key, &value)?,
+            "db_playtime" => self.db_playtime = value.parse().logerr(key, &value)?,
+            "db_update" => self.db_update = value.parse().logerr(key, &value)?,
+            _ => return Ok(LineHandled::No { value }),
+        }
+        Ok(LineHandled::Yes)
+    }
+}
+
+impl FromMpd for TagCount {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        match key {
+            "songs" => self.songs = value.parse().logerr(key, &value)?,
+            "playtime" => self.playtime = value.parse().logerr(key, &value)?,
+            _ => self.value = value,
+        }
+        Ok(LineHandled::Yes)
+    }
+}
+
+impl FromMpd for Vec<TagCount> {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        // Grouped results start with the tag, ungrouped result is a single
+        // entry with songs and playtime only
+        if self.is_empty() || !matches!(key, "songs" | "playtime") {
+            self.push(TagCount::default());
+        }
+
+        self.last_mut()
+            .context(anyhow!(
+                "No element in accumulator while parsing TagCounts. Key '{}' Value :'{}'",
+                key,
+                value
+            ))?
+            .next_internal(key, value)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{FromMpd, Stats, TagCount};
+
+    fn parse<T: FromMpd + Default>(input: &[(&str, &str)]) -> T {
+        let mut result = T::default();
+        for (key, value) in input {
+            result.next_internal(key, (*value).to_owned()).unwrap();
+        }
+        result
+    }
+
+    #[test]
+    fn parses_stats() {
+        let result: Stats = parse(&[
+            ("uptime", "4"),
+            ("playtime", "3"),
+            ("artists", "12"),
+            ("albums", "20"),
+            ("songs", "250"),
+            ("db_playtime", "61234"),
+            ("db_update", "1700000000"),
+        ]);
+
+        assert_eq!(result, Stats {
+            artists: 12,
+            albums: 20,
+            songs: 250,
+            uptime: 4,
+            playtime: 3,
+            db_playtime: 61234,
+            db_update: 1_700_000_000,
+        });
+    }
+
+    #[test]
+    fn parses_grouped_counts() {
+        let result: Vec<TagCount> = parse(&[
+            ("Genre", ""),
+            ("songs", "2"),
+            ("playtime", "300"),
+            ("Genre", "Rock"),
+            ("songs", "10"),
+            ("playtime", "2400"),
+        ]);
+
+        assert_eq!(result, [
+            TagCount { value: String::new(), songs: 2, playtime: 300 },
+            TagCount { value: "Rock".to_owned(), songs: 10, playtime: 2400 },
+        ]);
+    }
+
+    #[test]
+    fn parses_ungrouped_count() {
+        let result: Vec<TagCount> = parse(&[("songs", "10"), ("playtime", "2400")]);
+
+        assert_eq!(result, [TagCount { value: String::new(), songs: 10, playtime: 2400 }]);
+    }
+}
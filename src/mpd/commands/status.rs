@@ -3,7 +3,7 @@ use std::time::Duration;
 use anyhow::anyhow;
 use serde::Serialize;
 
-use super::Volume;
+use super::{Volume, replay_gain::ReplayGainMode};
 use crate::mpd::{FromMpd, LineHandled, ParseErrorExt, errors::MpdError};
 
 #[derive(Debug, Serialize, Default, Clone)]
@@ -44,6 +44,13 @@ pub struct Status {
                                     * explanation. */
     pub updating_db: Option<u32>, // job id
     pub error: Option<String>,    // if there is an error, returns message here
+    /// Not part of MPD's status response, queried separately with
+    /// `replay_gain_status` when the options change
+    pub replay_gain: Option<ReplayGainMode>,
+    /// Not part of MPD's status response, state of rmpc's own connection to
+    /// MPD
+    #[serde(skip)]
+    pub connection: ConnectionState,
 }
 
 impl FromMpd for Status {
@@ -94,6 +101,15 @@ pub enum State {
     Pause,
 }
 
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum ConnectionState {
+    #[default]
+    Connected,
+    /// Connection was lost and rmpc is waiting before the given attempt to
+    /// reconnect
+    Reconnecting { attempt: u32 },
+}
+
 #[derive(Debug, Serialize, Default, Clone, Copy, strum::AsRefStr)]
 pub enum OnOffOneshot {
     #[strum(serialize = "On")]
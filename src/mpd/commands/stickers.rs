@@ -6,6 +6,13 @@ use serde::Serialize;
 
 use crate::mpd::{FromMpd, LineHandled, errors::MpdError};
 
+/// Number of times the song was played through
+pub const PLAY_COUNT: &str = "playCount";
+/// Unix timestamp of the last time the song was played through
+pub const LAST_PLAYED: &str = "lastPlayed";
+/// Number of times the song was skipped
+pub const SKIP_COUNT: &str = "skipCount";
+
 #[derive(Debug, Default, Serialize, IntoIterator, AsRef, AsMut, Into)]
 pub struct Stickers(pub HashMap<String, String>);
 
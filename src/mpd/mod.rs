@@ -1,6 +1,7 @@
 use self::errors::MpdError;
 
 pub mod client;
+pub mod command_list;
 pub mod commands;
 pub mod errors;
 pub mod mpd_client;
@@ -2,6 +2,7 @@ use std::{
     borrow::Cow,
     ops::{Range, RangeInclusive},
     str::FromStr,
+    time::Duration,
 };
 
 use anyhow::Result;
@@ -12,13 +13,20 @@ use strum::AsRefStr;
 use super::{
     FromMpd,
     client::Client,
+    command_list::CommandList,
     commands::{
-        CurrentIndex, IdleEvent, ListFiles, LsInfo, Mounts, Playlist, Song, Status, Update, Volume,
+        CurrentIndex, IdleEvent, ListFiles, LsInfo, Mounts, Neighbors, Playlist, Song, Status,
+        Update, Volume,
         decoders::Decoders,
         list::MpdList,
         list_playlist::FileList,
+        messages::{Channels, Messages},
         mpd_config::MpdConfig,
         outputs::Outputs,
+        partitions::Partitions,
+        plchanges::PosId,
+        replay_gain::{ReplayGainMode, ReplayGainStatus},
+        stats::{Stats, TagCount},
         status::OnOffOneshot,
         stickers::{Sticker, Stickers, StickersWithFile},
         volume::Bound,
@@ -32,7 +40,6 @@ use crate::shared::{ext::error::ErrorExt, macros::status_error};
 type MpdResult<T> = Result<T, MpdError>;
 
 #[derive(AsRefStr, Debug)]
-#[allow(dead_code)]
 pub enum SaveMode {
     #[strum(serialize = "create")]
     Create,
@@ -93,6 +100,14 @@ pub trait MpdClient: Sized {
     fn execute_cmd_list(&mut self) -> MpdResult<ProtoClient<'static, '_, Self>>
     where
         Self: SocketClient;
+    /// Sends all commands of the list in a single round trip and returns the
+    /// result of every command in order
+    fn batch<T: FromMpd + Default>(&mut self, list: &CommandList) -> MpdResult<Vec<MpdResult<T>>>
+    where
+        Self: SocketClient,
+    {
+        list.execute(self)
+    }
 
     fn get_volume(&mut self) -> MpdResult<Volume>;
     fn set_volume(&mut self, volume: Volume) -> MpdResult<()>;
@@ -115,21 +130,53 @@ pub trait MpdClient: Sized {
     fn random(&mut self, enabled: bool) -> MpdResult<()>;
     fn single(&mut self, single: OnOffOneshot) -> MpdResult<()>;
     fn consume(&mut self, consume: OnOffOneshot) -> MpdResult<()>;
+    fn crossfade(&mut self, seconds: u32) -> MpdResult<()>;
+    /// Sets the mixramp threshold in decibels
+    fn mixramp_db(&mut self, db: f32) -> MpdResult<()>;
+    /// Sets the mixramp overlap in seconds, `None` disables mixramp and
+    /// crossfade is used instead
+    fn mixramp_delay(&mut self, seconds: Option<f32>) -> MpdResult<()>;
+    fn replay_gain_mode(&mut self, mode: ReplayGainMode) -> MpdResult<()>;
+    fn replay_gain_status(&mut self) -> MpdResult<ReplayGainMode>;
     // Mounts
     fn mount(&mut self, name: &str, path: &str) -> MpdResult<()>;
     fn unmount(&mut self, name: &str) -> MpdResult<()>;
     fn list_mounts(&mut self) -> MpdResult<Mounts>;
+    fn list_neighbors(&mut self) -> MpdResult<Neighbors>;
     // Current queue
     fn add(&mut self, path: &str) -> MpdResult<()>;
     fn add_relative_index(&mut self, path: &str, id: usize) -> MpdResult<CurrentIndex>;
+    /// Adds the song at the absolute position in the queue
+    fn add_at_position(&mut self, path: &str, position: usize) -> MpdResult<CurrentIndex>;
     fn clear(&mut self) -> MpdResult<()>;
     fn delete_id(&mut self, id: u32) -> MpdResult<()>;
     fn delete_from_queue(&mut self, songs: SingleOrRange) -> MpdResult<()>;
     fn playlist_info(&mut self, fetch_stickers: bool) -> MpdResult<Option<Vec<Song>>>;
+    /// Status along with positions and ids of songs in the queue which
+    /// changed since the given playlist version. Both are fetched in a single
+    /// command list so the playlist version of the status matches the
+    /// changes.
+    fn status_with_plchanges_pos_id(&mut self, version: u32) -> MpdResult<(Status, Vec<PosId>)>;
     fn find(&mut self, filter: &[Filter<'_>]) -> MpdResult<Vec<Song>>;
     fn search(&mut self, filter: &[Filter<'_>]) -> MpdResult<Vec<Song>>;
     fn move_in_queue(&mut self, from: SingleOrRange, to: QueueMoveTarget) -> MpdResult<()>;
     fn move_id(&mut self, id: u32, to: QueueMoveTarget) -> MpdResult<()>;
+    /// Moves each song id to its absolute position, in order, within a
+    /// single command list
+    fn move_ids(&mut self, moves: &[(u32, usize)]) -> MpdResult<()>;
+    /// Swaps songs at the two positions in the queue
+    fn swap(&mut self, first: usize, second: usize) -> MpdResult<()>;
+    /// Swaps songs with the two ids in the queue
+    fn swap_id(&mut self, first: u32, second: u32) -> MpdResult<()>;
+    /// Shuffles the whole queue or only the songs in the given range
+    fn shuffle(&mut self, range: Option<SingleOrRange>) -> MpdResult<()>;
+    /// Sets the priority of songs in the given range. Songs with higher
+    /// priority are played first in random mode.
+    fn set_priority(&mut self, priority: u8, songs: SingleOrRange) -> MpdResult<()>;
+    fn set_priority_id(&mut self, priority: u8, ids: &[u32]) -> MpdResult<()>;
+    /// Sets the portion of the song with the given id which will be played.
+    /// An empty range plays the whole song again.
+    fn set_range_id(&mut self, id: u32, range: PlaybackRange) -> MpdResult<()>;
     fn find_one(&mut self, filter: &[Filter<'_>]) -> MpdResult<Option<Song>>;
     fn find_add(&mut self, filter: &[Filter<'_>]) -> MpdResult<()>;
     fn find_add_next(&mut self, filter: &[Filter<'_>]) -> MpdResult<()>;
@@ -141,6 +188,10 @@ pub trait MpdClient: Sized {
     fn list_files(&mut self, path: Option<&str>) -> MpdResult<ListFiles>;
     fn read_picture(&mut self, path: &str) -> MpdResult<Option<Vec<u8>>>;
     fn albumart(&mut self, path: &str) -> MpdResult<Option<Vec<u8>>>;
+    fn stats(&mut self) -> MpdResult<Stats>;
+    /// Counts songs and their total playtime matching the filter, optionally
+    /// grouped by the given tag. MPD requires at least one of them.
+    fn count(&mut self, filter: &[Filter<'_>], group: Option<Tag>) -> MpdResult<Vec<TagCount>>;
     // Stored playlists
     fn list_playlists(&mut self) -> MpdResult<Vec<Playlist>>;
     fn list_playlist(&mut self, name: &str) -> MpdResult<FileList>;
@@ -177,8 +228,23 @@ pub trait MpdClient: Sized {
     fn toggle_output(&mut self, id: u32) -> MpdResult<()>;
     fn enable_output(&mut self, id: u32) -> MpdResult<()>;
     fn disable_output(&mut self, id: u32) -> MpdResult<()>;
+    // Partitions
+    fn switch_to_partition(&mut self, name: &str) -> MpdResult<()>;
+    fn new_partition(&mut self, name: &str) -> MpdResult<()>;
+    fn delete_partition(&mut self, name: &str) -> MpdResult<()>;
+    fn list_partitions(&mut self) -> MpdResult<Partitions>;
+    fn move_output(&mut self, output_name: &str) -> MpdResult<()>;
     // Decoders
     fn decoders(&mut self) -> MpdResult<Decoders>;
+    // Client to client
+    /// Subscribes to a channel. The channel is created if it does not exist.
+    fn subscribe(&mut self, channel: &str) -> MpdResult<()>;
+    fn unsubscribe(&mut self, channel: &str) -> MpdResult<()>;
+    /// Lists all channels with at least one subscribed client.
+    fn channels(&mut self) -> MpdResult<Channels>;
+    /// Reads and removes messages received on the subscribed channels.
+    fn read_messages(&mut self) -> MpdResult<Messages>;
+    fn send_message(&mut self, channel: &str, message: &str) -> MpdResult<()>;
     // Stickers
 
     /// Reads a sticker value for the specified object.
@@ -377,6 +443,29 @@ impl MpdClient for Client<'_> {
         }
     }
 
+    fn crossfade(&mut self, seconds: u32) -> MpdResult<()> {
+        self.send(&format!("crossfade {seconds}")).and_then(read_ok)
+    }
+
+    fn mixramp_db(&mut self, db: f32) -> MpdResult<()> {
+        self.send(&format!("mixrampdb {db}")).and_then(read_ok)
+    }
+
+    fn mixramp_delay(&mut self, seconds: Option<f32>) -> MpdResult<()> {
+        match seconds {
+            Some(seconds) => self.send(&format!("mixrampdelay {seconds}")).and_then(read_ok),
+            None => self.send("mixrampdelay nan").and_then(read_ok),
+        }
+    }
+
+    fn replay_gain_mode(&mut self, mode: ReplayGainMode) -> MpdResult<()> {
+        self.send(&format!("replay_gain_mode {}", mode.to_mpd_value())).and_then(read_ok)
+    }
+
+    fn replay_gain_status(&mut self) -> MpdResult<ReplayGainMode> {
+        self.send("replay_gain_status").and_then(read_response).map(|v: ReplayGainStatus| v.mode)
+    }
+
     // Mounts
     fn mount(&mut self, name: &str, path: &str) -> MpdResult<()> {
         self.send(&format!("mount {} {}", name.quote_and_escape(), path.quote_and_escape()))
@@ -391,6 +480,10 @@ impl MpdClient for Client<'_> {
         self.send("listmounts").and_then(read_response)
     }
 
+    fn list_neighbors(&mut self) -> MpdResult<Neighbors> {
+        self.send("listneighbors").and_then(read_response)
+    }
+
     // Current queue
     fn add(&mut self, uri: &str) -> MpdResult<()> {
         self.send(&format!("add {}", uri.quote_and_escape())).and_then(read_ok)
@@ -402,6 +495,10 @@ impl MpdClient for Client<'_> {
         self.send(&format!("addid {} +{idx}", uri.quote_and_escape())).and_then(read_response)
     }
 
+    fn add_at_position(&mut self, uri: &str, position: usize) -> MpdResult<CurrentIndex> {
+        self.send(&format!("addid {} {position}", uri.quote_and_escape())).and_then(read_response)
+    }
+
     fn clear(&mut self) -> MpdResult<()> {
         self.send("clear").and_then(read_ok)
     }
@@ -414,6 +511,16 @@ impl MpdClient for Client<'_> {
         self.send(&format!("delete {}", songs.as_mpd_range())).and_then(read_ok)
     }
 
+    fn status_with_plchanges_pos_id(&mut self, version: u32) -> MpdResult<(Status, Vec<PosId>)> {
+        let command =
+            format!("command_list_ok_begin\nstatus\nplchangesposid {version}\ncommand_list_end");
+        let mut c = self.send(&command)?;
+        let status = c.read_response()?;
+        let changes = c.read_response()?;
+        c.read_ok()?;
+        Ok((status, changes))
+    }
+
     fn playlist_info(&mut self, fetch_stickers: bool) -> MpdResult<Option<Vec<Song>>> {
         let songs: Option<Vec<Song>> = self.send("playlistinfo").and_then(read_opt_response)?;
 
@@ -470,6 +577,46 @@ impl MpdClient for Client<'_> {
         self.send(&format!("moveid {id} \"{}\"", to.as_mpd_str())).and_then(read_ok)
     }
 
+    fn move_ids(&mut self, moves: &[(u32, usize)]) -> MpdResult<()> {
+        let mut list = CommandList::default();
+        for (id, pos) in moves {
+            list.move_id(*id, QueueMoveTarget::Absolute(*pos));
+        }
+        self.batch::<()>(&list)?.into_iter().collect()
+    }
+
+    fn swap(&mut self, first: usize, second: usize) -> MpdResult<()> {
+        self.send(&format!("swap {first} {second}")).and_then(read_ok)
+    }
+
+    fn swap_id(&mut self, first: u32, second: u32) -> MpdResult<()> {
+        self.send(&format!("swapid {first} {second}")).and_then(read_ok)
+    }
+
+    fn shuffle(&mut self, range: Option<SingleOrRange>) -> MpdResult<()> {
+        match range {
+            Some(range) => {
+                self.send(&format!("shuffle {}", range.as_mpd_range())).and_then(read_ok)
+            }
+            None => self.send("shuffle").and_then(read_ok),
+        }
+    }
+
+    fn set_priority(&mut self, priority: u8, songs: SingleOrRange) -> MpdResult<()> {
+        self.send(&format!("prio {priority} {}", songs.as_mpd_range())).and_then(read_ok)
+    }
+
+    fn set_priority_id(&mut self, priority: u8, ids: &[u32]) -> MpdResult<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        self.send(&format!("prioid {priority} {}", ids.iter().join(" "))).and_then(read_ok)
+    }
+
+    fn set_range_id(&mut self, id: u32, range: PlaybackRange) -> MpdResult<()> {
+        self.send(&format!("rangeid {id} {}", range.as_mpd_range())).and_then(read_ok)
+    }
+
     fn find_one(&mut self, filter: &[Filter<'_>]) -> MpdResult<Option<Song>> {
         let mut songs: Vec<Song> =
             self.send(&format!("find \"({})\"", filter.to_query_str())).and_then(read_response)?;
@@ -482,7 +629,8 @@ impl MpdClient for Client<'_> {
     }
 
     fn find_add_next(&mut self, filter: &[Filter<'_>]) -> MpdResult<()> {
-        self.send(&format!("findadd \"({})\" +0", filter.to_query_str())).and_then(read_ok)
+        self.send(&format!("findadd \"({})\" position +0", filter.to_query_str()))
+            .and_then(read_ok)
     }
 
     /// Search the database for songs matching FILTER (see Filters) AND add them
@@ -540,6 +688,20 @@ impl MpdClient for Client<'_> {
         self.send(&format!("albumart {} 0", path.quote_and_escape())).and_then(read_bin)
     }
 
+    fn stats(&mut self) -> MpdResult<Stats> {
+        self.send("stats").and_then(read_response)
+    }
+
+    fn count(&mut self, filter: &[Filter<'_>], group: Option<Tag>) -> MpdResult<Vec<TagCount>> {
+        let filter = if filter.is_empty() {
+            String::new()
+        } else {
+            format!(" \"({})\"", filter.to_query_str())
+        };
+        let group = group.map(|group| format!(" group {}", group.as_str())).unwrap_or_default();
+        self.send(&format!("count{filter}{group}")).and_then(read_response)
+    }
+
     // Stored playlists
     fn list_playlists(&mut self) -> MpdResult<Vec<Playlist>> {
         self.send("listplaylists").and_then(read_response)
@@ -695,11 +857,67 @@ impl MpdClient for Client<'_> {
         self.send(&format!("disableoutput {id}")).and_then(read_ok)
     }
 
+    // Partitions
+    fn switch_to_partition(&mut self, name: &str) -> MpdResult<()> {
+        self.send(&format!("partition {}", name.quote_and_escape())).and_then(read_ok)?;
+        // Partition is a property of the connection, remember it so it can be
+        // restored after reconnect
+        self.partition = Some(name.to_owned());
+        Ok(())
+    }
+
+    fn new_partition(&mut self, name: &str) -> MpdResult<()> {
+        self.send(&format!("newpartition {}", name.quote_and_escape())).and_then(read_ok)
+    }
+
+    fn delete_partition(&mut self, name: &str) -> MpdResult<()> {
+        self.send(&format!("delpartition {}", name.quote_and_escape())).and_then(read_ok)
+    }
+
+    fn list_partitions(&mut self) -> MpdResult<Partitions> {
+        self.send("listpartitions").and_then(read_response)
+    }
+
+    fn move_output(&mut self, output_name: &str) -> MpdResult<()> {
+        self.send(&format!("moveoutput {}", output_name.quote_and_escape())).and_then(read_ok)
+    }
+
     // Decoders
     fn decoders(&mut self) -> MpdResult<Decoders> {
         self.send("decoders").and_then(read_response)
     }
 
+    // Client to client
+    fn subscribe(&mut self, channel: &str) -> MpdResult<()> {
+        self.send(&format!("subscribe {}", channel.quote_and_escape())).and_then(read_ok)?;
+        // Subscriptions are a property of the connection, remember them so
+        // they can be restored after reconnect
+        self.subscriptions.insert(channel.to_owned());
+        Ok(())
+    }
+
+    fn unsubscribe(&mut self, channel: &str) -> MpdResult<()> {
+        self.subscriptions.remove(channel);
+        self.send(&format!("unsubscribe {}", channel.quote_and_escape())).and_then(read_ok)
+    }
+
+    fn channels(&mut self) -> MpdResult<Channels> {
+        self.send("channels").and_then(read_response)
+    }
+
+    fn read_messages(&mut self) -> MpdResult<Messages> {
+        self.send("readmessages").and_then(read_response)
+    }
+
+    fn send_message(&mut self, channel: &str, message: &str) -> MpdResult<()> {
+        self.send(&format!(
+            "sendmessage {} {}",
+            channel.quote_and_escape(),
+            message.quote_and_escape()
+        ))
+        .and_then(read_ok)
+    }
+
     // Stickers
     fn sticker(&mut self, uri: &str, key: &str) -> MpdResult<Option<Sticker>> {
         let result: MpdResult<Sticker> = self
@@ -748,43 +966,22 @@ impl MpdClient for Client<'_> {
     /// Default value (empty `HashMap`) is supplied if sticker
     /// for a specific URI cannot be found or an error is encountered
     fn list_stickers_multiple(&mut self, uris: &[&str]) -> MpdResult<Vec<Stickers>> {
-        let mut result = Vec::with_capacity(uris.len());
-        let mut list_ended_with_err = false;
-        let mut i = 0;
-
-        while i < uris.len() {
-            self.start_cmd_list()?;
-
-            for uri in &uris[i..] {
-                self.send(&format!("sticker list song {}", uri.quote_and_escape()))?;
-            }
-            let mut proto = self.execute_cmd_list()?;
-
-            for uri in &uris[i..] {
-                let res: MpdResult<Stickers> = proto.read_response();
-                i += 1;
-                match res {
-                    Ok(v) => {
-                        list_ended_with_err = false;
-                        result.push(v);
-                    }
-                    Err(error) => {
-                        log::warn!(error:?, uri; "Tried to find stickers but unexpected error occured");
-                        result.push(Stickers::default());
-                        list_ended_with_err = true;
-                        break;
-                    }
-                }
-            }
-        }
-
-        // In case the last sticker was fetched successfully we have to read an
-        // OK as an ack for the whole command list
-        if !list_ended_with_err {
-            ProtoClient::new_read_only(self).read_ok()?;
+        let mut list = CommandList::default();
+        for uri in uris {
+            list.list_stickers(uri);
         }
 
-        Ok(result)
+        Ok(self
+            .batch::<Stickers>(&list)?
+            .into_iter()
+            .zip(uris)
+            .map(|(result, uri)| {
+                result.unwrap_or_else(|error| {
+                    log::warn!(error:?, uri; "Tried to find stickers but unexpected error occured");
+                    Stickers::default()
+                })
+            })
+            .collect())
     }
 
     fn find_stickers(&mut self, uri: &str, key: &str) -> MpdResult<StickersWithFile> {
@@ -806,8 +1003,7 @@ impl MpdClient for Client<'_> {
     }
 }
 
-#[derive(Debug)]
-#[allow(dead_code)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum QueueMoveTarget {
     /// relative to the currently playing song; e.g. +0 moves to right after the
     /// current song
@@ -819,7 +1015,7 @@ pub enum QueueMoveTarget {
 }
 
 impl QueueMoveTarget {
-    fn as_mpd_str(&self) -> String {
+    pub(super) fn as_mpd_str(&self) -> String {
         match self {
             QueueMoveTarget::RelativeAdd(v) => format!("+{v}"),
             QueueMoveTarget::RelativeSub(v) => format!("-{v}"),
@@ -828,6 +1024,23 @@ impl QueueMoveTarget {
     }
 }
 
+impl FromStr for QueueMoveTarget {
+    type Err = anyhow::Error;
+
+    /// Parses absolute position or position relative to the current song
+    /// prefixed by + or -
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(value) = s.strip_prefix('+') {
+            Ok(Self::RelativeAdd(value.parse()?))
+        } else if let Some(value) = s.strip_prefix('-') {
+            Ok(Self::RelativeSub(value.parse()?))
+        } else {
+            Ok(Self::Absolute(s.parse()?))
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct SingleOrRange {
     pub start: usize,
@@ -868,6 +1081,62 @@ impl SingleOrRange {
     }
 }
 
+impl FromStr for SingleOrRange {
+    type Err = anyhow::Error;
+
+    /// Parses MPD's `START:END` range or a single position
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some((start, end)) => {
+                let (start, end) = (start.trim().parse()?, end.trim().parse()?);
+                anyhow::ensure!(start < end, "Range start has to be lower than its end");
+                Ok(Self::range(start, end))
+            }
+            None => Ok(Self::single(s.trim().parse()?)),
+        }
+    }
+}
+
+/// Portion of a song to play, in seconds from its start. Both ends are
+/// optional.
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct PlaybackRange {
+    pub start: Option<Duration>,
+    pub end: Option<Duration>,
+}
+
+impl PlaybackRange {
+    pub fn as_mpd_range(&self) -> String {
+        let fmt = |value: Option<Duration>| {
+            value.map(|value| format!("{:.3}", value.as_secs_f64())).unwrap_or_default()
+        };
+        format!("\"{}:{}\"", fmt(self.start), fmt(self.end))
+    }
+}
+
+impl FromStr for PlaybackRange {
+    type Err = anyhow::Error;
+
+    /// Parses `START:END` in seconds where both ends can be omitted. An empty
+    /// string is the same as `:`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |value: &str| -> anyhow::Result<Option<Duration>> {
+            let value = value.trim();
+            if value.is_empty() {
+                return Ok(None);
+            }
+            Ok(Some(Duration::try_from_secs_f64(value.parse()?)?))
+        };
+
+        let (start, end) = s.split_once(':').unwrap_or((s, ""));
+        let result = Self { start: parse(start)?, end: parse(end)? };
+        if let (Some(start), Some(end)) = (result.start, result.end) {
+            anyhow::ensure!(start < end, "Range start has to be lower than its end");
+        }
+        Ok(result)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[allow(unused)]
 pub enum Tag {
@@ -904,6 +1173,9 @@ pub enum FilterKind {
     #[default]
     Contains,
     Regex,
+    /// Filter expression in MPD's syntax which is sent as is, the tag is
+    /// ignored
+    Raw,
 }
 
 #[derive(Debug)]
@@ -933,6 +1205,12 @@ impl<'value> Filter<'value> {
         Self { tag: tag.into(), value: value.into(), kind }
     }
 
+    /// Filter from an expression in MPD's syntax, ie. `(artist == 'Foo')`.
+    /// Outer parentheses are optional.
+    pub fn raw<V: Into<Cow<'value, str>>>(expression: V) -> Self {
+        Self { tag: Tag::Any, value: expression.into(), kind: FilterKind::Raw }
+    }
+
     pub fn with_type(mut self, t: FilterKind) -> Self {
         self.kind = t;
         self
@@ -952,11 +1230,19 @@ impl<'value> Filter<'value> {
             FilterKind::Regex => {
                 format!("{} =~ '{}'", self.tag.as_str(), self.value.escape_filter())
             }
+            FilterKind::Raw => {
+                // The expression is wrapped in parentheses together with the
+                // other filters
+                let value = self.value.trim();
+                let value =
+                    value.strip_prefix('(').and_then(|v| v.strip_suffix(')')).unwrap_or(value);
+                value.replace('\\', "\\\\").replace('"', "\\\"")
+            }
         }
     }
 }
 
-trait StrExt {
+pub(super) trait StrExt {
     fn escape_filter(self) -> String;
     fn quote_and_escape(self) -> String;
 }
@@ -1017,6 +1303,69 @@ impl FilterExt for &[Filter<'_>] {
     }
 }
 
+#[cfg(test)]
+mod single_or_range_tests {
+    use test_case::test_case;
+
+    use super::SingleOrRange;
+
+    #[test_case("5", Some(SingleOrRange::single(5)))]
+    #[test_case("5:10", Some(SingleOrRange::range(5, 10)))]
+    #[test_case("10:5", None)]
+    #[test_case("5:", None)]
+    #[test_case("abc", None)]
+    fn parses_range(input: &str, expected: Option<SingleOrRange>) {
+        assert_eq!(input.parse::<SingleOrRange>().ok(), expected);
+    }
+}
+
+#[cfg(test)]
+mod playback_range_tests {
+    use std::time::Duration;
+
+    use test_case::test_case;
+
+    use super::PlaybackRange;
+
+    #[test_case("10:60.5", Some((Some(10.0), Some(60.5))), "\"10.000:60.500\"")]
+    #[test_case("10:", Some((Some(10.0), None)), "\"10.000:\"")]
+    #[test_case(":60", Some((None, Some(60.0))), "\":60.000\"")]
+    #[test_case("", Some((None, None)), "\":\"")]
+    #[test_case("60:10", None, "")]
+    #[test_case("-5:", None, "")]
+    #[test_case("abc", None, "")]
+    fn parses_range(input: &str, expected: Option<(Option<f64>, Option<f64>)>, mpd: &str) {
+        let result = input.parse::<PlaybackRange>().ok();
+
+        assert_eq!(
+            result,
+            expected.map(|(start, end)| PlaybackRange {
+                start: start.map(Duration::from_secs_f64),
+                end: end.map(Duration::from_secs_f64),
+            })
+        );
+        if let Some(result) = result {
+            assert_eq!(result.as_mpd_range(), mpd);
+        }
+    }
+}
+
+#[cfg(test)]
+mod queue_move_target_tests {
+    use test_case::test_case;
+
+    use super::QueueMoveTarget;
+
+    #[test_case("5", Some(QueueMoveTarget::Absolute(5)))]
+    #[test_case("+0", Some(QueueMoveTarget::RelativeAdd(0)))]
+    #[test_case("-2", Some(QueueMoveTarget::RelativeSub(2)))]
+    #[test_case("+", None)]
+    #[test_case("abc", None)]
+    fn parses_target(input: &str, expected: Option<QueueMoveTarget>) {
+        assert_eq!(input.parse::<QueueMoveTarget>().ok(), expected);
+    }
+}
+
 #[cfg(test)]
 mod strext_tests {
     use crate::mpd::mpd_client::StrExt;
@@ -1049,6 +1398,22 @@ mod filter_tests {
         assert_eq!(input.to_query_str(), format!("({expected} == 'mrs singer')"));
     }
 
+    #[test_case("(artist == 'mrs singer')"; "with parentheses")]
+    #[test_case("artist == 'mrs singer'"; "without parentheses")]
+    fn raw(expression: &str) {
+        let input: &[Filter<'_>] =
+            &[Filter::new(Tag::Album, "the greatest"), Filter::raw(expression)];
+
+        assert_eq!(input.to_query_str(), "(Album == 'the greatest') AND (artist == 'mrs singer')");
+    }
+
+    #[test]
+    fn raw_escapes_quotes() {
+        let input: &[Filter<'_>] = &[Filter::raw(r#"(artist == "mrs \"singer\"")"#)];
+
+        assert_eq!(input.to_query_str(), r#"(artist == \"mrs \\\"singer\\\"\")"#);
+    }
+
     #[test]
     fn starts_with() {
         let input: &[Filter<'_>] =
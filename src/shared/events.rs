@@ -1,17 +1,19 @@
 use std::path::PathBuf;
 
 use anyhow::Result;
-use crossterm::event::KeyEvent;
+use crossbeam::channel::Sender;
 use serde::{Deserialize, Serialize};
 
 use super::{
+    ipc::GetIpcCommand,
+    key_event::KeyEvent,
     lrc::{LrcIndex, LrcIndexEntry},
     mouse_event::MouseEvent,
     mpd_query::{MpdCommand, MpdQuery, MpdQueryResult, MpdQuerySync},
 };
 use crate::{
-    config::{Config, cli::Command, tabs::PaneType, theme::UiConfig},
-    mpd::commands::IdleEvent,
+    config::{Config, address::MpdProfile, cli::Command, tabs::PaneType, theme::UiConfig},
+    mpd::{client::Client, commands::IdleEvent},
     ui::UiAppEvent,
 };
 
@@ -34,6 +36,11 @@ pub(crate) enum WorkRequest {
         path: PathBuf,
     },
     Command(Command),
+    /// Opens a new connection to the server of the profile, the current
+    /// connection is replaced once it succeeds
+    ConnectProfile {
+        profile: MpdProfile,
+    },
 }
 
 #[derive(Debug)]
@@ -42,6 +49,7 @@ pub(crate) enum WorkDone {
     LyricsIndexed { index: LrcIndex },
     SingleLrcIndexed { lrc_entry: Option<LrcIndexEntry> },
     MpdCommandFinished { id: &'static str, target: Option<PaneType>, data: MpdQueryResult },
+    ProfileConnected { profile: MpdProfile, client: Client<'static> },
     None,
 }
 
@@ -58,10 +66,15 @@ pub(crate) enum AppEvent {
     WorkDone(Result<WorkDone>),
     UiEvent(UiAppEvent),
     Reconnected,
-    LostConnection,
+    LostConnection { attempt: u32 },
+    ReconnectNow,
+    /// This client switched to a different partition. MPD does not notify
+    /// the client which switched so it has to reload everything by itself.
+    PartitionSwitched,
     TmuxHook { hook: String },
     ConfigChanged { config: Config, keep_old_theme: bool },
     ThemeChanged { theme: UiConfig },
+    IpcQuery { command: GetIpcCommand, tx: Sender<Result<serde_json::Value>> },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Copy, Eq, Hash, PartialEq)]
@@ -35,6 +35,8 @@ pub mod error {
 pub mod duration {
     pub trait DurationExt {
         fn to_string(&self) -> String;
+        /// Formats long durations like library playtime, ie. `3d 4h 12m`
+        fn to_human_string(&self) -> String;
     }
 
     impl DurationExt for std::time::Duration {
@@ -43,6 +45,35 @@ pub mod duration {
             let min = secs / 60;
             format!("{}:{:0>2}", min, secs - min * 60)
         }
+
+        fn to_human_string(&self) -> String {
+            let secs = self.as_secs();
+            let (days, hours, min) = (secs / 86400, secs / 3600 % 24, secs / 60 % 60);
+            match (days, hours) {
+                (0, 0) => format!("{min}m {}s", secs % 60),
+                (0, _) => format!("{hours}h {min}m"),
+                _ => format!("{days}d {hours}h {min}m"),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::time::Duration;
+
+        use test_case::test_case;
+
+        use super::DurationExt;
+
+        #[test_case(0, "0m 0s")]
+        #[test_case(59, "0m 59s")]
+        #[test_case(754, "12m 34s")]
+        #[test_case(3600, "1h 0m")]
+        #[test_case(86399, "23h 59m")]
+        #[test_case(274_350, "3d 4h 12m")]
+        fn human_string(secs: u64, expected: &str) {
+            assert_eq!(Duration::from_secs(secs).to_human_string(), expected);
+        }
     }
 }
 
@@ -132,16 +163,109 @@ pub mod iter {
 }
 
 pub mod mpd_client {
-    use crate::mpd::{
-        errors::{ErrorCode, MpdError, MpdFailureResponse},
-        mpd_client::MpdClient,
+    use std::{collections::HashMap, path::Path};
+
+    use itertools::Itertools;
+
+    use crate::{
+        config::{
+            rating::{Rating, RatingChange},
+            smart_playlist::SmartPlaylist,
+        },
+        mpd::{
+            command_list::CommandList,
+            commands::{
+                Song,
+                stickers::{LAST_PLAYED, PLAY_COUNT, SKIP_COUNT},
+            },
+            errors::{ErrorCode, MpdError, MpdFailureResponse},
+            mpd_client::{Filter, MpdClient, QueueMoveTarget, Tag, ValueChange},
+            proto_client::SocketClient,
+        },
+        shared::{
+            playlist_file::{self, PlaylistFileEntry, PlaylistFormat, PlaylistImport},
+            queue_changes::QueueChanges,
+            queue_history::{self, QueueEdit},
+            queue_order::{self, QueueOrder},
+        },
     };
 
     pub trait MpdClientExt {
         fn play_last(&mut self, queue_len: usize) -> Result<(), MpdError>;
+        /// Adds the files to the end of the queue in a single command list.
+        /// Files which could not be added are skipped, returns the number of
+        /// added files.
+        fn add_files(&mut self, files: &[&str]) -> Result<usize, MpdError>;
+        /// Writes the changed rating of the song to the configured sticker and
+        /// returns the new value
+        fn rate(
+            &mut self,
+            uri: &str,
+            change: RatingChange,
+            rating: &Rating,
+        ) -> Result<u8, MpdError>;
+        /// Sets the like sticker if it is not present, removes it otherwise.
+        /// Returns whether the song is liked now.
+        fn toggle_like(&mut self, uri: &str, sticker: &str) -> Result<bool, MpdError>;
+        /// Increments a numeric sticker, missing or invalid value is treated
+        /// as zero. Returns the new value.
+        fn increment_sticker(&mut self, uri: &str, sticker: &str) -> Result<u32, MpdError>;
+        /// Increments the play count of the song and sets its last played
+        /// time to `timestamp`
+        fn record_play(&mut self, uri: &str, timestamp: u64) -> Result<(), MpdError>;
+        /// Increments the skip count of the song
+        fn record_skip(&mut self, uri: &str) -> Result<(), MpdError>;
+        /// Fills in stickers of the given songs
+        fn fetch_stickers(&mut self, songs: &mut [Song]) -> Result<(), MpdError>;
+        /// Fetches changes of the queue since the given playlist version.
+        /// `known_positions` maps ids of songs already in the queue to their
+        /// positions. Songs which only moved are not fetched again, only their
+        /// new positions are.
+        fn queue_changes(
+            &mut self,
+            from_version: u32,
+            known_positions: &HashMap<u32, usize>,
+            fetch_stickers: bool,
+        ) -> Result<QueueChanges, MpdError>;
+        /// Changes priority of the queue songs with the given ids relative to
+        /// their current priority in MPD
+        fn change_priority(&mut self, ids: &[u32], change: ValueChange) -> Result<(), MpdError>;
+        /// Resolves the smart playlist to its current list of songs
+        fn smart_playlist_songs(&mut self, playlist: &SmartPlaylist)
+        -> Result<Vec<Song>, MpdError>;
+        /// Adds the current songs of the smart playlist to the end of the
+        /// queue. Returns the number of added songs.
+        fn load_smart_playlist(&mut self, playlist: &SmartPlaylist) -> Result<usize, MpdError>;
+        /// Reorders songs at `positions` in the queue, or the whole queue if
+        /// `positions` is empty. Returns the number of moved songs.
+        fn reorder_queue(
+            &mut self,
+            order: &QueueOrder,
+            positions: &[usize],
+        ) -> Result<usize, MpdError>;
+        /// Deletes, adds and moves songs until the queue consists of the
+        /// given files in their order. Returns the number of changes made.
+        fn restore_queue(&mut self, files: &[String]) -> Result<usize, MpdError>;
+        /// Renders the stored playlist as a playlist file. Paths are relative
+        /// to the music directory unless `absolute` is set.
+        fn export_playlist(
+            &mut self,
+            name: &str,
+            format: PlaylistFormat,
+            absolute: bool,
+        ) -> Result<String, MpdError>;
+        /// Creates a new stored playlist from entries of a playlist file.
+        /// Relative paths are resolved against `playlist_dir` first, entries
+        /// whose file is not in the database are matched by their tags.
+        fn import_playlist(
+            &mut self,
+            name: &str,
+            entries: &[PlaylistFileEntry],
+            playlist_dir: Option<&Path>,
+        ) -> Result<PlaylistImport, MpdError>;
     }
 
-    impl<T: MpdClient> MpdClientExt for T {
+    impl<T: MpdClient + SocketClient> MpdClientExt for T {
         fn play_last(&mut self, queue_len: usize) -> Result<(), MpdError> {
             match self.play_pos(queue_len) {
                 Ok(()) => {}
@@ -157,6 +281,358 @@ pub mod mpd_client {
             };
             Ok(())
         }
+
+        fn add_files(&mut self, files: &[&str]) -> Result<usize, MpdError> {
+            let mut list = CommandList::default();
+            for file in files {
+                list.add(file);
+            }
+
+            let mut added = 0;
+            for (result, file) in self.batch::<()>(&list)?.into_iter().zip(files) {
+                match result {
+                    Ok(()) => added += 1,
+                    Err(err) => log::warn!(err:?, file; "Failed to add file to the queue"),
+                }
+            }
+            Ok(added)
+        }
+
+        fn rate(
+            &mut self,
+            uri: &str,
+            change: RatingChange,
+            rating: &Rating,
+        ) -> Result<u8, MpdError> {
+            let current = self.sticker(uri, &rating.sticker)?;
+            let value = change.apply(current.as_ref().map(|s| s.value.as_str()), rating.max);
+            self.set_sticker(uri, &rating.sticker, &value.to_string())?;
+            Ok(value)
+        }
+
+        fn toggle_like(&mut self, uri: &str, sticker: &str) -> Result<bool, MpdError> {
+            if self.sticker(uri, sticker)?.is_some() {
+                self.delete_sticker(uri, sticker)?;
+                Ok(false)
+            } else {
+                self.set_sticker(uri, sticker, "1")?;
+                Ok(true)
+            }
+        }
+
+        fn increment_sticker(&mut self, uri: &str, sticker: &str) -> Result<u32, MpdError> {
+            let current = self.sticker(uri, sticker)?.and_then(|s| s.value.parse::<u32>().ok());
+            let value = current.unwrap_or(0).saturating_add(1);
+            self.set_sticker(uri, sticker, &value.to_string())?;
+            Ok(value)
+        }
+
+        fn record_play(&mut self, uri: &str, timestamp: u64) -> Result<(), MpdError> {
+            self.increment_sticker(uri, PLAY_COUNT)?;
+            self.set_sticker(uri, LAST_PLAYED, &timestamp.to_string())
+        }
+
+        fn record_skip(&mut self, uri: &str) -> Result<(), MpdError> {
+            self.increment_sticker(uri, SKIP_COUNT)?;
+            Ok(())
+        }
+
+        fn fetch_stickers(&mut self, songs: &mut [Song]) -> Result<(), MpdError> {
+            let uris = songs.iter().map(|song| song.file.as_str()).collect_vec();
+            let stickers = self.list_stickers_multiple(&uris)?;
+            for (song, stickers) in songs.iter_mut().zip(stickers) {
+                song.stickers = Some(stickers.0);
+            }
+            Ok(())
+        }
+
+        fn queue_changes(
+            &mut self,
+            from_version: u32,
+            known_positions: &HashMap<u32, usize>,
+            fetch_stickers: bool,
+        ) -> Result<QueueChanges, MpdError> {
+            let (status, changes) = self.status_with_plchanges_pos_id(from_version)?;
+
+            // Songs reported at their old position changed in place, ie. their
+            // priority, range or stream tags, and are fetched again along with
+            // the new ones
+            let mut list = CommandList::default();
+            for change in changes.iter().filter(|change| {
+                known_positions.get(&change.id).is_none_or(|pos| *pos == change.pos)
+            }) {
+                list.playlist_id(change.id);
+            }
+            // Songs removed in the meantime are skipped, applying the changes
+            // fails without them and the whole queue is fetched instead
+            let mut songs: Vec<Song> =
+                self.batch(&list)?.into_iter().filter_map(Result::ok).collect();
+            if fetch_stickers && !songs.is_empty() {
+                self.fetch_stickers(&mut songs)?;
+            }
+
+            Ok(QueueChanges {
+                from_version,
+                version: status.playlist.unwrap_or_default(),
+                length: status.playlistlength as usize,
+                changes,
+                songs,
+            })
+        }
+
+        fn change_priority(&mut self, ids: &[u32], change: ValueChange) -> Result<(), MpdError> {
+            let mut list = CommandList::default();
+            for id in ids {
+                list.playlist_id(*id);
+            }
+            // Songs removed in the meantime are skipped
+            let songs: Vec<Song> = self.batch(&list)?.into_iter().filter_map(Result::ok).collect();
+
+            // Songs with different priorities are changed by the same amount
+            let changes = songs
+                .iter()
+                .map(|song| {
+                    let current = u32::from(song.priority);
+                    let priority = match change {
+                        ValueChange::Increase(step) => current.saturating_add(step),
+                        ValueChange::Decrease(step) => current.saturating_sub(step),
+                        ValueChange::Set(value) => value,
+                    };
+                    (u8::try_from(priority).unwrap_or(u8::MAX), song.id)
+                })
+                .into_group_map();
+            let mut list = CommandList::default();
+            for (priority, ids) in &changes {
+                list.set_priority_id(*priority, ids);
+            }
+            for result in self.batch::<()>(&list)? {
+                result?;
+            }
+            Ok(())
+        }
+
+        fn smart_playlist_songs(
+            &mut self,
+            playlist: &SmartPlaylist,
+        ) -> Result<Vec<Song>, MpdError> {
+            let mut songs = if playlist.is_sticker_only() {
+                // Only songs with the stickers can match, they are looked up
+                // directly instead of going through the whole library
+                let found = playlist
+                    .stickers
+                    .iter()
+                    .map(|condition| self.find_stickers("", &condition.sticker))
+                    .try_collect()?;
+                let mut list = CommandList::default();
+                for file in playlist.sticker_matches(found) {
+                    list.lsinfo(&file);
+                }
+                self.batch(&list)?.into_iter().filter_map(Result::ok).collect()
+            } else {
+                let filters = playlist.mpd_filters();
+                if playlist.case_sensitive { self.find(&filters)? } else { self.search(&filters)? }
+            };
+            if playlist.needs_stickers() {
+                self.fetch_stickers(&mut songs)?;
+            }
+            Ok(playlist.apply(songs))
+        }
+
+        fn load_smart_playlist(&mut self, playlist: &SmartPlaylist) -> Result<usize, MpdError> {
+            let songs = self.smart_playlist_songs(playlist)?;
+            self.add_files(&songs.iter().map(|song| song.file.as_str()).collect_vec())
+        }
+
+        fn reorder_queue(
+            &mut self,
+            order: &QueueOrder,
+            positions: &[usize],
+        ) -> Result<usize, MpdError> {
+            let queue = self.playlist_info(order.needs_stickers())?.unwrap_or_default();
+            let current = queue.iter().map(|song| song.id).collect_vec();
+            let desired = queue_order::reorder(queue, positions, order);
+            let moves = queue_order::moves(&current, &desired);
+            self.move_ids(&moves)?;
+            Ok(moves.len())
+        }
+
+        fn restore_queue(&mut self, files: &[String]) -> Result<usize, MpdError> {
+            let queue = self.playlist_info(false)?.unwrap_or_default();
+            let edits = queue_history::edits(&queue, files);
+            let mut list = CommandList::default();
+            for edit in &edits {
+                match edit {
+                    QueueEdit::Clear => list.clear_queue(),
+                    QueueEdit::Delete(id) => list.delete_id(*id),
+                    QueueEdit::Add { file, position } => {
+                        list.add_to_position(file, QueueMoveTarget::Absolute(*position))
+                    }
+                    QueueEdit::Move { id, position } => {
+                        list.move_id(*id, QueueMoveTarget::Absolute(*position))
+                    }
+                };
+            }
+            for result in self.batch::<()>(&list)? {
+                result?;
+            }
+            Ok(edits.len())
+        }
+
+        fn export_playlist(
+            &mut self,
+            name: &str,
+            format: PlaylistFormat,
+            absolute: bool,
+        ) -> Result<String, MpdError> {
+            let music_directory = if absolute {
+                let Some(config) = self.config() else {
+                    return Err(MpdError::Generic(
+                        "Cannot export absolute paths without socket connection to MPD".to_owned(),
+                    ));
+                };
+                Some(config.music_directory.clone())
+            } else {
+                None
+            };
+            let songs = self.list_playlist_info(name, None)?;
+            Ok(playlist_file::render(format, name, &songs, music_directory.as_deref()))
+        }
+
+        fn import_playlist(
+            &mut self,
+            name: &str,
+            entries: &[PlaylistFileEntry],
+            playlist_dir: Option<&Path>,
+        ) -> Result<PlaylistImport, MpdError> {
+            if self.list_playlists()?.iter().any(|playlist| playlist.name == name) {
+                return Err(MpdError::Generic(format!("Playlist '{name}' already exists")));
+            }
+            let music_directory = self.config().map(|config| config.music_directory.clone());
+
+            let mut files = Vec::with_capacity(entries.len());
+            let mut missing = Vec::new();
+            for entry in entries {
+                match resolve_entry(self, entry, music_directory.as_deref(), playlist_dir)? {
+                    Some(file) => files.push(file),
+                    None => missing.push(entry.location.clone()),
+                }
+            }
+            if files.is_empty() {
+                return Err(MpdError::Generic(format!(
+                    "None of the {} songs were found in the database",
+                    entries.len()
+                )));
+            }
+
+            for file in &files {
+                self.add_to_playlist(name, file, None)?;
+            }
+            Ok(PlaylistImport { name: name.to_owned(), added: files.len(), missing })
+        }
+    }
+
+    /// Finds the database file for a playlist file entry. The location is
+    /// tried relative to the playlist file and to the music directory before
+    /// falling back to matching the entry's tags.
+    fn resolve_entry(
+        client: &mut impl MpdClient,
+        entry: &PlaylistFileEntry,
+        music_directory: Option<&str>,
+        playlist_dir: Option<&Path>,
+    ) -> Result<Option<String>, MpdError> {
+        if entry.location.contains("://") {
+            return Ok(Some(entry.location.clone()));
+        }
+
+        let location = Path::new(&entry.location);
+        let music_directory = music_directory.map(Path::new);
+        let mut candidates = Vec::new();
+        if location.is_absolute() {
+            if let Some(relative) = music_directory.and_then(|dir| location.strip_prefix(dir).ok())
+            {
+                candidates.push(relative.to_string_lossy().into_owned());
+            }
+        } else {
+            let relative_dir = match (playlist_dir, music_directory) {
+                (Some(playlist_dir), Some(music_directory)) => {
+                    playlist_dir.strip_prefix(music_directory).ok()
+                }
+                _ => None,
+            };
+            if let Some(dir) = relative_dir {
+                candidates.push(dir.join(location).to_string_lossy().into_owned());
+            }
+            candidates.push(entry.location.clone());
+        }
+
+        for candidate in candidates {
+            let candidate = playlist_file::normalize_path(&candidate);
+            if let Some(song) = client.find_one(&[Filter::new(Tag::File, candidate.as_str())])? {
+                return Ok(Some(song.file));
+            }
+        }
+
+        let Some(title) = entry.title_or_file_stem() else {
+            return Ok(None);
+        };
+        let songs = client.search(&[Filter::new(Tag::Title, title.as_str())])?;
+        Ok(playlist_file::best_match(entry, &songs).map(|song| song.file.clone()))
+    }
+
+    #[cfg(test)]
+    #[allow(clippy::unwrap_used)]
+    mod tests {
+        use rstest::rstest;
+
+        use super::MpdClientExt;
+        use crate::{
+            mpd::{errors::MpdError, mpd_client::MpdClient},
+            shared::playlist_file::{PlaylistFileEntry, PlaylistFormat},
+            tests::fixtures::mpd_client::{TestMpdClient, client},
+        };
+
+        fn entry(location: &str, title: Option<&str>) -> PlaylistFileEntry {
+            PlaylistFileEntry {
+                location: location.to_owned(),
+                title: title.map(ToOwned::to_owned),
+                ..Default::default()
+            }
+        }
+
+        #[rstest]
+        fn import_playlist_resolves_paths_and_tags(mut client: TestMpdClient) {
+            let entries = [
+                entry("./artist_1_album_1_file_1", None),
+                entry("/elsewhere/song.mp3", Some("ARTIST_2_ALBUM_1_FILE_3")),
+                entry("missing.mp3", None),
+            ];
+
+            let result = client.import_playlist("imported", &entries, None).unwrap();
+
+            assert_eq!(result.added, 2);
+            assert_eq!(result.missing, ["missing.mp3"]);
+            assert_eq!(client.list_playlist("imported").unwrap().0, [
+                "artist_1_album_1_file_1",
+                "artist_2_album_1_file_3"
+            ]);
+        }
+
+        #[rstest]
+        fn import_playlist_fails_when_playlist_exists(mut client: TestMpdClient) {
+            let entries = [entry("artist_1_album_1_file_1", None)];
+
+            let result = client.import_playlist("playlist_2", &entries, None);
+
+            assert!(matches!(result, Err(MpdError::Generic(_))));
+        }
+
+        #[rstest]
+        fn export_playlist_lists_songs(mut client: TestMpdClient) {
+            let result = client.export_playlist("playlist_3", PlaylistFormat::M3u8, false).unwrap();
+
+            assert!(result.starts_with("#EXTM3U\n#PLAYLIST:playlist_3\n"));
+            assert_eq!(result.lines().filter(|line| !line.starts_with('#')).count(), 10);
+        }
     }
 }
 
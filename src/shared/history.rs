@@ -0,0 +1,242 @@
+use std::{
+    io::Write,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Result;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{mpd::commands::Song, shared::env::ENV};
+
+/// Only this many most recent entries are kept
+const MAX_ENTRIES: usize = 5000;
+const FILE_NAME: &str = "history.jsonl";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub file: String,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    /// Unix timestamp of when the song started playing
+    pub played_at: i64,
+}
+
+impl HistoryEntry {
+    pub fn new(song: &Song) -> Self {
+        let played_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .and_then(|d| i64::try_from(d.as_secs()).ok())
+            .unwrap_or_default();
+        Self {
+            file: song.file.clone(),
+            title: song.title().cloned(),
+            artist: song.artist().cloned(),
+            album: song.album().cloned(),
+            played_at,
+        }
+    }
+}
+
+/// Songs played in this and previous sessions, oldest first. Persisted as
+/// one json entry per line in `cache_dir` or the platform state directory.
+#[derive(Debug, Default)]
+pub struct History {
+    entries: Vec<HistoryEntry>,
+    path: Option<PathBuf>,
+}
+
+impl History {
+    /// Loads the history from the cache directory, or the platform state
+    /// directory if no cache directory is configured. History is kept only in
+    /// memory if neither of them is available or the file cannot be read.
+    pub fn load(cache_dir: Option<&str>) -> Self {
+        let Some(dir) = cache_dir.map(PathBuf::from).or_else(default_dir) else {
+            return Self::default();
+        };
+        let path = dir.join(FILE_NAME);
+
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => {
+                log::error!(err:?, path:?; "Failed to read history file");
+                return Self::default();
+            }
+        };
+
+        let (entries, needs_rewrite) = parse(&content);
+        let result = Self { entries, path: Some(path) };
+        if needs_rewrite {
+            if let Err(err) = result.rewrite() {
+                log::error!(err:?; "Failed to trim history file");
+            }
+        }
+        result
+    }
+
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    pub fn push(&mut self, entry: HistoryEntry) {
+        if let Err(err) = self.append(&entry) {
+            log::error!(err:?; "Failed to write history entry");
+        }
+        self.entries.push(entry);
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.drain(..self.entries.len() - MAX_ENTRIES);
+        }
+    }
+
+    fn append(&self, entry: &HistoryEntry) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        Ok(())
+    }
+
+    fn rewrite(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let content: String = self
+            .entries
+            .iter()
+            .map(|entry| serde_json::to_string(entry).map(|line| line + "\n"))
+            .try_collect()?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+/// `$XDG_STATE_HOME/rmpc` or `~/.local/state/rmpc`
+fn default_dir() -> Option<PathBuf> {
+    let mut path = PathBuf::new();
+    if let Some(dir) = ENV.var_os("XDG_STATE_HOME").filter(|dir| !dir.is_empty()) {
+        path.push(dir);
+    } else if let Some(home) = ENV.var_os("HOME") {
+        path.push(home);
+        path.push(".local");
+        path.push("state");
+    } else {
+        return None;
+    }
+    path.push(env!("CARGO_CRATE_NAME"));
+    Some(path)
+}
+
+/// Parses the history file content. Invalid lines are skipped. Returns
+/// whether the file should be rewritten because it contained invalid lines
+/// or too many entries.
+fn parse(content: &str) -> (Vec<HistoryEntry>, bool) {
+    let mut needs_rewrite = false;
+    let mut entries = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str::<HistoryEntry>(line) {
+            Ok(entry) => Some(entry),
+            Err(err) => {
+                log::warn!(err:?, line; "Skipping invalid history entry");
+                needs_rewrite = true;
+                None
+            }
+        })
+        .collect_vec();
+
+    if entries.len() > MAX_ENTRIES {
+        entries.drain(..entries.len() - MAX_ENTRIES);
+        needs_rewrite = true;
+    }
+
+    (entries, needs_rewrite)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::{
+        path::PathBuf,
+        sync::{LazyLock, Mutex},
+    };
+
+    use test_case::test_case;
+
+    use super::{HistoryEntry, MAX_ENTRIES, default_dir, parse};
+    use crate::shared::env::ENV;
+
+    static TEST_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+    fn entry(file: &str, played_at: i64) -> HistoryEntry {
+        HistoryEntry {
+            file: file.to_owned(),
+            title: Some("title".to_owned()),
+            artist: None,
+            album: None,
+            played_at,
+        }
+    }
+
+    #[test]
+    fn parses_entries_and_skips_invalid_lines() {
+        let content = [
+            serde_json::to_string(&entry("a.flac", 1)).unwrap_or_default(),
+            "garbage".to_owned(),
+            String::new(),
+            serde_json::to_string(&entry("b.flac", 2)).unwrap_or_default(),
+        ]
+        .join("\n");
+
+        let (entries, needs_rewrite) = parse(&content);
+
+        assert_eq!(entries, vec![entry("a.flac", 1), entry("b.flac", 2)]);
+        assert!(needs_rewrite);
+    }
+
+    #[test]
+    fn keeps_only_most_recent_entries() {
+        let content = (0..=MAX_ENTRIES)
+            .map(|i| {
+                serde_json::to_string(&entry("a.flac", i64::try_from(i).unwrap_or_default()))
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let (entries, needs_rewrite) = parse(&content);
+
+        assert_eq!(entries.len(), MAX_ENTRIES);
+        assert_eq!(entries.first().map(|e| e.played_at), Some(1));
+        assert!(needs_rewrite);
+    }
+
+    #[test_case(Some("/state"), Some("/home/u"), Some("/state/rmpc"); "xdg state home")]
+    #[test_case(Some(""), Some("/home/u"), Some("/home/u/.local/state/rmpc"); "empty xdg")]
+    #[test_case(None, Some("/home/u"), Some("/home/u/.local/state/rmpc"); "home")]
+    #[test_case(None, None, None; "neither")]
+    fn resolves_default_dir(
+        state_home: Option<&str>,
+        home: Option<&str>,
+        expected: Option<&str>,
+    ) {
+        let _guard = TEST_LOCK.lock().unwrap();
+
+        ENV.clear();
+        if let Some(state_home) = state_home {
+            ENV.set("XDG_STATE_HOME".to_string(), state_home.to_string());
+        }
+        if let Some(home) = home {
+            ENV.set("HOME".to_string(), home.to_string());
+        }
+
+        assert_eq!(default_dir(), expected.map(PathBuf::from));
+    }
+}
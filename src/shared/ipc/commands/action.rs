@@ -0,0 +1,105 @@
+use std::os::unix::net::UnixStream;
+
+use anyhow::{Result, anyhow};
+use crossbeam::channel::Sender;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AppEvent,
+    WorkRequest,
+    config::{
+        Config,
+        cli::ActionSection,
+        keys::{CommonActionFile, GlobalActionFile, PlaylistsActionsFile, QueueActionsFile},
+    },
+    shared::{ipc::SocketCommandExecute, key_event::InjectedAction},
+};
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+pub(crate) enum ActionIpcCommand {
+    Global(GlobalActionFile),
+    Navigation(CommonActionFile),
+    Queue(QueueActionsFile),
+    Playlists(PlaylistsActionsFile),
+}
+
+impl ActionIpcCommand {
+    /// Parses action written in the same format as in the keybinds section of
+    /// the config. Without explicit section the global, navigation, queue and
+    /// playlists actions are tried in this order.
+    pub(crate) fn parse(action: &str, section: Option<ActionSection>) -> Result<Self> {
+        let global = || ron::de::from_str(action).map(ActionIpcCommand::Global);
+        let navigation = || ron::de::from_str(action).map(ActionIpcCommand::Navigation);
+        let queue = || ron::de::from_str(action).map(ActionIpcCommand::Queue);
+        let playlists = || ron::de::from_str(action).map(ActionIpcCommand::Playlists);
+
+        match section {
+            Some(ActionSection::Global) => global().ok(),
+            Some(ActionSection::Navigation) => navigation().ok(),
+            Some(ActionSection::Queue) => queue().ok(),
+            Some(ActionSection::Playlists) => playlists().ok(),
+            None => global()
+                .or_else(|_| navigation())
+                .or_else(|_| queue())
+                .or_else(|_| playlists())
+                .ok(),
+        }
+        .ok_or_else(|| anyhow!("Unknown action '{action}'"))
+    }
+}
+
+impl SocketCommandExecute for ActionIpcCommand {
+    fn execute(
+        self,
+        _stream: &mut UnixStream,
+        event_tx: &Sender<AppEvent>,
+        _work_tx: &Sender<WorkRequest>,
+        _config: &Config,
+    ) -> Result<()> {
+        let action = match self {
+            ActionIpcCommand::Global(action) => InjectedAction::Global(action.into()),
+            ActionIpcCommand::Navigation(action) => InjectedAction::Common(action.into()),
+            ActionIpcCommand::Queue(action) => InjectedAction::Queue(action.into()),
+            ActionIpcCommand::Playlists(action) => InjectedAction::Playlists(action.into()),
+        };
+        event_tx.send(AppEvent::UserKeyInput(action.into()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::needless_pass_by_value)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case("TogglePause", None, ActionIpcCommand::Global(GlobalActionFile::TogglePause))]
+    #[test_case(
+        "SwitchToTab(\"Queue\")",
+        None,
+        ActionIpcCommand::Global(GlobalActionFile::SwitchToTab("Queue".to_owned()))
+    )]
+    #[test_case("Down", None, ActionIpcCommand::Navigation(CommonActionFile::Down))]
+    #[test_case("Delete", None, ActionIpcCommand::Navigation(CommonActionFile::Delete))]
+    #[test_case(
+        "Delete",
+        Some(ActionSection::Queue),
+        ActionIpcCommand::Queue(QueueActionsFile::Delete)
+    )]
+    #[test_case("JumpToCurrent", None, ActionIpcCommand::Queue(QueueActionsFile::JumpToCurrent))]
+    #[test_case(
+        "ExportPlaylist",
+        None,
+        ActionIpcCommand::Playlists(PlaylistsActionsFile::ExportPlaylist)
+    )]
+    fn parses_action(input: &str, section: Option<ActionSection>, expected: ActionIpcCommand) {
+        assert_eq!(ActionIpcCommand::parse(input, section).unwrap(), expected);
+    }
+
+    #[test_case("Nonexistent", None)]
+    #[test_case("TogglePause", Some(ActionSection::Queue))]
+    fn rejects_unknown_action(input: &str, section: Option<ActionSection>) {
+        assert!(ActionIpcCommand::parse(input, section).is_err());
+    }
+}
@@ -0,0 +1,51 @@
+use std::{io::Write, os::unix::net::UnixStream, time::Duration};
+
+use anyhow::{Context, Result};
+use crossbeam::channel::{Sender, bounded};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AppEvent,
+    WorkRequest,
+    config::Config,
+    shared::ipc::{SocketCommandExecute, SocketResponse},
+};
+
+/// How long to wait for the main loop to answer the query before giving up
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GetIpcCommand {
+    ActiveTab,
+    SelectedSongs,
+    FocusedPane,
+}
+
+impl SocketCommandExecute for GetIpcCommand {
+    fn execute(
+        self,
+        stream: &mut UnixStream,
+        event_tx: &Sender<AppEvent>,
+        _work_tx: &Sender<WorkRequest>,
+        _config: &Config,
+    ) -> Result<()> {
+        let (tx, rx) = bounded(1);
+        event_tx.send(AppEvent::IpcQuery { command: self, tx })?;
+
+        // The client waits for a response so one has to be written even when
+        // the main loop did not answer
+        let response = match rx.recv_timeout(RESPONSE_TIMEOUT) {
+            Ok(Ok(data)) => SocketResponse::Data(data),
+            Ok(Err(err)) => SocketResponse::Error(err.to_string()),
+            Err(err) => {
+                log::error!(err:?; "Main loop did not answer the ipc query");
+                SocketResponse::Error("rmpc did not answer the query in time".to_owned())
+            }
+        };
+
+        serde_json::to_writer(&mut *stream, &response)
+            .context("Failed to write response to socket")?;
+        stream.write_all(b"\n").context("Failed to write response to socket")?;
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{os::unix::net::UnixStream, path::PathBuf};
 
 use anyhow::Result;
 use crossbeam::channel::Sender;
@@ -14,6 +14,7 @@ pub(crate) struct IndexLrcCommand {
 impl SocketCommandExecute for IndexLrcCommand {
     fn execute(
         self,
+        _stream: &mut UnixStream,
         _event_tx: &Sender<AppEvent>,
         work_tx: &Sender<WorkRequest>,
         _config: &Config,
@@ -0,0 +1,32 @@
+use std::os::unix::net::UnixStream;
+
+use anyhow::Result;
+use crossbeam::channel::Sender;
+use crossterm::event::KeyEvent;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AppEvent,
+    WorkRequest,
+    config::{Config, keys::Key},
+    shared::ipc::SocketCommandExecute,
+};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct KeybindCommand {
+    pub(crate) key: Key,
+}
+
+impl SocketCommandExecute for KeybindCommand {
+    fn execute(
+        self,
+        _stream: &mut UnixStream,
+        event_tx: &Sender<AppEvent>,
+        _work_tx: &Sender<WorkRequest>,
+        _config: &Config,
+    ) -> Result<()> {
+        let key = KeyEvent::new(self.key.key, self.key.modifiers);
+        event_tx.send(AppEvent::UserKeyInput(key.into()))?;
+        Ok(())
+    }
+}
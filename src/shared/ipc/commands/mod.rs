@@ -1,7 +1,10 @@
 use std::path::PathBuf;
 
-use anyhow::{Context, Result};
+use action::ActionIpcCommand;
+use anyhow::{Context, Result, anyhow};
+use get::GetIpcCommand;
 use index_lrc::IndexLrcCommand;
+use keybind::KeybindCommand;
 use set::SetIpcCommand;
 use status_message::StatusMessageCommand;
 use tmux::TmuxHookCommand;
@@ -9,10 +12,13 @@ use tmux::TmuxHookCommand;
 use super::SocketCommand;
 use crate::config::{
     ConfigFile,
-    cli::{RemoteCmd, SetCommand},
+    cli::{GetCommand, RemoteCmd, SetCommand},
 };
 
+pub(super) mod action;
+pub(super) mod get;
 pub(super) mod index_lrc;
+pub(super) mod keybind;
 pub(super) mod set;
 pub(super) mod status_message;
 pub(super) mod tmux;
@@ -38,6 +44,17 @@ impl TryFrom<RemoteCmd> for SocketCommand {
                 }))
             }
             RemoteCmd::Tmux { hook } => Ok(SocketCommand::TmuxHook(TmuxHookCommand { hook })),
+            RemoteCmd::Get { command } => Ok(SocketCommand::Get(match command {
+                GetCommand::ActiveTab => GetIpcCommand::ActiveTab,
+                GetCommand::SelectedSongs => GetIpcCommand::SelectedSongs,
+                GetCommand::FocusedPane => GetIpcCommand::FocusedPane,
+            })),
+            RemoteCmd::Keybind { key } => Ok(SocketCommand::Keybind(KeybindCommand {
+                key: key.parse().map_err(|err| anyhow!("Invalid key '{key}': {err}"))?,
+            })),
+            RemoteCmd::Action { action, section } => {
+                Ok(SocketCommand::Action(ActionIpcCommand::parse(&action, section)?))
+            }
             RemoteCmd::Set { command: SetCommand::Config { path } } if path == "-" => Ok(
                 SocketCommand::Set(SetIpcCommand::Config(ron::de::from_reader(std::io::stdin())?)),
             ),
@@ -1,3 +1,5 @@
+use std::os::unix::net::UnixStream;
+
 use anyhow::Result;
 use crossbeam::channel::Sender;
 use serde::{Deserialize, Serialize};
@@ -21,13 +23,14 @@ pub(crate) enum SetIpcCommand {
 impl SocketCommandExecute for SetIpcCommand {
     fn execute(
         self,
+        _stream: &mut UnixStream,
         event_tx: &Sender<AppEvent>,
         _work_tx: &Sender<WorkRequest>,
         _config: &Config,
     ) -> Result<()> {
         match self {
             SetIpcCommand::Config(config) => {
-                let config = config.into_config(None, None, None, None, true)?;
+                let config = config.into_config(None, None, None, None, None, true)?;
                 Ok(event_tx.send(AppEvent::ConfigChanged { config, keep_old_theme: true })?)
             }
             SetIpcCommand::Theme(theme) => {
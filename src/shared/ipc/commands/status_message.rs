@@ -1,3 +1,5 @@
+use std::os::unix::net::UnixStream;
+
 use anyhow::Result;
 use crossbeam::channel::Sender;
 use serde::{Deserialize, Serialize};
@@ -18,6 +20,7 @@ pub(crate) struct StatusMessageCommand {
 impl SocketCommandExecute for StatusMessageCommand {
     fn execute(
         self,
+        _stream: &mut UnixStream,
         event_tx: &Sender<AppEvent>,
         _work_tx: &Sender<WorkRequest>,
         _config: &Config,
@@ -1,3 +1,5 @@
+use std::os::unix::net::UnixStream;
+
 use anyhow::Result;
 use crossbeam::channel::Sender;
 use serde::{Deserialize, Serialize};
@@ -12,6 +14,7 @@ pub(crate) struct TmuxHookCommand {
 impl SocketCommandExecute for TmuxHookCommand {
     fn execute(
         self,
+        _stream: &mut UnixStream,
         event_tx: &Sender<AppEvent>,
         _work_tx: &Sender<WorkRequest>,
         _config: &Config,
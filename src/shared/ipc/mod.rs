@@ -1,6 +1,12 @@
-use std::{io::Write, os::unix::net::UnixStream, path::PathBuf};
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::Shutdown,
+    os::unix::net::UnixStream,
+    path::PathBuf,
+    time::Duration,
+};
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use crossbeam::channel::Sender;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
@@ -10,7 +16,9 @@ use crate::{
     WorkRequest,
     config::{Config, cli::RemoteCmd},
     shared::ipc::commands::{
+        action::ActionIpcCommand,
         index_lrc::IndexLrcCommand,
+        keybind::KeybindCommand,
         set::SetIpcCommand,
         status_message::StatusMessageCommand,
         tmux::TmuxHookCommand,
@@ -19,6 +27,8 @@ use crate::{
 
 mod commands;
 
+pub(crate) use commands::get::GetIpcCommand;
+
 pub fn get_socket_path(pid: u32) -> PathBuf {
     let mut temp = std::env::temp_dir();
     temp.push(format!("rmpc-{pid}.sock"));
@@ -47,8 +57,11 @@ pub fn list_all_socket_paths() -> Result<impl Iterator<Item = PathBuf>> {
 }
 
 pub(crate) trait SocketCommandExecute {
+    /// Commands which answer the client write their response into `stream`,
+    /// others leave it untouched and the client receives just EOF
     fn execute(
         self,
+        stream: &mut UnixStream,
         event_tx: &Sender<AppEvent>,
         work_tx: &Sender<WorkRequest>,
         config: &Config,
@@ -61,30 +74,132 @@ pub(crate) enum SocketCommand {
     StatusMessage(StatusMessageCommand),
     TmuxHook(TmuxHookCommand),
     Set(SetIpcCommand),
+    Get(GetIpcCommand),
+    Keybind(KeybindCommand),
+    Action(ActionIpcCommand),
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) enum SocketResponse {
+    Data(serde_json::Value),
+    Error(String),
 }
 
 impl SocketCommandExecute for SocketCommand {
     fn execute(
         self,
+        stream: &mut UnixStream,
         event_tx: &Sender<AppEvent>,
         work_tx: &Sender<WorkRequest>,
         config: &Config,
     ) -> Result<()> {
         match self {
-            SocketCommand::IndexLrc(cmd) => cmd.execute(event_tx, work_tx, config),
-            SocketCommand::StatusMessage(cmd) => cmd.execute(event_tx, work_tx, config),
-            SocketCommand::TmuxHook(cmd) => cmd.execute(event_tx, work_tx, config),
-            SocketCommand::Set(cmd) => cmd.execute(event_tx, work_tx, config),
+            SocketCommand::IndexLrc(cmd) => cmd.execute(stream, event_tx, work_tx, config),
+            SocketCommand::StatusMessage(cmd) => cmd.execute(stream, event_tx, work_tx, config),
+            SocketCommand::TmuxHook(cmd) => cmd.execute(stream, event_tx, work_tx, config),
+            SocketCommand::Set(cmd) => cmd.execute(stream, event_tx, work_tx, config),
+            SocketCommand::Get(cmd) => cmd.execute(stream, event_tx, work_tx, config),
+            SocketCommand::Keybind(cmd) => cmd.execute(stream, event_tx, work_tx, config),
+            SocketCommand::Action(cmd) => cmd.execute(stream, event_tx, work_tx, config),
         }
     }
 }
 
 impl RemoteCmd {
-    pub fn write_to_socket(self, path: &PathBuf) -> Result<()> {
+    /// Sends the command to the rmpc instance listening on `path` and returns
+    /// its response if the command has any
+    pub fn write_to_socket(self, path: &PathBuf) -> Result<Option<serde_json::Value>> {
         let cmd = SocketCommand::try_from(self)?;
         let cmd = serde_json::to_string(&cmd).context("Failed to serialize command.")?;
 
         let mut stream = UnixStream::connect(path).context("Failed to connect to socket")?;
-        stream.write_all(cmd.as_bytes()).context("Failed to write command to socket.")
+        stream.write_all(cmd.as_bytes()).context("Failed to write command to socket.")?;
+        stream.write_all(b"\n").context("Failed to write command to socket.")?;
+        stream.shutdown(Shutdown::Write).context("Failed to finish writing to socket.")?;
+
+        stream
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .context("Failed to set read timeout on socket.")?;
+        let mut buf = String::new();
+        BufReader::new(stream).read_line(&mut buf).context("Failed to read response.")?;
+        if buf.trim().is_empty() {
+            return Ok(None);
+        }
+
+        match serde_json::from_str(&buf).context("Failed to parse response.")? {
+            SocketResponse::Data(data) => Ok(Some(data)),
+            SocketResponse::Error(err) => bail!(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader},
+        os::unix::net::UnixListener,
+    };
+
+    use crossbeam::channel::unbounded;
+
+    use super::*;
+    use crate::config::cli::GetCommand;
+
+    fn serve_get_command(
+        path: &PathBuf,
+        answer: impl FnOnce(Sender<Result<serde_json::Value>>) + Send + 'static,
+    ) -> Result<Option<serde_json::Value>> {
+        let _ = std::fs::remove_file(path);
+        let listener = UnixListener::bind(path).expect("socket to bind");
+        let (event_tx, event_rx) = unbounded::<AppEvent>();
+        let (work_tx, _work_rx) = unbounded::<WorkRequest>();
+
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().expect("client to connect");
+            let mut reader = BufReader::new(stream);
+            let mut buf = String::new();
+            reader.read_line(&mut buf).expect("command to be read");
+            let command: SocketCommand = serde_json::from_str(&buf).expect("command to parse");
+            command
+                .execute(reader.get_mut(), &event_tx, &work_tx, &Config::default())
+                .expect("command to succeed");
+        });
+
+        let ui = std::thread::spawn(move || {
+            let Ok(AppEvent::IpcQuery { command, tx }) = event_rx.recv() else {
+                panic!("expected ipc query");
+            };
+            assert_eq!(command, GetIpcCommand::ActiveTab);
+            answer(tx);
+        });
+
+        let result = RemoteCmd::Get { command: GetCommand::ActiveTab }.write_to_socket(path);
+
+        server.join().expect("server not to panic");
+        ui.join().expect("ui not to panic");
+        let _ = std::fs::remove_file(path);
+        result
+    }
+
+    #[test]
+    fn get_command_reports_unanswered_query() {
+        let path =
+            std::env::temp_dir().join(format!("rmpc-test-unanswered-{}.sock", std::process::id()));
+
+        // Dropping the sender without answering ends the wait right away
+        let result = serve_get_command(&path, drop);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn get_command_receives_response() {
+        let path = std::env::temp_dir().join(format!("rmpc-test-{}.sock", std::process::id()));
+
+        let result = serve_get_command(&path, |tx| {
+            tx.send(Ok(serde_json::Value::String("Queue".to_owned()))).expect("send to succeed");
+        });
+
+        assert_eq!(result.expect("remote command to succeed"), Some("Queue".into()));
     }
 }
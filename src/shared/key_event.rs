@@ -1,29 +1,141 @@
-use crossterm::event::{KeyCode, KeyEvent as CKeyEvent};
+use std::collections::HashMap;
+
+use crossterm::event::{KeyCode, KeyEvent as CKeyEvent, KeyModifiers};
 
 #[cfg(debug_assertions)]
 use crate::config::keys::LogsActions;
 use crate::{
-    config::keys::{CommonAction, GlobalAction, QueueActions},
+    config::keys::{CommonAction, GlobalAction, Key, KeySequence, PlaylistsActions, QueueActions},
     context::AppContext,
 };
 
+/// Action which was not produced by a key press but requested directly, ie.
+/// over the IPC socket
+#[derive(Debug, Clone)]
+pub enum InjectedAction {
+    Global(GlobalAction),
+    Queue(QueueActions),
+    Playlists(PlaylistsActions),
+    Common(CommonAction),
+}
+
 #[derive(Debug, Clone)]
 pub struct KeyEvent {
     inner: CKeyEvent,
+    injected: Option<InjectedAction>,
     already_handled: bool,
+    /// The key together with [`AppContext::pending_keys`] forms a prefix of
+    /// a longer keybind and more keys are expected
+    pending_sequence: bool,
+    /// Resolve the pending keys as they are without waiting for more
+    sequence_timed_out: bool,
+    /// Digit which is part of a count typed before an action, ie. the `5` in
+    /// `5j`
+    count_digit: Option<u32>,
 }
 
 impl From<CKeyEvent> for KeyEvent {
     fn from(value: CKeyEvent) -> Self {
-        Self { inner: value, already_handled: false }
+        Self {
+            inner: value,
+            injected: None,
+            already_handled: false,
+            pending_sequence: false,
+            sequence_timed_out: false,
+            count_digit: None,
+        }
+    }
+}
+
+impl From<InjectedAction> for KeyEvent {
+    fn from(value: InjectedAction) -> Self {
+        Self {
+            inner: CKeyEvent::new(KeyCode::Null, KeyModifiers::NONE),
+            injected: Some(value),
+            already_handled: false,
+            pending_sequence: false,
+            sequence_timed_out: false,
+            count_digit: None,
+        }
     }
 }
 
 impl KeyEvent {
+    /// Sent when no more keys were pressed in time after a pending sequence.
+    /// The pending keys are resolved as they are even though longer keybinds
+    /// starting with them exist.
+    pub fn sequence_timed_out() -> Self {
+        Self {
+            sequence_timed_out: true,
+            ..CKeyEvent::new(KeyCode::Null, KeyModifiers::NONE).into()
+        }
+    }
+
     pub fn code(&self) -> KeyCode {
         self.inner.code
     }
 
+    pub fn key(&self) -> Key {
+        self.inner.into()
+    }
+
+    pub fn is_sequence_timeout(&self) -> bool {
+        self.sequence_timed_out
+    }
+
+    pub fn is_pending_sequence(&self) -> bool {
+        self.pending_sequence
+    }
+
+    pub fn count_digit(&self) -> Option<u32> {
+        self.count_digit
+    }
+
+    /// Digits start a count only when they are not bound to anything. Once a
+    /// count is started all digits, including zero, extend it.
+    fn as_count_digit(&self, context: &AppContext, sequence: &[Key]) -> Option<u32> {
+        let [key] = sequence else {
+            return None;
+        };
+        if key.modifiers != KeyModifiers::NONE {
+            return None;
+        }
+        let KeyCode::Char(c) = key.key else {
+            return None;
+        };
+        let digit = c.to_digit(10)?;
+
+        let starts_count = digit != 0 && !context.config.keybinds.is_bound(sequence);
+        (context.pending_count.is_some() || starts_count).then_some(digit)
+    }
+
+    fn lookup<'ctx, V>(
+        &mut self,
+        context: &AppContext,
+        binds: &'ctx HashMap<KeySequence, V>,
+    ) -> Option<&'ctx V> {
+        // Timed out event carries no key of its own, only the pending keys are
+        // resolved
+        let mut sequence = context.pending_keys.clone();
+        if !self.sequence_timed_out {
+            sequence.push(self.key());
+            if context.config.keybinds.has_continuation(&sequence) {
+                self.already_handled = true;
+                self.pending_sequence = true;
+                return None;
+            }
+            if let Some(digit) = self.as_count_digit(context, &sequence) {
+                self.already_handled = true;
+                self.count_digit = Some(digit);
+                return None;
+            }
+        }
+
+        let action = binds.get(&KeySequence(sequence))?;
+        self.already_handled = true;
+        Some(action)
+    }
+
     pub fn stop_propagation(&mut self) {
         self.already_handled = true;
     }
@@ -35,48 +147,193 @@ impl KeyEvent {
     pub fn as_common_action(&mut self, context: &AppContext) -> Option<CommonAction> {
         if self.already_handled {
             None
-        } else if let Some(action) = context.config.keybinds.navigation.get(&self.inner.into()) {
+        } else if let Some(injected) = &self.injected {
+            let InjectedAction::Common(action) = injected else {
+                return None;
+            };
             self.already_handled = true;
             Some(*action)
         } else {
+            self.lookup(context, &context.config.keybinds.navigation).copied()
+        }
+    }
+
+    /// Same as [`Self::as_common_action`] but for keys typed into a focused
+    /// text input. Only keybinds of a single key are resolved so the typed
+    /// characters never start a key sequence or a count.
+    pub fn as_input_common_action(&mut self, context: &AppContext) -> Option<CommonAction> {
+        if self.already_handled || self.sequence_timed_out {
             None
+        } else if self.injected.is_some() {
+            self.as_common_action(context)
+        } else {
+            let action = context.config.keybinds.navigation.get(&KeySequence(vec![self.key()]))?;
+            self.already_handled = true;
+            Some(*action)
         }
     }
 
-    pub fn as_global_action<'ctx>(
-        &mut self,
-        context: &'ctx AppContext,
-    ) -> Option<&'ctx GlobalAction> {
+    pub fn as_global_action(&mut self, context: &AppContext) -> Option<GlobalAction> {
         if self.already_handled {
             None
-        } else if let Some(action) = context.config.keybinds.global.get(&self.inner.into()) {
+        } else if let Some(injected) = &self.injected {
+            let InjectedAction::Global(action) = injected else {
+                return None;
+            };
             self.already_handled = true;
-            Some(action)
+            Some(action.clone())
         } else {
-            None
+            self.lookup(context, &context.config.keybinds.global).cloned()
         }
     }
 
     #[cfg(debug_assertions)]
     pub fn as_logs_action(&mut self, context: &AppContext) -> Option<LogsActions> {
+        if self.already_handled || self.injected.is_some() {
+            None
+        } else {
+            self.lookup(context, &context.config.keybinds.logs).copied()
+        }
+    }
+
+    pub fn as_queue_action(&mut self, context: &AppContext) -> Option<QueueActions> {
         if self.already_handled {
             None
-        } else if let Some(action) = context.config.keybinds.logs.get(&self.inner.into()) {
+        } else if let Some(injected) = &self.injected {
+            let InjectedAction::Queue(action) = injected else {
+                return None;
+            };
             self.already_handled = true;
             Some(*action)
         } else {
-            None
+            self.lookup(context, &context.config.keybinds.queue).copied()
         }
     }
 
-    pub fn as_queue_action(&mut self, context: &AppContext) -> Option<QueueActions> {
+    pub fn as_playlists_action(&mut self, context: &AppContext) -> Option<PlaylistsActions> {
         if self.already_handled {
             None
-        } else if let Some(action) = context.config.keybinds.queue.get(&self.inner.into()) {
+        } else if let Some(injected) = &self.injected {
+            let InjectedAction::Playlists(action) = injected else {
+                return None;
+            };
             self.already_handled = true;
             Some(*action)
         } else {
-            None
+            self.lookup(context, &context.config.keybinds.playlists).copied()
         }
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent as CKeyEvent, KeyModifiers};
+    use rstest::rstest;
+
+    use super::KeyEvent;
+    use crate::{
+        config::keys::{CommonAction, GlobalAction, QueueActions},
+        context::AppContext,
+        tests::fixtures::app_context,
+    };
+
+    fn key(c: char) -> KeyEvent {
+        CKeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE).into()
+    }
+
+    #[rstest]
+    fn unbound_digit_starts_count(app_context: AppContext) {
+        let mut event = key('8');
+
+        assert_eq!(event.as_common_action(&app_context), None);
+        assert_eq!(event.count_digit(), Some(8));
+    }
+
+    #[rstest]
+    fn bound_digit_switches_tab_right_away(app_context: AppContext) {
+        let mut event = key('1');
+
+        assert_eq!(
+            event.as_global_action(&app_context),
+            Some(GlobalAction::SwitchToTab("Queue".into()))
+        );
+        assert!(!event.is_pending_sequence());
+        assert_eq!(event.count_digit(), None);
+    }
+
+    #[rstest]
+    #[case('1', Some(1))]
+    #[case('0', Some(0))]
+    fn any_digit_extends_count(
+        mut app_context: AppContext,
+        #[case] c: char,
+        #[case] expected: Option<u32>,
+    ) {
+        app_context.pending_count = Some(8);
+        let mut event = key(c);
+
+        assert!(event.as_global_action(&app_context).is_none());
+        assert_eq!(event.count_digit(), expected);
+    }
+
+    #[rstest]
+    fn count_is_kept_for_the_action(mut app_context: AppContext) {
+        app_context.pending_count = Some(5);
+        let mut event = key('j');
+
+        assert_eq!(event.as_common_action(&app_context), Some(CommonAction::Down));
+        assert_eq!(event.count_digit(), None);
+        assert_eq!(app_context.count(), 5);
+    }
+
+    #[rstest]
+    fn sequence_prefix_is_pending(mut app_context: AppContext) {
+        let mut config = (*app_context.config).clone();
+        config.keybinds.queue.insert("dd".parse().unwrap(), QueueActions::Delete);
+        app_context.config = std::sync::Arc::new(config);
+
+        let mut event = key('d');
+        assert_eq!(event.as_queue_action(&app_context), None);
+        assert!(event.is_pending_sequence());
+
+        app_context.pending_keys.push(event.key());
+        let mut event = key('d');
+        assert_eq!(event.as_queue_action(&app_context), Some(QueueActions::Delete));
+        assert!(!event.is_pending_sequence());
+    }
+
+    #[rstest]
+    #[case('g')]
+    #[case('5')]
+    fn input_keys_do_not_start_sequence_or_count(mut app_context: AppContext, #[case] c: char) {
+        let mut config = (*app_context.config).clone();
+        config.keybinds.navigation.insert("gg".parse().unwrap(), CommonAction::Top);
+        app_context.config = std::sync::Arc::new(config);
+
+        let mut event = key(c);
+        event.as_input_common_action(&app_context);
+
+        assert!(!event.is_pending_sequence());
+        assert_eq!(event.count_digit(), None);
+    }
+
+    #[rstest]
+    fn input_keys_resolve_single_key_binds(app_context: AppContext) {
+        let mut event: KeyEvent = CKeyEvent::new(KeyCode::Esc, KeyModifiers::NONE).into();
+
+        assert_eq!(event.as_input_common_action(&app_context), Some(CommonAction::Close));
+    }
+
+    #[rstest]
+    fn timed_out_sequence_resolves_pending_keys(mut app_context: AppContext) {
+        let mut config = (*app_context.config).clone();
+        config.keybinds.queue.insert("dd".parse().unwrap(), QueueActions::DeleteAll);
+        app_context.config = std::sync::Arc::new(config);
+        app_context.pending_keys.push(key('d').key());
+
+        let mut event = KeyEvent::sequence_timed_out();
+
+        assert_eq!(event.as_queue_action(&app_context), Some(QueueActions::Delete));
+    }
+}
@@ -3,6 +3,7 @@ pub mod env;
 pub mod events;
 pub mod ext;
 pub mod geometry;
+pub mod history;
 pub mod id;
 pub mod image;
 pub mod ipc;
@@ -13,6 +14,10 @@ pub mod macros;
 pub mod mouse_event;
 pub mod mpd_query;
 pub mod percent;
+pub mod playlist_file;
+pub mod queue_changes;
+pub mod queue_history;
+pub mod queue_order;
 pub mod string_util;
 pub mod tmux;
 pub mod ytdlp;
@@ -10,10 +10,23 @@ use crate::{
     config::tabs::PaneType,
     mpd::{
         client::Client,
-        commands::{Decoder, Output, Song, Status, Volume},
+        commands::{
+            Decoder,
+            Message,
+            Output,
+            Partition,
+            Song,
+            Stats,
+            Status,
+            TagCount,
+            Volume,
+            list_mounts::Mount,
+            list_neighbors::Neighbor,
+            stickers::Stickers,
+        },
         mpd_client::MpdClient,
     },
-    shared::{events::ClientRequest, macros::try_skip},
+    shared::{events::ClientRequest, macros::try_skip, queue_changes::QueueChanges},
     ui::panes::browser::DirOrSong,
 };
 
@@ -21,6 +34,7 @@ pub const EXTERNAL_COMMAND: &str = "external_command";
 pub const GLOBAL_STATUS_UPDATE: &str = "global_status_update";
 pub const GLOBAL_VOLUME_UPDATE: &str = "global_volume_update";
 pub const GLOBAL_QUEUE_UPDATE: &str = "global_queue_update";
+pub const GLOBAL_MESSAGES: &str = "global_messages";
 
 #[derive(derive_more::Debug, Builder)]
 pub(crate) struct MpdQuery {
@@ -86,13 +100,21 @@ pub(crate) enum MpdQueryResult {
     SongsList { data: Vec<Song>, origin_path: Option<Vec<String>> },
     LsInfo { data: Vec<String>, origin_path: Option<Vec<String>> },
     DirOrSong { data: Vec<DirOrSong>, origin_path: Option<Vec<String>> },
+    DirOrSongLevels { data: Vec<Vec<DirOrSong>>, path: Vec<String> },
     AddToPlaylist { playlists: Vec<String>, song_file: String },
     AlbumArt(Option<Vec<u8>>),
     Status(Status),
-    Queue(Option<Vec<Song>>),
+    Queue { version: Option<u32>, queue: Option<Vec<Song>> },
+    QueueChanges(QueueChanges),
+    QueueStickers(Vec<(u32, Stickers)>),
     Volume(Volume),
     Outputs(Vec<Output>),
+    Partitions(Vec<Partition>),
+    Mounts { mounts: Vec<Mount>, neighbors: Vec<Neighbor> },
     Decoders(Vec<Decoder>),
+    Stats { stats: Stats, counts: Vec<TagCount> },
+    Messages(Vec<Message>),
+    Channels(Vec<String>),
     ExternalCommand(Arc<Vec<String>>, Vec<Song>),
     Any(Box<dyn Any + Send + Sync>),
 }
@@ -0,0 +1,542 @@
+use std::{collections::BTreeMap, fmt::Write, path::Path, time::Duration};
+
+use itertools::Itertools;
+use url::Url;
+
+use crate::mpd::commands::Song;
+
+/// File formats stored playlists can be exported to and imported from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaylistFormat {
+    M3u,
+    M3u8,
+    Pls,
+    Xspf,
+}
+
+impl PlaylistFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            PlaylistFormat::M3u => "m3u",
+            PlaylistFormat::M3u8 => "m3u8",
+            PlaylistFormat::Pls => "pls",
+            PlaylistFormat::Xspf => "xspf",
+        }
+    }
+
+    /// Guesses the format from the file's extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "m3u" => Some(PlaylistFormat::M3u),
+            "m3u8" => Some(PlaylistFormat::M3u8),
+            "pls" => Some(PlaylistFormat::Pls),
+            "xspf" => Some(PlaylistFormat::Xspf),
+            _ => None,
+        }
+    }
+}
+
+/// Song as listed in a playlist file. Only the location is required, the
+/// rest is used to find the song when the location cannot be resolved.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PlaylistFileEntry {
+    /// Path or URL of the song, `file://` URLs are converted to paths
+    pub location: String,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub duration: Option<Duration>,
+}
+
+impl PlaylistFileEntry {
+    /// Title of the song, guessed from the file name if the playlist does not
+    /// contain it. Leading track numbers like `01 - ` are removed.
+    pub fn title_or_file_stem(&self) -> Option<String> {
+        if let Some(title) = &self.title {
+            return Some(title.clone());
+        }
+        let stem = Path::new(&self.location).file_stem()?.to_str()?;
+        let stem = stem.trim_start_matches(|c: char| {
+            c.is_ascii_digit() || c.is_whitespace() || matches!(c, '-' | '.' | '_')
+        });
+        (!stem.is_empty()).then(|| stem.to_owned())
+    }
+}
+
+/// Renders the songs in the given format. Paths are written relative to the
+/// music directory unless `music_directory` is given.
+pub fn render(
+    format: PlaylistFormat,
+    name: &str,
+    songs: &[Song],
+    music_directory: Option<&str>,
+) -> String {
+    let mut result = String::new();
+    match format {
+        PlaylistFormat::M3u | PlaylistFormat::M3u8 => {
+            result.push_str("#EXTM3U\n");
+            let _ = writeln!(result, "#PLAYLIST:{name}");
+            for song in songs {
+                if let Some(title) = display_title(song) {
+                    let _ = writeln!(result, "#EXTINF:{},{title}", seconds(song.duration));
+                }
+                let _ = writeln!(result, "{}", location(song, music_directory));
+            }
+        }
+        PlaylistFormat::Pls => {
+            result.push_str("[playlist]\n");
+            for (idx, song) in (1..).zip(songs) {
+                let _ = writeln!(result, "File{idx}={}", location(song, music_directory));
+                if let Some(title) = display_title(song) {
+                    let _ = writeln!(result, "Title{idx}={title}");
+                }
+                let _ = writeln!(result, "Length{idx}={}", seconds(song.duration));
+            }
+            let _ = writeln!(result, "NumberOfEntries={}", songs.len());
+            result.push_str("Version=2\n");
+        }
+        PlaylistFormat::Xspf => {
+            result.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+            result.push_str("<playlist version=\"1\" xmlns=\"http://xspf.org/ns/0/\">\n");
+            let _ = writeln!(result, "  <title>{}</title>", xml_escape(name));
+            result.push_str("  <trackList>\n");
+            for song in songs {
+                result.push_str("    <track>\n");
+                let location = location_uri(&location(song, music_directory));
+                let _ = writeln!(result, "      <location>{}</location>", xml_escape(&location));
+                for (element, value) in
+                    [("title", song.title()), ("creator", song.artist()), ("album", song.album())]
+                {
+                    if let Some(value) = value {
+                        let _ =
+                            writeln!(result, "      <{element}>{}</{element}>", xml_escape(value));
+                    }
+                }
+                if let Some(duration) = song.duration {
+                    let _ = writeln!(result, "      <duration>{}</duration>", duration.as_millis());
+                }
+                result.push_str("    </track>\n");
+            }
+            result.push_str("  </trackList>\n");
+            result.push_str("</playlist>\n");
+        }
+    }
+    result
+}
+
+/// Result of importing a playlist file into a stored playlist
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PlaylistImport {
+    pub name: String,
+    pub added: usize,
+    /// Locations of the entries for which no song was found
+    pub missing: Vec<String>,
+}
+
+/// Reads and parses the playlist file, the format is guessed from its
+/// extension. Files which are not valid UTF-8 are read as Latin-1 which older
+/// M3U and PLS files commonly use.
+pub fn read(path: &Path) -> std::io::Result<Vec<PlaylistFileEntry>> {
+    let Some(format) = PlaylistFormat::from_path(path) else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Unsupported playlist format of '{}'", path.display()),
+        ));
+    };
+    let content = match String::from_utf8(std::fs::read(path)?) {
+        Ok(content) => content,
+        Err(err) => err.into_bytes().into_iter().map(char::from).collect(),
+    };
+    Ok(parse(format, &content))
+}
+
+/// Parses entries of a playlist file. Lines and elements which are not
+/// understood are skipped.
+pub fn parse(format: PlaylistFormat, content: &str) -> Vec<PlaylistFileEntry> {
+    let content = content.trim_start_matches('\u{feff}');
+    match format {
+        PlaylistFormat::M3u | PlaylistFormat::M3u8 => {
+            let mut result = Vec::new();
+            let mut info: Option<PlaylistFileEntry> = None;
+            for line in content.lines().map(str::trim).filter(|line| !line.is_empty()) {
+                if let Some(extinf) = line.strip_prefix("#EXTINF:") {
+                    let (duration, title) = extinf.split_once(',').unwrap_or((extinf, ""));
+                    let (artist, title) = split_artist_title(title);
+                    info = Some(PlaylistFileEntry {
+                        title,
+                        artist,
+                        duration: parse_seconds(duration),
+                        ..Default::default()
+                    });
+                } else if !line.starts_with('#') {
+                    result.push(PlaylistFileEntry {
+                        location: location_path(line, false),
+                        ..info.take().unwrap_or_default()
+                    });
+                }
+            }
+            result
+        }
+        PlaylistFormat::Pls => {
+            let mut entries: BTreeMap<usize, PlaylistFileEntry> = BTreeMap::new();
+            for line in content.lines().map(str::trim) {
+                let Some((key, value)) = line.split_once('=') else {
+                    continue;
+                };
+                let Some((kind, idx)) = ["File", "Title", "Length"]
+                    .iter()
+                    .find_map(|kind| Some((*kind, key.strip_prefix(kind)?.parse::<usize>().ok()?)))
+                else {
+                    continue;
+                };
+                let entry = entries.entry(idx).or_default();
+                match kind {
+                    "File" => entry.location = location_path(value, false),
+                    "Title" => (entry.artist, entry.title) = split_artist_title(value),
+                    _ => entry.duration = parse_seconds(value),
+                }
+            }
+            entries.into_values().filter(|entry| !entry.location.is_empty()).collect()
+        }
+        PlaylistFormat::Xspf => content
+            .split("<track>")
+            .skip(1)
+            .filter_map(|track| {
+                let track = track.split("</track>").next()?;
+                Some(PlaylistFileEntry {
+                    location: location_path(&xml_element(track, "location")?, true),
+                    title: xml_element(track, "title"),
+                    artist: xml_element(track, "creator"),
+                    album: xml_element(track, "album"),
+                    duration: xml_element(track, "duration")
+                        .and_then(|ms| ms.parse().ok())
+                        .map(Duration::from_millis),
+                })
+            })
+            .collect(),
+    }
+}
+
+/// Picks the song which matches the entry best out of the songs with the
+/// same title. Artist, album, duration and file name break ties.
+pub fn best_match<'songs>(
+    entry: &PlaylistFileEntry,
+    candidates: &'songs [Song],
+) -> Option<&'songs Song> {
+    let title = normalize(&entry.title_or_file_stem()?);
+    let file_stem =
+        Path::new(&entry.location).file_stem().and_then(|stem| stem.to_str()).map(normalize);
+    let same = |a: Option<&String>, b: Option<&String>| {
+        a.zip(b).is_some_and(|(a, b)| normalize(a) == normalize(b))
+    };
+
+    candidates
+        .iter()
+        .filter(|song| song.title().is_some_and(|t| normalize(t) == title))
+        .map(|song| {
+            let mut score = 0;
+            if same(song.artist(), entry.artist.as_ref()) {
+                score += 2;
+            }
+            if same(song.album(), entry.album.as_ref()) {
+                score += 1;
+            }
+            if song.duration.zip(entry.duration).is_some_and(|(a, b)| a.abs_diff(b).as_secs() <= 2)
+            {
+                score += 1;
+            }
+            if file_stem.is_some()
+                && Path::new(&song.file).file_stem().and_then(|stem| stem.to_str()).map(normalize)
+                    == file_stem
+            {
+                score += 1;
+            }
+            (score, song)
+        })
+        // max_by_key returns the last maximum, keep the first one instead
+        .rev()
+        .max_by_key(|(score, _)| *score)
+        .map(|(_, song)| song)
+}
+
+/// Resolves `.` and `..` in a relative path without touching the filesystem
+pub fn normalize_path(path: &str) -> String {
+    let mut result: Vec<&str> = Vec::new();
+    for part in path.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                result.pop();
+            }
+            part => result.push(part),
+        }
+    }
+    result.join("/")
+}
+
+fn display_title(song: &Song) -> Option<String> {
+    let title = song.title()?;
+    Some(match song.artist() {
+        Some(artist) => format!("{artist} - {title}"),
+        None => title.clone(),
+    })
+}
+
+fn split_artist_title(value: &str) -> (Option<String>, Option<String>) {
+    let value = value.trim();
+    match value.split_once(" - ") {
+        Some((artist, title)) => (Some(artist.trim().to_owned()), Some(title.trim().to_owned())),
+        None if value.is_empty() => (None, None),
+        None => (None, Some(value.to_owned())),
+    }
+}
+
+fn seconds(duration: Option<Duration>) -> i64 {
+    duration.map_or(-1, |duration| i64::try_from(duration.as_secs()).unwrap_or(i64::MAX))
+}
+
+fn parse_seconds(value: &str) -> Option<Duration> {
+    value.trim().parse::<f64>().ok().filter(|secs| *secs >= 0.0).map(Duration::from_secs_f64)
+}
+
+fn location(song: &Song, music_directory: Option<&str>) -> String {
+    match music_directory {
+        Some(dir) if !song.file.contains("://") => {
+            Path::new(dir).join(&song.file).to_string_lossy().into_owned()
+        }
+        _ => song.file.clone(),
+    }
+}
+
+/// XSPF locations are URIs, paths have to be percent encoded and absolute
+/// paths turned into `file://` URLs
+fn location_uri(location: &str) -> String {
+    if location.contains("://") {
+        return location.to_owned();
+    }
+    let absolute = location.starts_with('/');
+    match Url::from_file_path(Path::new("/").join(location)) {
+        Ok(url) if absolute => url.to_string(),
+        Ok(url) => url.path().trim_start_matches('/').to_owned(),
+        Err(()) => location.to_owned(),
+    }
+}
+
+/// Turns `file://` URLs and, for formats which use URIs, percent encoded
+/// paths back into plain paths. Other URLs like streams are kept as is.
+fn location_path(location: &str, percent_encoded: bool) -> String {
+    let location = location.trim();
+    if location.starts_with("file://") {
+        return Url::parse(location)
+            .ok()
+            .and_then(|url| url.to_file_path().ok())
+            .map_or_else(|| location.to_owned(), |path| path.to_string_lossy().into_owned());
+    }
+    if location.contains("://") || !percent_encoded {
+        return location.to_owned();
+    }
+    let absolute = location.starts_with('/');
+    Url::parse("file:///")
+        .and_then(|base| base.join(location))
+        .ok()
+        .and_then(|url| url.to_file_path().ok())
+        .map_or_else(
+            || location.to_owned(),
+            |path| {
+                let path = path.to_string_lossy();
+                if absolute { path.into_owned() } else { path.trim_start_matches('/').to_owned() }
+            },
+        )
+}
+
+fn normalize(value: &str) -> String {
+    value
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .join(" ")
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn xml_unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..end];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map_or_else(
+                    || entity.strip_prefix('#')?.parse().ok(),
+                    |hex| u32::from_str_radix(hex, 16).ok(),
+                )
+                .and_then(char::from_u32),
+        };
+        if let Some(decoded) = decoded {
+            result.push(decoded);
+            rest = &rest[end + 1..];
+        } else {
+            result.push('&');
+            rest = &rest[1..];
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Text of the first element with the given name, `None` if missing or empty
+fn xml_element(xml: &str, name: &str) -> Option<String> {
+    let start = xml.find(&format!("<{name}>"))? + name.len() + 2;
+    let end = start + xml[start..].find(&format!("</{name}>"))?;
+    let value = xml[start..end].trim();
+    let value = value
+        .strip_prefix("<![CDATA[")
+        .and_then(|value| value.strip_suffix("]]>"))
+        .map_or_else(|| xml_unescape(value), ToOwned::to_owned);
+    (!value.is_empty()).then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, time::Duration};
+
+    use test_case::test_case;
+
+    use super::{PlaylistFileEntry, PlaylistFormat, best_match, normalize_path, parse, render};
+    use crate::mpd::commands::Song;
+
+    fn song(file: &str, title: &str, artist: &str, secs: u64) -> Song {
+        Song {
+            file: file.to_owned(),
+            duration: Some(Duration::from_secs(secs)),
+            metadata: HashMap::from([
+                ("title".to_owned(), title.to_owned().into()),
+                ("artist".to_owned(), artist.to_owned().into()),
+            ]),
+            ..Default::default()
+        }
+    }
+
+    fn entry(location: &str, title: &str, artist: &str, secs: u64) -> PlaylistFileEntry {
+        PlaylistFileEntry {
+            location: location.to_owned(),
+            title: Some(title.to_owned()),
+            artist: Some(artist.to_owned()),
+            album: None,
+            duration: Some(Duration::from_secs(secs)),
+        }
+    }
+
+    #[test_case(PlaylistFormat::M3u)]
+    #[test_case(PlaylistFormat::M3u8)]
+    #[test_case(PlaylistFormat::Pls)]
+    #[test_case(PlaylistFormat::Xspf)]
+    fn render_and_parse_roundtrip(format: PlaylistFormat) {
+        let songs = [
+            song("dir/a & b.flac", "A & B", "Artist", 120),
+            song("dir/ünïcode #1.mp3", "Ünïcode", "Other", 61),
+            song("http://radio.example/stream", "Radio", "Station", 0),
+        ];
+
+        let result = parse(format, &render(format, "name", &songs, None));
+
+        assert_eq!(result, [
+            entry("dir/a & b.flac", "A & B", "Artist", 120),
+            entry("dir/ünïcode #1.mp3", "Ünïcode", "Other", 61),
+            entry("http://radio.example/stream", "Radio", "Station", 0),
+        ]);
+    }
+
+    #[test]
+    fn xspf_uses_file_urls_for_absolute_paths() {
+        let songs = [song("dir/a b.flac", "A", "B", 1)];
+
+        let rendered = render(PlaylistFormat::Xspf, "name", &songs, Some("/music"));
+        let result = parse(PlaylistFormat::Xspf, &rendered);
+
+        assert!(rendered.contains("<location>file:///music/dir/a%20b.flac</location>"));
+        assert_eq!(result[0].location, "/music/dir/a b.flac");
+    }
+
+    #[test]
+    fn parses_m3u_without_extended_info() {
+        let content = "\u{feff}# comment\r\nfirst.mp3\r\n\r\nfile:///music/second%20song.mp3\r\n";
+
+        let result = parse(PlaylistFormat::M3u, content);
+
+        assert_eq!(result, [
+            PlaylistFileEntry { location: "first.mp3".to_owned(), ..Default::default() },
+            PlaylistFileEntry {
+                location: "/music/second song.mp3".to_owned(),
+                ..Default::default()
+            },
+        ]);
+    }
+
+    #[test]
+    fn parses_pls_out_of_order() {
+        let content =
+            "[playlist]\nTitle2=Second\nFile2=b.mp3\nFile1=a.mp3\nLength1=-1\nNumberOfEntries=2\n";
+
+        let result = parse(PlaylistFormat::Pls, content);
+
+        assert_eq!(result, [
+            PlaylistFileEntry { location: "a.mp3".to_owned(), ..Default::default() },
+            PlaylistFileEntry {
+                location: "b.mp3".to_owned(),
+                title: Some("Second".to_owned()),
+                ..Default::default()
+            },
+        ]);
+    }
+
+    #[test]
+    fn best_match_prefers_artist_and_duration() {
+        let candidates = [
+            song("live/song.mp3", "Song", "Artist", 300),
+            song("other/song.mp3", "Song", "Someone else", 200),
+            song("album/song.mp3", "Song", "Artist", 200),
+            song("album/different.mp3", "Different", "Artist", 200),
+        ];
+
+        let result = best_match(&entry("/old/song.mp3", "song", "ARTIST", 201), &candidates);
+
+        assert_eq!(result.map(|song| song.file.as_str()), Some("album/song.mp3"));
+    }
+
+    #[test]
+    fn best_match_uses_file_name_without_title() {
+        let candidates = [song("a/other.mp3", "Other", "A", 1), song("a/song.mp3", "Song", "A", 1)];
+        let entry =
+            PlaylistFileEntry { location: "x/01 - Song.mp3".to_owned(), ..Default::default() };
+
+        let result = best_match(&entry, &candidates);
+
+        assert_eq!(result.map(|song| song.file.as_str()), Some("a/song.mp3"));
+    }
+
+    #[test_case("a/./b/../c.mp3", "a/c.mp3"; "dots")]
+    #[test_case("../a\\b.mp3", "a/b.mp3"; "backslashes")]
+    #[test_case("/a//b.mp3", "a/b.mp3"; "empty parts")]
+    fn normalizes_path(input: &str, expected: &str) {
+        assert_eq!(normalize_path(input), expected);
+    }
+}
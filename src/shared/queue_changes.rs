@@ -0,0 +1,186 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+
+use crate::mpd::commands::{Song, plchanges::PosId, stickers::Stickers};
+
+/// Changes of the queue since a known playlist version as reported by
+/// `plchangesposid`. Songs which were just moved are reused, all the others
+/// are fetched in full.
+#[derive(Debug, Default)]
+pub struct QueueChanges {
+    /// Playlist version the changes are relative to
+    pub from_version: u32,
+    /// Playlist version after the changes
+    pub version: u32,
+    /// Length of the queue after the changes
+    pub length: usize,
+    pub changes: Vec<PosId>,
+    /// Songs which were not part of the queue at `from_version` or which
+    /// changed in place
+    pub songs: Vec<Song>,
+}
+
+/// Applies the changes to the queue. Returns false and leaves the queue
+/// untouched if the changes do not fit it, ie. because a song is neither in
+/// the queue nor among the fetched songs. The whole queue has to be fetched
+/// again in that case.
+pub fn apply(queue: &mut Vec<Song>, changes: QueueChanges) -> bool {
+    let length = changes.length;
+    let by_position: HashMap<usize, u32> = changes
+        .changes
+        .iter()
+        .filter(|change| change.pos < length)
+        .map(|change| (change.pos, change.id))
+        .collect();
+    let changed_ids: HashSet<u32> = by_position.values().copied().collect();
+    let old_positions: HashMap<u32, usize> =
+        queue.iter().enumerate().map(|(pos, song)| (song.id, pos)).collect();
+    let mut songs: HashMap<u32, Song> =
+        changes.songs.into_iter().map(|song| (song.id, song)).collect();
+
+    // Unchanged positions must still hold a song which is not placed anywhere
+    // else and every changed song must come from somewhere
+    let unchanged_ok = (0..length)
+        .filter(|pos| !by_position.contains_key(pos))
+        .all(|pos| queue.get(pos).is_some_and(|song| !changed_ids.contains(&song.id)));
+    let changed_ok = changed_ids.len() == by_position.len()
+        && changed_ids
+            .iter()
+            .all(|id| songs.contains_key(id) || old_positions.contains_key(id));
+    if !unchanged_ok || !changed_ok {
+        log::debug!(length, changes = by_position.len(); "Queue changes do not fit the current queue");
+        return false;
+    }
+
+    let mut old = std::mem::take(queue).into_iter().map(Some).collect_vec();
+    *queue = (0..length)
+        .filter_map(|pos| match by_position.get(&pos) {
+            Some(id) => {
+                let mut song = songs
+                    .remove(id)
+                    .or_else(|| old_positions.get(id).and_then(|old_pos| old[*old_pos].take()))?;
+                if let Some(tag) = song.metadata.get_mut("pos") {
+                    *tag = pos.to_string().into();
+                }
+                Some(song)
+            }
+            None => old[pos].take(),
+        })
+        .collect();
+
+    true
+}
+
+/// Replaces stickers of the queue songs with the given ones, matched by song
+/// id. Returns whether any of them changed.
+pub fn apply_stickers(queue: &mut [Song], stickers: Vec<(u32, Stickers)>) -> bool {
+    let mut stickers: HashMap<u32, Stickers> = stickers.into_iter().collect();
+    let mut changed = false;
+    for song in queue {
+        let Some(new) = stickers.remove(&song.id) else {
+            continue;
+        };
+        if song.stickers.as_ref() != Some(&new.0) {
+            song.stickers = Some(new.0);
+            changed = true;
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use itertools::Itertools;
+    use test_case::test_case;
+
+    use super::{QueueChanges, apply, apply_stickers};
+    use crate::mpd::commands::{Song, plchanges::PosId, stickers::Stickers};
+
+    fn queue(ids: &[u32]) -> Vec<Song> {
+        ids.iter()
+            .map(|id| Song { id: *id, file: format!("{id}.mp3"), ..Default::default() })
+            .collect()
+    }
+
+    fn changes(length: usize, changes: &[(usize, u32)], songs: &[u32]) -> QueueChanges {
+        QueueChanges {
+            from_version: 1,
+            version: 2,
+            length,
+            changes: changes.iter().map(|(pos, id)| PosId { pos: *pos, id: *id }).collect(),
+            songs: queue(songs),
+        }
+    }
+
+    #[test_case(&[1, 2, 3], changes(4, &[(3, 4)], &[4]), &[1, 2, 3, 4]; "appended")]
+    #[test_case(&[1, 2, 3], changes(2, &[(1, 3)], &[]), &[1, 3]; "deleted from the middle")]
+    #[test_case(&[1, 2, 3], changes(2, &[], &[]), &[1, 2]; "deleted from the end")]
+    #[test_case(&[1, 2, 3], changes(3, &[(0, 3), (1, 1), (2, 2)], &[]), &[3, 1, 2]; "moved")]
+    #[test_case(&[1, 2, 3], changes(4, &[(1, 5), (2, 2), (3, 3)], &[5]), &[1, 5, 2, 3]; "inserted")]
+    #[test_case(&[1, 2], changes(0, &[], &[]), &[]; "cleared")]
+    #[test_case(&[1, 2, 3], changes(3, &[(1, 2), (5, 9)], &[]), &[1, 2, 3]; "positions past the end are ignored")]
+    fn applies_changes(current: &[u32], changes: QueueChanges, expected: &[u32]) {
+        let mut queue = queue(current);
+
+        assert!(apply(&mut queue, changes));
+
+        assert_eq!(queue.iter().map(|song| song.id).collect_vec(), expected);
+    }
+
+    #[test_case(&[1, 2, 3], changes(4, &[(3, 4)], &[]); "unknown song")]
+    #[test_case(&[1, 2, 3], changes(4, &[], &[]); "unchanged position past the end")]
+    #[test_case(&[1, 2, 3], changes(3, &[(0, 3)], &[]); "song in two places")]
+    fn rejects_changes_which_do_not_fit(current: &[u32], changes: QueueChanges) {
+        let mut queue = queue(current);
+
+        assert!(!apply(&mut queue, changes));
+
+        assert_eq!(queue.iter().map(|song| song.id).collect_vec(), current);
+    }
+
+    #[test]
+    fn updates_position_of_moved_songs() {
+        let mut queue = queue(&[1, 2]);
+        for (pos, song) in queue.iter_mut().enumerate() {
+            song.metadata.insert("pos".to_owned(), pos.to_string().into());
+        }
+
+        assert!(apply(&mut queue, changes(2, &[(0, 2), (1, 1)], &[])));
+
+        assert_eq!(queue[0].tag("pos").map(String::as_str), Some("0"));
+        assert_eq!(queue[1].tag("pos").map(String::as_str), Some("1"));
+    }
+
+    #[test]
+    fn replaces_songs_changed_in_place() {
+        let mut queue = queue(&[1, 2]);
+        let mut changes = changes(2, &[(1, 2)], &[2]);
+        changes.songs[0].priority = 10;
+
+        assert!(apply(&mut queue, changes));
+
+        assert_eq!(queue.iter().map(|song| (song.id, song.priority)).collect_vec(), [
+            (1, 0),
+            (2, 10)
+        ]);
+    }
+
+    #[test]
+    fn applies_only_changed_stickers() {
+        let mut queue = queue(&[1, 2]);
+        queue[0].stickers = Some(HashMap::from([("rating".to_owned(), "5".to_owned())]));
+
+        let unchanged = vec![(1, Stickers(HashMap::from([("rating".to_owned(), "5".to_owned())])))];
+        assert!(!apply_stickers(&mut queue, unchanged));
+
+        let changed = vec![(2, Stickers(HashMap::from([("rating".to_owned(), "3".to_owned())])))];
+        assert!(apply_stickers(&mut queue, changed));
+        assert_eq!(
+            queue[1].stickers.as_ref().and_then(|s| s.get("rating")).map(String::as_str),
+            Some("3")
+        );
+    }
+}
@@ -0,0 +1,355 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use itertools::Itertools;
+
+use crate::mpd::commands::Song;
+
+/// Only this many most recent queue edits can be undone
+const MAX_ENTRIES: usize = 100;
+
+/// Undo and redo stacks of the queue's content. The queue is recorded as a
+/// list of its files before each edit issued from the UI.
+#[derive(Debug, Default)]
+pub struct QueueHistory {
+    undo: Snapshots,
+    redo: Snapshots,
+}
+
+/// Single MPD command turning the queue into its recorded state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueueEdit {
+    Clear,
+    Delete(u32),
+    Add { file: String, position: usize },
+    Move { id: u32, position: usize },
+}
+
+impl QueueHistory {
+    /// Records the queue as it was before an edit. Recording a new edit
+    /// discards everything that could be redone.
+    pub fn record(&mut self, queue: &[Song]) {
+        let snapshot = snapshot(queue);
+        if self.undo.last() != Some(&snapshot) {
+            self.undo.push(snapshot);
+        }
+        if self.undo.len() > MAX_ENTRIES {
+            self.undo.remove_oldest();
+        }
+        self.redo.clear();
+    }
+
+    /// Returns the queue's files as they were before the last recorded edit.
+    /// Recorded states equal to the current queue, ie. edits which did not
+    /// change anything, are skipped.
+    pub fn undo(&mut self, queue: &[Song]) -> Option<Vec<String>> {
+        let current = snapshot(queue);
+        let target = self.undo.pop_different(&current)?;
+        self.redo.push(current);
+        Some(target)
+    }
+
+    /// Returns the queue's files as they were before the last undo
+    pub fn redo(&mut self, queue: &[Song]) -> Option<Vec<String>> {
+        let current = snapshot(queue);
+        let target = self.redo.pop_different(&current)?;
+        self.undo.push(current);
+        Some(target)
+    }
+}
+
+fn snapshot(queue: &[Song]) -> Vec<String> {
+    queue.iter().map(|song| song.file.clone()).collect()
+}
+
+/// Stack of queue states where only the most recent one is kept whole. Every
+/// older state is stored as its difference to the state above it, so a
+/// small edit of a large queue costs only the few changed files.
+#[derive(Debug, Default)]
+struct Snapshots {
+    last: Option<Vec<String>>,
+    /// Oldest first
+    older: VecDeque<Delta>,
+}
+
+/// Files between an unchanged prefix and suffix of a newer state
+#[derive(Debug, PartialEq, Eq)]
+struct Delta {
+    prefix: usize,
+    suffix: usize,
+    middle: Vec<String>,
+}
+
+impl Delta {
+    /// Difference which turns `newer` back into `older`
+    fn between(newer: &[String], mut older: Vec<String>) -> Self {
+        let prefix = newer.iter().zip(&older).take_while(|(a, b)| a == b).count();
+        let suffix = newer[prefix..]
+            .iter()
+            .rev()
+            .zip(older[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        older.truncate(older.len() - suffix);
+        older.drain(..prefix);
+        Self { prefix, suffix, middle: older }
+    }
+
+    fn apply(self, newer: &[String]) -> Vec<String> {
+        let mut result = Vec::with_capacity(self.prefix + self.middle.len() + self.suffix);
+        result.extend_from_slice(&newer[..self.prefix]);
+        result.extend(self.middle);
+        result.extend_from_slice(&newer[newer.len() - self.suffix..]);
+        result
+    }
+}
+
+impl Snapshots {
+    fn len(&self) -> usize {
+        self.older.len() + usize::from(self.last.is_some())
+    }
+
+    fn last(&self) -> Option<&Vec<String>> {
+        self.last.as_ref()
+    }
+
+    fn push(&mut self, snapshot: Vec<String>) {
+        if let Some(previous) = self.last.take() {
+            self.older.push_back(Delta::between(&snapshot, previous));
+        }
+        self.last = Some(snapshot);
+    }
+
+    fn pop(&mut self) -> Option<Vec<String>> {
+        let result = self.last.take()?;
+        self.last = self.older.pop_back().map(|delta| delta.apply(&result));
+        Some(result)
+    }
+
+    fn remove_oldest(&mut self) {
+        if self.older.pop_front().is_none() {
+            self.last = None;
+        }
+    }
+
+    fn clear(&mut self) {
+        self.last = None;
+        self.older.clear();
+    }
+
+    fn pop_different(&mut self, current: &[String]) -> Option<Vec<String>> {
+        while let Some(snapshot) = self.pop() {
+            if snapshot != current {
+                return Some(snapshot);
+            }
+        }
+        None
+    }
+}
+
+/// Returns edits which turn `queue` into `target` when applied one after
+/// another. Songs are matched by their file, songs already in the queue are
+/// moved to their position instead of being deleted and added again.
+pub fn edits(queue: &[Song], target: &[String]) -> Vec<QueueEdit> {
+    if target.is_empty() {
+        return if queue.is_empty() { Vec::new() } else { vec![QueueEdit::Clear] };
+    }
+
+    let mut available: HashMap<&str, VecDeque<u32>> = HashMap::new();
+    for song in queue {
+        available.entry(song.file.as_str()).or_default().push_back(song.id);
+    }
+    let desired = target
+        .iter()
+        .map(|file| available.get_mut(file.as_str()).and_then(VecDeque::pop_front).ok_or(file))
+        .collect_vec();
+    let kept: HashSet<u32> = desired.iter().filter_map(|slot| slot.ok()).collect();
+
+    let mut result = queue
+        .iter()
+        .filter(|song| !kept.contains(&song.id))
+        .map(|song| QueueEdit::Delete(song.id))
+        .collect_vec();
+
+    // Current state of the queue after the edits so far, `None` are the newly
+    // added songs whose ids are not known yet
+    let mut current =
+        queue.iter().map(|song| song.id).filter(|id| kept.contains(id)).map(Some).collect_vec();
+    for (position, slot) in desired.into_iter().enumerate() {
+        match slot {
+            Err(file) => {
+                current.insert(position, None);
+                result.push(QueueEdit::Add { file: file.clone(), position });
+            }
+            Ok(id) if current.get(position) == Some(&Some(id)) => {}
+            Ok(id) => {
+                if let Some(from) = current.iter().position(|v| *v == Some(id)) {
+                    current.remove(from);
+                    current.insert(position, Some(id));
+                    result.push(QueueEdit::Move { id, position });
+                }
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use test_case::test_case;
+
+    use super::{Delta, MAX_ENTRIES, QueueEdit, QueueHistory, edits};
+    use crate::mpd::commands::Song;
+
+    fn queue(files: &[&str]) -> Vec<Song> {
+        files
+            .iter()
+            .zip(1..)
+            .map(|(file, id)| Song { id, file: (*file).to_owned(), ..Default::default() })
+            .collect()
+    }
+
+    fn files(files: &[&str]) -> Vec<String> {
+        files.iter().map(|file| (*file).to_owned()).collect()
+    }
+
+    /// Applies the edits the way MPD would, new songs get ids from 100 up
+    fn apply(queue: &[Song], edits: &[QueueEdit]) -> Vec<String> {
+        let mut result = queue.iter().map(|song| (song.id, song.file.clone())).collect_vec();
+        let mut next_id = 100;
+        for edit in edits {
+            match edit {
+                QueueEdit::Clear => result.clear(),
+                QueueEdit::Delete(id) => result.retain(|(v, _)| v != id),
+                QueueEdit::Add { file, position } => {
+                    result.insert(*position, (next_id, file.clone()));
+                    next_id += 1;
+                }
+                QueueEdit::Move { id, position } => {
+                    let from = result.iter().position(|(v, _)| v == id).unwrap_or_default();
+                    let song = result.remove(from);
+                    result.insert(*position, song);
+                }
+            }
+        }
+        result.into_iter().map(|(_, file)| file).collect()
+    }
+
+    #[test_case(&["a", "b", "c"], &["a", "b", "c"]; "unchanged")]
+    #[test_case(&["a", "c"], &["a", "b", "c", "d"]; "deleted songs are added back")]
+    #[test_case(&["a", "b", "x", "c"], &["a", "b", "c"]; "added songs are deleted")]
+    #[test_case(&["c", "a", "b"], &["a", "b", "c"]; "moved songs are moved back")]
+    #[test_case(&["b", "x", "a", "a"], &["a", "b", "a", "c"]; "duplicates")]
+    #[test_case(&[], &["a", "b"]; "cleared queue is restored")]
+    fn edits_restore_target(current: &[&str], target: &[&str]) {
+        let current = queue(current);
+        let target = files(target);
+
+        let result = edits(&current, &target);
+
+        assert_eq!(apply(&current, &result), target);
+    }
+
+    #[test]
+    fn edits_clear_queue() {
+        assert_eq!(edits(&queue(&["a", "b"]), &[]), [QueueEdit::Clear]);
+    }
+
+    #[test]
+    fn edits_move_only_displaced_songs() {
+        let current = queue(&["a", "b", "c", "d"]);
+        let target = ["d", "a", "b", "c"].map(str::to_owned);
+
+        assert_eq!(edits(&current, &target), [QueueEdit::Move { id: 4, position: 0 }]);
+    }
+
+    #[test]
+    fn undo_and_redo() {
+        let mut history = QueueHistory::default();
+        history.record(&queue(&["a", "b"]));
+
+        assert_eq!(history.undo(&queue(&["a"])), Some(files(&["a", "b"])));
+        assert_eq!(history.undo(&queue(&["a", "b"])), None);
+
+        assert_eq!(history.redo(&queue(&["a", "b"])), Some(files(&["a"])));
+        assert_eq!(history.redo(&queue(&["a"])), None);
+    }
+
+    #[test]
+    fn undo_skips_edits_which_changed_nothing() {
+        let mut history = QueueHistory::default();
+        history.record(&queue(&["a", "b"]));
+        history.record(&queue(&["a"]));
+
+        assert_eq!(history.undo(&queue(&["a"])), Some(files(&["a", "b"])));
+    }
+
+    #[test]
+    fn record_discards_redo() {
+        let mut history = QueueHistory::default();
+        history.record(&queue(&["a", "b"]));
+        history.undo(&queue(&["a"]));
+
+        history.record(&queue(&["a", "b"]));
+
+        assert_eq!(history.redo(&queue(&["a", "b", "c"])), None);
+    }
+
+    #[test_case(&["a", "b", "c"], &["a", "b", "c"]; "same")]
+    #[test_case(&["a", "b", "c"], &["a", "c"]; "added in the middle")]
+    #[test_case(&["a", "c"], &["a", "b", "c"]; "deleted from the middle")]
+    #[test_case(&["c", "b", "a"], &["a", "b", "c"]; "reversed")]
+    #[test_case(&[], &["a", "b"]; "cleared")]
+    #[test_case(&["a", "a"], &["a"]; "duplicates")]
+    fn delta_restores_older_state(newer: &[&str], older: &[&str]) {
+        let newer = files(newer);
+
+        let delta = Delta::between(&newer, files(older));
+
+        assert_eq!(delta.apply(&newer), files(older));
+    }
+
+    #[test]
+    fn delta_keeps_only_changed_files() {
+        let newer = files(&["a", "b", "x", "c", "d"]);
+
+        let delta = Delta::between(&newer, files(&["a", "b", "c", "d"]));
+
+        assert_eq!(delta, Delta { prefix: 2, suffix: 2, middle: Vec::new() });
+    }
+
+    #[test]
+    fn undo_restores_every_recorded_state_in_order() {
+        let states = [&["a"][..], &["a", "b"], &["b"], &["c", "b", "a"], &["c"]];
+        let mut history = QueueHistory::default();
+        for state in states {
+            history.record(&queue(state));
+        }
+
+        let mut current = queue(&["d"]);
+        for state in states.iter().rev() {
+            let result = history.undo(&current);
+            assert_eq!(result, Some(files(state)));
+            current = queue(state);
+        }
+        assert_eq!(history.undo(&current), None);
+    }
+
+    #[test]
+    fn record_keeps_only_most_recent_entries() {
+        let mut history = QueueHistory::default();
+        for i in 0..=MAX_ENTRIES {
+            history.record(&queue(&[&i.to_string()]));
+        }
+
+        let mut current = queue(&["last"]);
+        let mut count = 0;
+        while let Some(files) = history.undo(&current) {
+            current = queue(&files.iter().map(String::as_str).collect_vec());
+            count += 1;
+        }
+
+        assert_eq!(count, MAX_ENTRIES);
+        assert_eq!(current[0].file, "1");
+    }
+}
@@ -0,0 +1,213 @@
+use std::{
+    collections::{HashMap, hash_map::RandomState},
+    hash::BuildHasher,
+    time::SystemTime,
+};
+
+use itertools::Itertools;
+
+use crate::{config::queue_sort::QueueSort, mpd::commands::Song};
+
+/// Way to reorder songs in the queue
+#[derive(Debug, Clone)]
+pub enum QueueOrder {
+    Sort(QueueSort),
+    Shuffle,
+    /// Shuffles whole albums, tracks of each album are kept together in
+    /// their current order
+    ShuffleByAlbum,
+}
+
+impl QueueOrder {
+    /// Whether stickers have to be fetched to reorder the songs
+    pub fn needs_stickers(&self) -> bool {
+        match self {
+            QueueOrder::Sort(sort) => sort.needs_stickers(),
+            QueueOrder::Shuffle | QueueOrder::ShuffleByAlbum => false,
+        }
+    }
+
+    fn apply(&self, songs: &mut Vec<Song>) {
+        match self {
+            QueueOrder::Sort(sort) => sort.apply(songs),
+            QueueOrder::Shuffle => shuffle(songs),
+            QueueOrder::ShuffleByAlbum => {
+                let mut albums: Vec<Vec<Song>> = Vec::new();
+                let mut album_indices: HashMap<(Option<String>, String), usize> = HashMap::new();
+                for song in songs.drain(..) {
+                    // Songs without an album are shuffled on their own
+                    let Some(album) = song.album().cloned() else {
+                        albums.push(vec![song]);
+                        continue;
+                    };
+                    let artist = song.tag("albumartist").or_else(|| song.artist()).cloned();
+                    if let Some(idx) = album_indices.get(&(artist.clone(), album.clone())) {
+                        albums[*idx].push(song);
+                    } else {
+                        album_indices.insert((artist, album), albums.len());
+                        albums.push(vec![song]);
+                    }
+                }
+                shuffle(&mut albums);
+                songs.extend(albums.into_iter().flatten());
+            }
+        }
+    }
+}
+
+/// Returns ids of the whole queue in their new order. Only songs at
+/// `positions` are reordered between themselves, the rest of the queue stays
+/// in place. The whole queue is reordered if `positions` is empty.
+pub fn reorder(queue: Vec<Song>, positions: &[usize], order: &QueueOrder) -> Vec<u32> {
+    let positions = if positions.is_empty() {
+        (0..queue.len()).collect_vec()
+    } else {
+        positions.iter().copied().filter(|pos| *pos < queue.len()).sorted().dedup().collect_vec()
+    };
+
+    let mut result = queue.iter().map(|song| song.id).collect_vec();
+    let mut queue = queue.into_iter().map(Some).collect_vec();
+    let mut songs = positions.iter().filter_map(|pos| queue[*pos].take()).collect_vec();
+    order.apply(&mut songs);
+
+    for (pos, song) in positions.into_iter().zip(songs) {
+        result[pos] = song.id;
+    }
+    result
+}
+
+/// Returns the moves, as song id and its absolute position, which reorder
+/// `current` into `desired` when applied one after another. Songs already in
+/// place are not moved.
+pub fn moves(current: &[u32], desired: &[u32]) -> Vec<(u32, usize)> {
+    let mut current = current.to_vec();
+    let mut result = Vec::new();
+    for (pos, id) in desired.iter().enumerate() {
+        if current.get(pos) == Some(id) {
+            continue;
+        }
+        if let Some(from) = current.iter().position(|v| v == id) {
+            current.remove(from);
+            current.insert(pos, *id);
+            result.push((*id, pos));
+        }
+    }
+    result
+}
+
+/// Fisher-Yates shuffle seeded from the std's random hasher state
+fn shuffle<T>(items: &mut [T]) {
+    let mut seed = RandomState::new().hash_one(SystemTime::now()) | 1;
+    for i in (1..items.len()).rev() {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        let bound = u64::try_from(i + 1).unwrap_or(u64::MAX);
+        let j = usize::try_from(seed % bound).unwrap_or(0);
+        items.swap(i, j);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use itertools::Itertools;
+    use test_case::test_case;
+
+    use super::{QueueOrder, moves, reorder};
+    use crate::{
+        config::queue_sort::{QueueSort, QueueSortKey},
+        mpd::commands::Song,
+    };
+
+    fn song(id: u32, album: Option<&str>) -> Song {
+        Song {
+            id,
+            file: id.to_string(),
+            metadata: album
+                .map(|album| HashMap::from([("album".to_owned(), album.to_owned().into())]))
+                .unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    fn apply_moves(current: &[u32], moves: &[(u32, usize)]) -> Vec<u32> {
+        let mut result = current.to_vec();
+        for (id, pos) in moves {
+            let from = result.iter().position(|v| v == id).unwrap_or_default();
+            let id = result.remove(from);
+            result.insert(*pos, id);
+        }
+        result
+    }
+
+    #[test_case(&[1, 2, 3, 4], &[1, 2, 3, 4], 0)]
+    #[test_case(&[1, 2, 3, 4], &[4, 3, 2, 1], 3)]
+    #[test_case(&[1, 2, 3, 4], &[2, 3, 4, 1], 3)]
+    #[test_case(&[1, 2, 3, 4], &[4, 1, 2, 3], 1)]
+    #[test_case(&[5, 1, 4, 2, 3], &[1, 2, 3, 4, 5], 4)]
+    fn moves_reorder_queue(current: &[u32], desired: &[u32], count: usize) {
+        let result = moves(current, desired);
+
+        assert_eq!(apply_moves(current, &result), desired);
+        assert_eq!(result.len(), count);
+    }
+
+    #[test]
+    fn reorders_only_songs_at_positions() {
+        let queue = ["c", "x", "b", "y", "a"]
+            .into_iter()
+            .zip(1..)
+            .map(|(title, id)| Song {
+                metadata: HashMap::from([("title".to_owned(), title.to_owned().into())]),
+                ..song(id, None)
+            })
+            .collect_vec();
+        let order = QueueOrder::Sort(QueueSort {
+            name: "Title".to_owned(),
+            keys: vec![QueueSortKey::Tag("title".to_owned())],
+            reverse: false,
+        });
+
+        let result = reorder(queue, &[4, 0, 2], &order);
+
+        assert_eq!(result, [5, 2, 3, 4, 1]);
+    }
+
+    #[test]
+    fn shuffle_by_album_keeps_albums_together() {
+        let queue = vec![
+            song(1, Some("a")),
+            song(2, Some("b")),
+            song(3, Some("a")),
+            song(4, None),
+            song(5, Some("b")),
+            song(6, Some("a")),
+        ];
+
+        let result = reorder(queue, &[], &QueueOrder::ShuffleByAlbum);
+
+        let groups = result
+            .iter()
+            .chunk_by(|id| match id {
+                1 | 3 | 6 => 'a',
+                2 | 5 => 'b',
+                _ => 'x',
+            })
+            .into_iter()
+            .map(|(album, ids)| (album, ids.copied().collect_vec()))
+            .sorted()
+            .collect_vec();
+        assert_eq!(groups, [('a', vec![1, 3, 6]), ('b', vec![2, 5]), ('x', vec![4])]);
+    }
+
+    #[test]
+    fn shuffle_keeps_all_songs() {
+        let queue = (1..=50).map(|id| song(id, None)).collect_vec();
+
+        let result = reorder(queue, &[], &QueueOrder::Shuffle);
+
+        assert_eq!(result.into_iter().sorted().collect_vec(), (1..=50).collect_vec());
+    }
+}
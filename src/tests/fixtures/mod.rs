@@ -1,4 +1,7 @@
-use std::{cell::Cell, collections::HashSet};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashSet,
+};
 
 use crossbeam::channel::{Receiver, Sender, unbounded};
 use ratatui::{Terminal, backend::TestBackend};
@@ -11,11 +14,14 @@ use crate::{
     mpd::commands::Status,
     shared::{
         events::{ClientRequest, WorkRequest},
+        history::History,
         lrc::LrcIndex,
+        queue_history::QueueHistory,
     },
 };
 
 pub mod mpd_client;
+pub mod mpd_server;
 
 #[fixture]
 pub fn status() -> Status {
@@ -39,22 +45,29 @@ pub fn app_context(
 ) -> AppContext {
     let chan1 = unbounded();
     let config = ConfigFile::default()
-        .into_config(None, None, None, None, true)
+        .into_config(None, None, None, None, None, true)
         .expect("Test default config to convert correctly");
 
     let chan1 = Box::leak(Box::new(chan1));
     let scheduler = Scheduler::new((chan1.0.clone(), unbounded().0));
+    let config = std::sync::Arc::new(config);
     AppContext {
         status: Status::default(),
-        config: std::sync::Arc::new(config),
+        user_config: std::sync::Arc::clone(&config),
+        config,
         queue: Vec::default(),
+        queue_version: None,
         app_event_sender: chan1.0.clone(),
         work_sender: work_request_channel.0.clone(),
         client_request_sender: client_request_channel.0.clone(),
         supported_commands: HashSet::new(),
         needs_render: Cell::new(false),
         lrc_index: LrcIndex::default(),
+        history: History::default(),
+        queue_history: RefCell::new(QueueHistory::default()),
         should_fetch_stickers: false,
+        pending_keys: Vec::new(),
+        pending_count: None,
         rendered_frames: 0,
         scheduler,
     }
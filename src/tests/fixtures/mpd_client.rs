@@ -10,6 +10,7 @@ use rstest::fixture;
 
 use crate::mpd::{
     commands::{
+        CurrentIndex,
         IdleEvent,
         ListFiles,
         LsInfo,
@@ -21,12 +22,22 @@ use crate::mpd::{
         list::MpdList,
         list_playlist::FileList,
         mpd_config::MpdConfig,
+        replay_gain::ReplayGainMode,
         status::OnOffOneshot,
         stickers::Sticker,
         volume::Bound,
     },
     errors::MpdError,
-    mpd_client::{Filter, MpdClient, QueueMoveTarget, SaveMode, SingleOrRange, Tag, ValueChange},
+    mpd_client::{
+        Filter,
+        MpdClient,
+        PlaybackRange,
+        QueueMoveTarget,
+        SaveMode,
+        SingleOrRange,
+        Tag,
+        ValueChange,
+    },
     proto_client::SocketClient,
 };
 
@@ -47,12 +58,13 @@ pub fn client() -> TestMpdClient {
                 id: i,
                 file: format!("{}_{}_file_{i}", *artist, *album),
                 metadata: HashMap::from([
-                    ("artist".to_owned(), (*artist).to_string()),
-                    ("album".to_owned(), (*album).to_string()),
-                    ("title".to_owned(), format!("{}_{}_file_{i}", *artist, *album)),
+                    ("artist".to_owned(), (*artist).to_string().into()),
+                    ("album".to_owned(), (*album).to_string().into()),
+                    ("title".to_owned(), format!("{}_{}_file_{i}", *artist, *album).into()),
                 ]),
                 duration: Some(Duration::from_secs(i.into())),
                 stickers: None,
+                priority: 0,
             })
         })
         .collect();
@@ -259,6 +271,28 @@ impl MpdClient for TestMpdClient {
         Ok(())
     }
 
+    fn crossfade(&mut self, seconds: u32) -> MpdResult<()> {
+        self.status.xfade = Some(seconds);
+        Ok(())
+    }
+
+    fn mixramp_db(&mut self, _db: f32) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn mixramp_delay(&mut self, _seconds: Option<f32>) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn replay_gain_mode(&mut self, mode: ReplayGainMode) -> MpdResult<()> {
+        self.status.replay_gain = Some(mode);
+        Ok(())
+    }
+
+    fn replay_gain_status(&mut self) -> MpdResult<ReplayGainMode> {
+        Ok(self.status.replay_gain.unwrap_or_default())
+    }
+
     fn mount(&mut self, _name: &str, _path: &str) -> MpdResult<()> {
         todo!("Not yet implemented")
     }
@@ -271,10 +305,22 @@ impl MpdClient for TestMpdClient {
         todo!("Not yet implemented")
     }
 
+    fn list_neighbors(&mut self) -> MpdResult<crate::mpd::commands::Neighbors> {
+        todo!("Not yet implemented")
+    }
+
     fn add(&mut self, _path: &str) -> MpdResult<()> {
         todo!("Not yet implemented")
     }
 
+    fn add_relative_index(&mut self, _path: &str, _id: usize) -> MpdResult<CurrentIndex> {
+        todo!("Not yet implemented")
+    }
+
+    fn add_at_position(&mut self, _path: &str, _position: usize) -> MpdResult<CurrentIndex> {
+        todo!("Not yet implemented")
+    }
+
     fn clear(&mut self) -> MpdResult<()> {
         self.songs.clear();
         self.current_song_idx = None;
@@ -294,6 +340,13 @@ impl MpdClient for TestMpdClient {
         Ok(Some(self.queue.iter().map(|idx| self.songs[*idx].clone()).collect_vec()))
     }
 
+    fn status_with_plchanges_pos_id(
+        &mut self,
+        _version: u32,
+    ) -> MpdResult<(Status, Vec<crate::mpd::commands::plchanges::PosId>)> {
+        todo!("Not yet implemented")
+    }
+
     /// `FilterKind` not implemented, everything is treated as Contains
     fn find(&mut self, filter: &[Filter<'_>]) -> MpdResult<Vec<Song>> {
         Ok(self
@@ -303,11 +356,11 @@ impl MpdClient for TestMpdClient {
                 let mut matches = true;
                 let values = [
                     s.artist(),
-                    s.metadata.get("albumartist"),
+                    s.tag("albumartist"),
                     s.album(),
                     s.title(),
                     Some(&s.file),
-                    s.metadata.get("genre"),
+                    s.tag("genre"),
                 ];
 
                 for filter in filter {
@@ -344,11 +397,11 @@ impl MpdClient for TestMpdClient {
                 let mut matches = true;
                 let values = [
                     s.artist(),
-                    s.metadata.get("albumartist"),
+                    s.tag("albumartist"),
                     s.album(),
                     s.title(),
                     Some(&s.file),
-                    s.metadata.get("genre"),
+                    s.tag("genre"),
                 ];
 
                 for filter in filter {
@@ -397,12 +450,40 @@ impl MpdClient for TestMpdClient {
         todo!("Not yet implemented")
     }
 
+    fn move_ids(&mut self, _moves: &[(u32, usize)]) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn swap(&mut self, _first: usize, _second: usize) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn swap_id(&mut self, _first: u32, _second: u32) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn shuffle(&mut self, _range: Option<SingleOrRange>) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn set_priority(&mut self, _priority: u8, _songs: SingleOrRange) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn set_priority_id(&mut self, _priority: u8, _ids: &[u32]) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn set_range_id(&mut self, _id: u32, _range: PlaybackRange) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
     fn find_one(&mut self, filter: &[Filter<'_>]) -> MpdResult<Option<Song>> {
         let mut res = self.find(filter)?;
         if res.len() > 1 {
             Err(MpdError::Generic("More than one song found".to_string()))
         } else {
-            Ok(Some(res.remove(0)))
+            Ok(res.pop())
         }
     }
 
@@ -410,10 +491,18 @@ impl MpdClient for TestMpdClient {
         todo!("Not yet implemented")
     }
 
+    fn find_add_next(&mut self, _filter: &[Filter<'_>]) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
     fn search_add(&mut self, _filter: &[Filter<'_>]) -> MpdResult<()> {
         todo!("Not yet implemented")
     }
 
+    fn search_add_next(&mut self, _filter: &[Filter<'_>]) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
     fn list_tag(&mut self, _tag: Tag, _filter: Option<&[Filter<'_>]>) -> MpdResult<MpdList> {
         todo!("Not yet implemented")
     }
@@ -434,6 +523,18 @@ impl MpdClient for TestMpdClient {
         todo!("Not yet implemented")
     }
 
+    fn stats(&mut self) -> MpdResult<crate::mpd::commands::Stats> {
+        todo!("Not yet implemented")
+    }
+
+    fn count(
+        &mut self,
+        _filter: &[Filter<'_>],
+        _group: Option<Tag>,
+    ) -> MpdResult<Vec<crate::mpd::commands::TagCount>> {
+        todo!("Not yet implemented")
+    }
+
     fn list_playlists(&mut self) -> MpdResult<Vec<Playlist>> {
         self.playlists
             .iter()
@@ -468,6 +569,7 @@ impl MpdClient for TestMpdClient {
                         duration: None,
                         metadata: HashMap::default(),
                         stickers: None,
+                        priority: 0,
                     })
                     .collect())
             },
@@ -505,11 +607,20 @@ impl MpdClient for TestMpdClient {
 
     fn add_to_playlist(
         &mut self,
-        _playlist_name: &str,
-        _uri: &str,
+        playlist_name: &str,
+        uri: &str,
         _target_position: Option<usize>,
     ) -> MpdResult<()> {
-        todo!("Not yet implemented")
+        let Some(idx) = self.songs.iter().position(|s| s.file == uri) else {
+            return Err(MpdError::Generic("Song not found".to_string()));
+        };
+        if let Some(playlist) = self.playlists.iter_mut().find(|p| p.name == playlist_name) {
+            playlist.songs_indices.push(idx);
+        } else {
+            self.playlists
+                .push(TestPlaylist { name: playlist_name.to_string(), songs_indices: vec![idx] });
+        }
+        Ok(())
     }
 
     fn save_queue_as_playlist(&mut self, _name: &str, _mode: Option<SaveMode>) -> MpdResult<()> {
@@ -537,10 +648,50 @@ impl MpdClient for TestMpdClient {
         todo!("Not yet implemented")
     }
 
+    fn switch_to_partition(&mut self, _name: &str) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn new_partition(&mut self, _name: &str) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn delete_partition(&mut self, _name: &str) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn list_partitions(&mut self) -> MpdResult<crate::mpd::commands::partitions::Partitions> {
+        todo!("Not yet implemented")
+    }
+
+    fn move_output(&mut self, _output_name: &str) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
     fn decoders(&mut self) -> MpdResult<crate::mpd::commands::decoders::Decoders> {
         todo!("Not yet implemented")
     }
 
+    fn subscribe(&mut self, _channel: &str) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn unsubscribe(&mut self, _channel: &str) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn channels(&mut self) -> MpdResult<crate::mpd::commands::messages::Channels> {
+        todo!("Not yet implemented")
+    }
+
+    fn read_messages(&mut self) -> MpdResult<crate::mpd::commands::messages::Messages> {
+        todo!("Not yet implemented")
+    }
+
+    fn send_message(&mut self, _channel: &str, _message: &str) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
     fn sticker(&mut self, _uri: &str, _name: &str) -> MpdResult<Option<Sticker>> {
         todo!("Not yet implemented")
     }
@@ -590,7 +741,7 @@ impl MpdClient for TestMpdClient {
     }
 
     fn config(&mut self) -> Option<&MpdConfig> {
-        todo!("Not yet implemented")
+        None
     }
 }
 
@@ -0,0 +1,62 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    os::unix::net::UnixListener,
+    path::PathBuf,
+};
+
+use crate::config::MpdAddress;
+
+/// Fake MPD server listening on a unix socket in the temp dir. Every
+/// connection is greeted like MPD does and gets its own line handler from
+/// the factory, which is given the index of the connection. The handler
+/// returns the response to a line, `None` leaves the line unanswered.
+/// The socket is removed when the server is dropped.
+pub struct FakeMpdServer {
+    path: PathBuf,
+}
+
+impl FakeMpdServer {
+    #[allow(clippy::unwrap_used)]
+    pub fn start<F, H>(name: &str, handler: F) -> Self
+    where
+        F: Fn(usize) -> H + Send + 'static,
+        H: FnMut(&str) -> Option<String> + Send + 'static,
+    {
+        let path =
+            std::env::temp_dir().join(format!("rmpc-test-{name}-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).expect("socket to bind");
+        std::thread::spawn(move || {
+            for (connection, stream) in listener.incoming().enumerate() {
+                let mut stream = stream.unwrap();
+                let mut handle_line = handler(connection);
+                std::thread::spawn(move || {
+                    stream.write_all(b"OK MPD 0.24.0\n").unwrap();
+                    for line in BufReader::new(stream.try_clone().unwrap()).lines() {
+                        let Ok(line) = line else {
+                            return;
+                        };
+                        let Some(response) = handle_line(&line) else {
+                            continue;
+                        };
+                        if stream.write_all(response.as_bytes()).is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+
+        Self { path }
+    }
+
+    pub fn address(&self) -> MpdAddress {
+        MpdAddress::SocketPath(self.path.to_string_lossy().into_owned())
+    }
+}
+
+impl Drop for FakeMpdServer {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
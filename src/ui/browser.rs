@@ -1,11 +1,11 @@
 use anyhow::Result;
 use crossterm::event::KeyCode;
-use itertools::Itertools;
+use itertools::{Either, Itertools};
 use ratatui::prelude::Rect;
 
 use super::{
     dirstack::{DirStack, DirStackItem},
-    panes::Pane,
+    panes::{Pane, rate_songs},
 };
 use crate::{
     MpdQueryResult,
@@ -13,7 +13,9 @@ use crate::{
     context::AppContext,
     mpd::{client::Client, commands::Song},
     shared::{
+        ext::mpd_client::MpdClientExt,
         key_event::KeyEvent,
+        macros::{status_info, status_warn},
         mouse_event::{MouseEvent, MouseEventKind},
         mpd_query::EXTERNAL_COMMAND,
     },
@@ -39,6 +41,11 @@ where
         &self,
         item: T,
     ) -> impl FnOnce(&mut Client<'_>) -> Result<Vec<Song>> + Send + 'static;
+    /// Uri which MPD's `add` accepts for the item as is, ie. a directory or
+    /// a song file. Items without one are resolved by `list_songs_in_item`.
+    fn add_uri(&self, item: &T) -> Option<String> {
+        None
+    }
     fn prepare_preview(&mut self, context: &AppContext) -> Result<()>;
     fn add(&self, item: &T, context: &AppContext) -> Result<()>;
     fn add_next(&self, item: &T, context: &AppContext) -> Result<()>;
@@ -50,7 +57,12 @@ where
     fn rename(&self, item: &T, context: &AppContext) -> Result<()> {
         Ok(())
     }
-    fn move_selected(&mut self, direction: MoveDirection, context: &AppContext) -> Result<()> {
+    fn move_selected(
+        &mut self,
+        direction: MoveDirection,
+        count: usize,
+        context: &AppContext,
+    ) -> Result<()> {
         Ok(())
     }
     fn handle_filter_input(&mut self, event: &mut KeyEvent, context: &AppContext) -> Result<()> {
@@ -59,7 +71,7 @@ where
         }
 
         let config = &context.config;
-        match event.as_common_action(context) {
+        match event.as_input_common_action(context) {
             Some(CommonAction::Close) => {
                 self.set_filter_input_mode_active(false);
                 self.stack_mut().current_mut().set_filter(None, config);
@@ -89,6 +101,26 @@ where
         Ok(())
     }
 
+    fn marked_or_selected_songs(&self, context: &AppContext) -> Result<Vec<Song>> {
+        let current = self.stack().current();
+        let items: Vec<_> = if current.marked().is_empty() {
+            current
+                .selected()
+                .map(|item| self.list_songs_in_item(item.clone()))
+                .into_iter()
+                .collect()
+        } else {
+            current.marked_items().map(|item| self.list_songs_in_item(item.clone())).collect()
+        };
+        if items.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        context.query_sync(move |client| {
+            items.into_iter().map(|item| (item)(client)).flatten_ok().try_collect()
+        })
+    }
+
     fn handle_global_action(&mut self, event: &mut KeyEvent, context: &AppContext) -> Result<()> {
         let Some(action) = event.as_global_action(context) else {
             return Ok(());
@@ -172,6 +204,7 @@ where
                         .current_mut()
                         .select_idx(idx_to_select, context.config.scrolloff);
                     if let Some(item) = self.stack().current().selected() {
+                        context.record_queue_edit();
                         self.add(item, context)?;
                     }
 
@@ -228,20 +261,28 @@ where
 
         match action {
             CommonAction::Up => {
-                self.stack_mut().current_mut().prev(config.scrolloff, config.wrap_navigation);
+                self.stack_mut().current_mut().prev_by(
+                    context.count(),
+                    config.scrolloff,
+                    config.wrap_navigation,
+                );
                 self.prepare_preview(context);
                 context.render()?;
             }
             CommonAction::Down => {
-                self.stack_mut().current_mut().next(config.scrolloff, config.wrap_navigation);
+                self.stack_mut().current_mut().next_by(
+                    context.count(),
+                    config.scrolloff,
+                    config.wrap_navigation,
+                );
                 self.prepare_preview(context);
                 context.render()?;
             }
             CommonAction::MoveUp => {
-                self.move_selected(MoveDirection::Up, context);
+                self.move_selected(MoveDirection::Up, context.count(), context)?;
             }
             CommonAction::MoveDown => {
-                self.move_selected(MoveDirection::Down, context);
+                self.move_selected(MoveDirection::Down, context.count(), context)?;
             }
             CommonAction::DownHalf => {
                 self.stack_mut().current_mut().next_half_viewport(context.config.scrolloff);
@@ -314,26 +355,57 @@ where
                 context.render()?;
             }
             CommonAction::Add if !self.stack().current().marked().is_empty() => {
-                for idx in self.stack().current().marked() {
-                    let item = &self.stack().current().items[*idx];
-                    self.add(item, context)?;
-                }
+                context.record_queue_edit();
+                let items = self
+                    .stack()
+                    .current()
+                    .marked_items()
+                    .map(|item| match self.add_uri(item) {
+                        Some(uri) => Either::Left(uri),
+                        None => Either::Right(self.list_songs_in_item(item.clone())),
+                    })
+                    .collect_vec();
+                context.command(move |client| {
+                    let mut uris = Vec::new();
+                    for item in items {
+                        match item {
+                            Either::Left(uri) => uris.push(uri),
+                            Either::Right(list_songs) => {
+                                uris.extend(list_songs(client)?.into_iter().map(|song| song.file));
+                            }
+                        }
+                    }
+                    let uris = uris.iter().map(String::as_str).collect_vec();
+                    let added = client.add_files(&uris)?;
+                    if added == uris.len() {
+                        status_info!("{added} items added to queue");
+                    } else {
+                        status_warn!(
+                            "{added} items added to queue, {} could not be added",
+                            uris.len() - added
+                        );
+                    }
+                    Ok(())
+                });
 
                 context.render()?;
             }
             CommonAction::Add => {
                 if let Some(item) = self.stack().current().selected() {
+                    context.record_queue_edit();
                     self.add(item, context);
                 }
             }
             CommonAction::AddNext => {
                 if let Some(item) = self.stack().current().selected() {
+                    context.record_queue_edit();
                     self.add_next(item, context);
                 }
             }
             CommonAction::AddAllNext => {}
             CommonAction::AddAll if !self.stack().current().items.is_empty() => {
                 log::debug!("add all");
+                context.record_queue_edit();
                 self.add_all(context)?;
             }
             CommonAction::AddAll => {}
@@ -367,6 +439,12 @@ where
             CommonAction::PaneUp => {}
             CommonAction::PaneRight => {}
             CommonAction::PaneLeft => {}
+            CommonAction::Rate(_)
+            | CommonAction::RateUp
+            | CommonAction::RateDown
+            | CommonAction::ToggleLike => {
+                rate_songs(action, self.marked_or_selected_songs(context)?, context);
+            }
         }
 
         Ok(())
@@ -171,6 +171,14 @@ impl<T: std::fmt::Debug + DirStackItem + Clone + Send> Dir<T> {
         self.state.prev(scrolloff, wrap);
     }
 
+    pub fn next_by(&mut self, count: usize, scrolloff: usize, wrap: bool) {
+        self.state.next_by(count, scrolloff, wrap);
+    }
+
+    pub fn prev_by(&mut self, count: usize, scrolloff: usize, wrap: bool) {
+        self.state.prev_by(count, scrolloff, wrap);
+    }
+
     pub fn select_idx(&mut self, idx: usize, scrolloff: usize) {
         self.state.select(Some(idx), scrolloff);
     }
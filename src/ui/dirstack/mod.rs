@@ -11,7 +11,12 @@ pub use dir::Dir;
 pub use stack::DirStack;
 pub use state::DirState;
 
-use crate::{config::Config, mpd::commands::Song, ui::panes::browser::DirOrSong};
+use crate::{
+    config::Config,
+    mpd::commands::Song,
+    shared::history::HistoryEntry,
+    ui::panes::browser::DirOrSong,
+};
 
 pub trait DirStackItem {
     fn as_path(&self) -> &str;
@@ -41,7 +46,11 @@ impl DirStackItem for DirOrSong {
             DirOrSong::Dir { name, .. } => if name.is_empty() { "Untitled" } else { name.as_str() }
                 .to_lowercase()
                 .contains(&filter.to_lowercase()),
-            DirOrSong::Song(s) => s.matches(config.theme.browser_song_format.0.as_slice(), filter),
+            DirOrSong::Song(s) => s.matches(
+                config.theme.browser_song_format.0.as_slice(),
+                filter,
+                &config.theme.format_tag_separator,
+            ),
         }
     }
 
@@ -71,11 +80,12 @@ impl DirStackItem for DirOrSong {
                 let spans =
                     [marker_span, Span::from(config.theme.symbols.song.clone()), Span::from(" ")]
                         .into_iter()
-                        .chain(
-                            config.theme.browser_song_format.0.iter().map(|prop| {
-                                Span::from(prop.as_string(Some(s)).unwrap_or_default())
-                            }),
-                        );
+                        .chain(config.theme.browser_song_format.0.iter().map(|prop| {
+                            Span::from(
+                                prop.as_string(Some(s), &config.theme.format_tag_separator)
+                                    .unwrap_or_default(),
+                            )
+                        }));
                 Line::from(spans.collect_vec())
             }
         };
@@ -96,7 +106,11 @@ impl DirStackItem for Song {
     }
 
     fn matches(&self, config: &Config, filter: &str) -> bool {
-        self.matches(config.theme.browser_song_format.0.as_slice(), filter)
+        self.matches(
+            config.theme.browser_song_format.0.as_slice(),
+            filter,
+            &config.theme.format_tag_separator,
+        )
     }
 
     fn to_list_item<'a>(
@@ -130,6 +144,49 @@ impl DirStackItem for Song {
     }
 }
 
+impl DirStackItem for HistoryEntry {
+    fn as_path(&self) -> &str {
+        &self.file
+    }
+
+    fn matches(&self, _config: &Config, filter: &str) -> bool {
+        let filter = filter.to_lowercase();
+        [Some(&self.file), self.title.as_ref(), self.artist.as_ref(), self.album.as_ref()]
+            .into_iter()
+            .flatten()
+            .any(|value| value.to_lowercase().contains(&filter))
+    }
+
+    fn to_list_item<'a>(
+        &self,
+        config: &Config,
+        is_marked: bool,
+        matches_filter: bool,
+        _additional_content: Option<String>,
+    ) -> ListItem<'a> {
+        let marker_span = if is_marked {
+            Span::styled(config.theme.symbols.marker.clone(), config.theme.highlighted_item_style)
+        } else {
+            Span::from(" ".repeat(config.theme.symbols.marker.chars().count()))
+        };
+
+        let played_at = chrono::DateTime::from_timestamp(self.played_at, 0)
+            .map(|time| time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M ").to_string())
+            .unwrap_or_default();
+        let title = self.title.clone().unwrap_or_else(|| self.file.clone());
+        let artist = self.artist.as_deref().unwrap_or("Untitled").to_owned();
+
+        let result = ListItem::new(Line::from(vec![
+            marker_span,
+            Span::styled(played_at, config.theme.borders_style),
+            Span::from(artist),
+            Span::from(" - "),
+            Span::from(title),
+        ]));
+        if matches_filter { result.style(config.theme.highlighted_item_style) } else { result }
+    }
+}
+
 pub trait ScrollingState {
     fn select_scrolling(&mut self, idx: Option<usize>);
     fn get_selected_scrolling(&self) -> Option<usize>;
@@ -72,6 +72,36 @@ impl<T: ScrollingState> DirState<T> {
         }
     }
 
+    /// Same as calling [`Self::next`] `count` times
+    pub fn next_by(&mut self, count: usize, scrolloff: usize, wrap: bool) {
+        let (Some(item_count @ 1..), Some(i)) = (self.content_len, self.get_selected()) else {
+            self.next(scrolloff, wrap);
+            return;
+        };
+        let i = i.min(item_count - 1);
+        let new_idx = if wrap {
+            (i + count % item_count) % item_count
+        } else {
+            i.saturating_add(count).min(item_count - 1)
+        };
+        self.select(Some(new_idx), scrolloff);
+    }
+
+    /// Same as calling [`Self::prev`] `count` times
+    pub fn prev_by(&mut self, count: usize, scrolloff: usize, wrap: bool) {
+        let (Some(item_count @ 1..), Some(i)) = (self.content_len, self.get_selected()) else {
+            self.prev(scrolloff, wrap);
+            return;
+        };
+        let i = i.min(item_count - 1);
+        let new_idx = if wrap {
+            (i + item_count - count % item_count) % item_count
+        } else {
+            i.saturating_sub(count)
+        };
+        self.select(Some(new_idx), scrolloff);
+    }
+
     fn prev_non_wrapping(&mut self, scrolloff: usize) {
         if let Some(item_count) = self.content_len {
             match self.get_selected() {
@@ -542,6 +572,59 @@ mod tests {
         }
     }
 
+    mod next_by {
+        use ratatui::widgets::ListState;
+        use test_case::test_case;
+
+        use crate::ui::dirstack::DirState;
+
+        #[test_case(2, 3, false, 5; "moves by count")]
+        #[test_case(2, 999_999_999, false, 9; "caps at last item")]
+        #[test_case(8, 3, true, 1; "wraps around")]
+        #[test_case(2, 999_999_999, true, 1; "wraps around with huge count")]
+        fn moves_selection(selected: usize, count: usize, wrap: bool, expected: usize) {
+            let mut subject: DirState<ListState> = DirState::default();
+            subject.set_content_len(Some(10));
+            subject.select(Some(selected), 0);
+
+            subject.next_by(count, 0, wrap);
+
+            assert_eq!(subject.get_selected(), Some(expected));
+        }
+
+        #[test]
+        fn selects_first_item_when_nothing_is_selected() {
+            let mut subject: DirState<ListState> = DirState::default();
+            subject.set_content_len(Some(10));
+            subject.select(None, 0);
+
+            subject.next_by(5, 0, false);
+
+            assert_eq!(subject.get_selected(), Some(0));
+        }
+    }
+
+    mod prev_by {
+        use ratatui::widgets::ListState;
+        use test_case::test_case;
+
+        use crate::ui::dirstack::DirState;
+
+        #[test_case(5, 3, false, 2; "moves by count")]
+        #[test_case(5, 999_999_999, false, 0; "caps at first item")]
+        #[test_case(1, 3, true, 8; "wraps around")]
+        #[test_case(2, 999_999_999, true, 3; "wraps around with huge count")]
+        fn moves_selection(selected: usize, count: usize, wrap: bool, expected: usize) {
+            let mut subject: DirState<ListState> = DirState::default();
+            subject.set_content_len(Some(10));
+            subject.select(Some(selected), 0);
+
+            subject.prev_by(count, 0, wrap);
+
+            assert_eq!(subject.get_selected(), Some(expected));
+        }
+    }
+
     mod next_half_viewport {
         use ratatui::widgets::ListState;
 
@@ -11,8 +11,12 @@ use modals::{
     decoders::DecodersModal,
     input_modal::InputModal,
     keybinds::KeybindsModal,
+    mounts::MountsModal,
     outputs::OutputsModal,
+    partitions::PartitionsModal,
+    profiles::ProfilesModal,
     song_info::SongInfoModal,
+    stats::{GROUP_TAGS, StatsModal},
 };
 use panes::{PaneContainer, Panes, pane_call};
 use ratatui::{
@@ -38,12 +42,14 @@ use crate::{
     context::AppContext,
     core::command::{create_env, run_external},
     mpd::{
-        commands::{State, idle::IdleEvent},
+        commands::{Message, State, idle::IdleEvent},
         mpd_client::{FilterKind, MpdClient, ValueChange},
         version::Version,
     },
     shared::{
-        events::{Level, WorkRequest},
+        events::{AppEvent, Level, WorkRequest},
+        ext::mpd_client::MpdClientExt,
+        ipc::GetIpcCommand,
         key_event::KeyEvent,
         macros::{modal, status_error, status_info, status_warn},
         mouse_event::MouseEvent,
@@ -77,6 +83,9 @@ pub struct Ui<'ui> {
 
 const OPEN_DECODERS_MODAL: &str = "open_decoders_modal";
 const OPEN_OUTPUTS_MODAL: &str = "open_outputs_modal";
+const OPEN_MOUNTS_MODAL: &str = "open_mounts_modal";
+const OPEN_PARTITIONS_MODAL: &str = "open_partitions_modal";
+const OPEN_STATS_MODAL: &str = "open_stats_modal";
 
 macro_rules! active_tab_call {
     ($self:ident, $fn:ident($($param:expr),+)) => {
@@ -336,6 +345,52 @@ impl<'ui> Ui<'ui> {
                         Ok(())
                     });
                 }
+                GlobalAction::CrossfadeUp => {
+                    let crossfade = context.status.xfade.unwrap_or(0).saturating_add(1);
+                    context.command(move |client| {
+                        client.crossfade(crossfade)?;
+                        Ok(())
+                    });
+                }
+                GlobalAction::CrossfadeDown => {
+                    let crossfade = context.status.xfade.unwrap_or(0).saturating_sub(1);
+                    context.command(move |client| {
+                        client.crossfade(crossfade)?;
+                        Ok(())
+                    });
+                }
+                GlobalAction::Undo => {
+                    let files = context.queue_history.borrow_mut().undo(&context.queue);
+                    if let Some(files) = files {
+                        context.command(move |client| {
+                            client.restore_queue(&files)?;
+                            status_info!("Queue change undone");
+                            Ok(())
+                        });
+                    } else {
+                        status_info!("Nothing to undo");
+                    }
+                }
+                GlobalAction::Redo => {
+                    let files = context.queue_history.borrow_mut().redo(&context.queue);
+                    if let Some(files) = files {
+                        context.command(move |client| {
+                            client.restore_queue(&files)?;
+                            status_info!("Queue change redone");
+                            Ok(())
+                        });
+                    } else {
+                        status_info!("Nothing to redo");
+                    }
+                }
+                GlobalAction::CycleReplayGain => {
+                    let mode = context.status.replay_gain.unwrap_or_default().cycle();
+                    context.command(move |client| {
+                        client.replay_gain_mode(mode)?;
+                        status_info!("ReplayGain mode set to {mode}");
+                        Ok(())
+                    });
+                }
                 GlobalAction::SeekForward
                     if matches!(context.status.state, State::Play | State::Pause) =>
                 {
@@ -361,7 +416,7 @@ impl<'ui> Ui<'ui> {
                     context.render()?;
                 }
                 GlobalAction::SwitchToTab(name) => {
-                    if context.config.tabs.names.contains(name) {
+                    if context.config.tabs.names.contains(&name) {
                         self.change_tab(name.clone(), context)?;
                         context.render()?;
                     } else {
@@ -398,6 +453,39 @@ impl<'ui> Ui<'ui> {
                         .replace_id(OPEN_DECODERS_MODAL)
                         .query(|client| Ok(MpdQueryResult::Decoders(client.decoders()?.0)));
                 }
+                GlobalAction::ShowProfiles => {
+                    if context.config.profiles.is_empty() {
+                        status_warn!("No profiles are configured");
+                    } else {
+                        let modal = ProfilesModal::new(context);
+                        modal!(context, modal);
+                    }
+                }
+                GlobalAction::Reconnect => {
+                    context.app_event_sender.send(AppEvent::ReconnectNow)?;
+                }
+                GlobalAction::ShowStats => {
+                    context
+                        .query()
+                        .id(OPEN_STATS_MODAL)
+                        .replace_id(OPEN_STATS_MODAL)
+                        .query(|client| Ok(modals::stats::fetch(client, GROUP_TAGS[0])?));
+                }
+                GlobalAction::ShowPartitions => {
+                    context
+                        .query()
+                        .id(OPEN_PARTITIONS_MODAL)
+                        .replace_id(OPEN_PARTITIONS_MODAL)
+                        .query(|client| {
+                            Ok(MpdQueryResult::Partitions(client.list_partitions()?.0))
+                        });
+                }
+                GlobalAction::ShowMounts => {
+                    let neighbors_supported = context.supported_commands.contains("listneighbors");
+                    context.query().id(OPEN_MOUNTS_MODAL).replace_id(OPEN_MOUNTS_MODAL).query(
+                        move |client| Ok(modals::mounts::fetch(client, neighbors_supported)?),
+                    );
+                }
                 GlobalAction::ShowCurrentSongInfo => {
                     if let Some((_, current_song)) = context.find_current_song_in_queue() {
                         modal!(context, SongInfoModal::new(current_song.clone()));
@@ -428,6 +516,35 @@ impl<'ui> Ui<'ui> {
         })
     }
 
+    pub fn handle_ipc_query(
+        &mut self,
+        command: GetIpcCommand,
+        context: &AppContext,
+    ) -> Result<serde_json::Value> {
+        let focused_pane = self
+            .tabs
+            .get(&self.active_tab)
+            .context(anyhow!("Expected tab '{}' to be defined", self.active_tab))?
+            .focused_pane()
+            .cloned();
+
+        match command {
+            GetIpcCommand::ActiveTab => Ok(serde_json::Value::String(self.active_tab.to_string())),
+            GetIpcCommand::FocusedPane => Ok(focused_pane
+                .map_or(serde_json::Value::Null, |pane| {
+                    serde_json::Value::String(pane.to_string())
+                })),
+            GetIpcCommand::SelectedSongs => {
+                let Some(focused_pane) = focused_pane else {
+                    return Ok(serde_json::Value::Array(Vec::new()));
+                };
+                let mut pane = self.panes.get_mut(&focused_pane, context)?;
+                let songs = pane_call!(pane, selected_songs(context))?;
+                Ok(serde_json::to_value(songs)?)
+            }
+        }
+    }
+
     pub fn on_ui_app_event(&mut self, event: UiAppEvent, context: &mut AppContext) -> Result<()> {
         match event {
             UiAppEvent::Modal(modal) => {
@@ -441,6 +558,24 @@ impl<'ui> Ui<'ui> {
                 context.render()?;
             }
             UiAppEvent::ChangeTab(tab_name) => self.change_tab(tab_name, context)?,
+            UiAppEvent::BrowseDirectory(path) => {
+                let tab_name = context.config.tabs.names.iter().find(|name| {
+                    context.config.tabs.tabs.get(*name).is_some_and(|tab| {
+                        tab.panes.panes_iter().any(|pane| pane.pane == PaneType::Directories)
+                    })
+                });
+                let Some(tab_name) = tab_name.cloned() else {
+                    status_warn!("No tab contains the Directories pane");
+                    return Ok(());
+                };
+
+                self.change_tab(tab_name, context)?;
+                if let Panes::Directories(pane) =
+                    self.panes.get_mut(&PaneType::Directories, context)?
+                {
+                    pane.browse(&path, context);
+                }
+            }
         }
         Ok(())
     }
@@ -529,6 +664,8 @@ impl<'ui> Ui<'ui> {
                 Panes::Artists(p) => p.on_event(&mut event, visible, context),
                 Panes::Playlists(p) => p.on_event(&mut event, visible, context),
                 Panes::Search(p) => p.on_event(&mut event, visible, context),
+                Panes::History(p) => p.on_event(&mut event, visible, context),
+                Panes::Messages(p) => p.on_event(&mut event, visible, context),
                 Panes::AlbumArtists(p) => p.on_event(&mut event, visible, context),
                 Panes::AlbumArt(p) => p.on_event(&mut event, visible, context),
                 Panes::Lyrics(p) => p.on_event(&mut event, visible, context),
@@ -569,6 +706,8 @@ impl<'ui> Ui<'ui> {
                     Panes::Artists(p) => p.on_query_finished(id, data, visible, context),
                     Panes::Playlists(p) => p.on_query_finished(id, data, visible, context),
                     Panes::Search(p) => p.on_query_finished(id, data, visible, context),
+                    Panes::History(p) => p.on_query_finished(id, data, visible, context),
+                    Panes::Messages(p) => p.on_query_finished(id, data, visible, context),
                     Panes::AlbumArtists(p) => p.on_query_finished(id, data, visible, context),
                     Panes::AlbumArt(p) => p.on_query_finished(id, data, visible, context),
                     Panes::Lyrics(p) => p.on_query_finished(id, data, visible, context),
@@ -590,6 +729,15 @@ impl<'ui> Ui<'ui> {
                 (OPEN_DECODERS_MODAL, MpdQueryResult::Decoders(decoders)) => {
                     modal!(context, DecodersModal::new(decoders));
                 }
+                (OPEN_STATS_MODAL, MpdQueryResult::Stats { stats, counts }) => {
+                    modal!(context, StatsModal::new(stats, counts));
+                }
+                (OPEN_PARTITIONS_MODAL, MpdQueryResult::Partitions(partitions)) => {
+                    modal!(context, PartitionsModal::new(partitions));
+                }
+                (OPEN_MOUNTS_MODAL, MpdQueryResult::Mounts { mounts, neighbors }) => {
+                    modal!(context, MountsModal::new(mounts, neighbors));
+                }
                 (id, mut data) => {
                     // TODO a proper modal target
                     for modal in &mut self.modals {
@@ -611,6 +759,9 @@ pub enum UiAppEvent {
     Modal(ModalWrapper),
     PopModal,
     ChangeTab(TabName),
+    /// Opens the given directory in the Directories pane and switches to the
+    /// first tab which contains it
+    BrowseDirectory(String),
 }
 
 #[derive(Debug, Eq, Hash, PartialEq)]
@@ -631,6 +782,8 @@ pub enum UiEvent {
     Displayed,
     Hidden,
     ConfigChanged,
+    Subscription,
+    Messages(Vec<Message>),
 }
 
 impl TryFrom<IdleEvent> for UiEvent {
@@ -641,6 +794,7 @@ impl TryFrom<IdleEvent> for UiEvent {
             IdleEvent::Player => UiEvent::Player,
             IdleEvent::Database => UiEvent::Database,
             IdleEvent::StoredPlaylist => UiEvent::StoredPlaylist,
+            IdleEvent::Subscription => UiEvent::Subscription,
             _ => return Err(()),
         })
     }
@@ -694,6 +848,7 @@ impl From<&FilterKind> for &'static str {
             FilterKind::Contains => "Contains value",
             FilterKind::StartsWith => "Starts with value",
             FilterKind::Regex => "Regex",
+            FilterKind::Raw => "Raw expression",
         }
     }
 }
@@ -705,6 +860,7 @@ impl std::fmt::Display for FilterKind {
             FilterKind::Contains => write!(f, "Contains value"),
             FilterKind::StartsWith => write!(f, "Starts with value"),
             FilterKind::Regex => write!(f, "Regex"),
+            FilterKind::Raw => write!(f, "Raw expression"),
         }
     }
 }
@@ -715,7 +871,7 @@ impl FilterKind {
             FilterKind::Exact => FilterKind::Contains,
             FilterKind::Contains => FilterKind::StartsWith,
             FilterKind::StartsWith => FilterKind::Regex,
-            FilterKind::Regex => FilterKind::Exact,
+            FilterKind::Regex | FilterKind::Raw => FilterKind::Exact,
         };
         self
     }
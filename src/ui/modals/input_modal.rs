@@ -155,7 +155,11 @@ impl<'a, C: FnMut(&AppContext, &str) -> Result<()> + 'a> Modal for InputModal<'a
     }
 
     fn handle_key(&mut self, key: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
-        let action = key.as_common_action(context);
+        let action = if self.input_focused {
+            key.as_input_common_action(context)
+        } else {
+            key.as_common_action(context)
+        };
         if self.input_focused {
             if let Some(CommonAction::Close) = action {
                 self.input_focused = false;
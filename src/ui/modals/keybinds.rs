@@ -13,7 +13,7 @@ use ratatui::{
 
 use super::{Modal, RectExt};
 use crate::{
-    config::keys::{CommonAction, ToDescription},
+    config::keys::{CommonAction, Key, KeySequence, ToDescription},
     context::AppContext,
     shared::{
         ext::iter::IntoZipLongest2,
@@ -31,12 +31,16 @@ pub struct KeybindsModal {
 }
 
 trait KeybindsExt {
-    fn to_str(&self) -> impl Iterator<Item = (String, String, Cow<'static, str>)>;
+    /// Keybinds continuing after the `prefix` or all keybinds if the prefix is
+    /// empty
+    fn to_str(&self, prefix: &[Key]) -> impl Iterator<Item = (String, String, Cow<'static, str>)>;
 }
 
-impl<K: Display, V: Display + ToDescription> KeybindsExt for HashMap<K, V> {
-    fn to_str(&self) -> impl Iterator<Item = (String, String, Cow<'static, str>)> {
-        self.iter().map(|(key, value)| (key.to_string(), value.to_string(), value.to_description()))
+impl<V: Display + ToDescription> KeybindsExt for HashMap<KeySequence, V> {
+    fn to_str(&self, prefix: &[Key]) -> impl Iterator<Item = (String, String, Cow<'static, str>)> {
+        self.iter()
+            .filter(move |(key, _)| prefix.is_empty() || key.continues(prefix))
+            .map(|(key, value)| (key.to_string(), value.to_string(), value.to_description()))
     }
 }
 
@@ -100,7 +104,11 @@ impl Modal for KeybindsModal {
             .border_set(border::ROUNDED)
             .border_style(app.config.as_border_style())
             .title_alignment(ratatui::prelude::Alignment::Center)
-            .title("Keybinds");
+            .title(if app.pending_keys.is_empty() {
+                "Keybinds".to_owned()
+            } else {
+                format!("Keybinds - {}", KeySequence(app.pending_keys.clone()))
+            });
 
         let margin = Margin { horizontal: 1, vertical: 0 };
         let [header_area, table_area] =
@@ -123,14 +131,16 @@ impl Modal for KeybindsModal {
         let keybinds = &app.config.keybinds;
         let header_style = app.config.theme.current_item_style;
 
-        let global = keybinds.global.to_str().collect_vec();
-        let navigation = keybinds.navigation.to_str().collect_vec();
-        let albums = keybinds.albums.to_str().collect_vec();
-        let artists = keybinds.artists.to_str().collect_vec();
-        let directories = keybinds.directories.to_str().collect_vec();
-        let playlists = keybinds.playlists.to_str().collect_vec();
-        let search = keybinds.search.to_str().collect_vec();
-        let queue = keybinds.queue.to_str().collect_vec();
+        let pending = &app.pending_keys;
+
+        let global = keybinds.global.to_str(pending).collect_vec();
+        let navigation = keybinds.navigation.to_str(pending).collect_vec();
+        let albums = keybinds.albums.to_str(pending).collect_vec();
+        let artists = keybinds.artists.to_str(pending).collect_vec();
+        let directories = keybinds.directories.to_str(pending).collect_vec();
+        let playlists = keybinds.playlists.to_str(pending).collect_vec();
+        let search = keybinds.search.to_str(pending).collect_vec();
+        let queue = keybinds.queue.to_str(pending).collect_vec();
 
         let rows = row_header(&navigation, "Global", header_style)
             .into_iter()
@@ -14,9 +14,13 @@ pub mod confirm_modal;
 pub mod decoders;
 pub mod input_modal;
 pub mod keybinds;
+pub mod mounts;
 pub mod outputs;
+pub mod partitions;
+pub mod profiles;
 pub mod select_modal;
 pub mod song_info;
+pub mod stats;
 
 #[allow(unused)]
 pub(super) trait Modal: std::fmt::Debug {
@@ -0,0 +1,320 @@
+use anyhow::Result;
+use ratatui::{
+    layout::{Constraint, Margin, Rect},
+    style::Style,
+    symbols::border,
+    widgets::{Block, Borders, Cell, Clear, Row, Table, TableState},
+};
+
+use super::{Modal, RectExt, confirm_modal::ConfirmModal, input_modal::InputModal};
+use crate::{
+    AppEvent,
+    MpdQueryResult,
+    config::keys::CommonAction,
+    context::AppContext,
+    mpd::{
+        commands::{list_mounts::Mount, list_neighbors::Neighbor},
+        errors::MpdError,
+        mpd_client::MpdClient,
+    },
+    shared::{
+        key_event::KeyEvent,
+        macros::{modal, pop_modal, status_info, status_warn},
+        mouse_event::{MouseEvent, MouseEventKind},
+    },
+    ui::{UiAppEvent, dirstack::DirState},
+};
+
+pub const REFRESH_MOUNTS: &str = "refresh_mounts";
+
+/// Fetches the current mounts together with the neighbors MPD discovered.
+/// Neighbors are left empty when MPD has no neighbor plugin enabled.
+pub fn fetch(
+    client: &mut impl MpdClient,
+    neighbors_supported: bool,
+) -> Result<MpdQueryResult, MpdError> {
+    let mounts = client.list_mounts()?.0;
+    let neighbors = if neighbors_supported {
+        match client.list_neighbors() {
+            Ok(neighbors) => neighbors.0,
+            Err(MpdError::Mpd(err)) => {
+                log::debug!(err:?; "Failed to list neighbors");
+                Vec::new()
+            }
+            Err(err) => return Err(err),
+        }
+    } else {
+        Vec::new()
+    };
+
+    Ok(MpdQueryResult::Mounts { mounts, neighbors })
+}
+
+#[derive(Debug)]
+enum Entry {
+    Mount(Mount),
+    Neighbor(Neighbor),
+}
+
+#[derive(Debug)]
+pub struct MountsModal {
+    scrolling_state: DirState<TableState>,
+    table_area: Rect,
+    entries: Vec<Entry>,
+}
+
+impl MountsModal {
+    pub fn new(mounts: Vec<Mount>, neighbors: Vec<Neighbor>) -> Self {
+        let mut result = Self {
+            entries: Vec::new(),
+            scrolling_state: DirState::default(),
+            table_area: Rect::default(),
+        };
+        result.set_entries(mounts, neighbors);
+        result.scrolling_state.first();
+
+        result
+    }
+
+    fn set_entries(&mut self, mounts: Vec<Mount>, neighbors: Vec<Neighbor>) {
+        self.entries = mounts
+            .into_iter()
+            .map(Entry::Mount)
+            .chain(neighbors.into_iter().map(Entry::Neighbor))
+            .collect();
+        self.scrolling_state.set_content_len(Some(self.entries.len()));
+    }
+
+    fn selected_entry(&self) -> Option<&Entry> {
+        self.scrolling_state.get_selected().and_then(|idx| self.entries.get(idx))
+    }
+
+    fn confirm_selected(&self, context: &AppContext) -> Result<()> {
+        match self.selected_entry() {
+            Some(Entry::Mount(mount)) => {
+                context
+                    .app_event_sender
+                    .send(AppEvent::UiEvent(UiAppEvent::BrowseDirectory(mount.mount.clone())))?;
+                pop_modal!(context);
+            }
+            Some(Entry::Neighbor(neighbor)) => Self::mount_neighbor(neighbor, context)?,
+            None => {}
+        }
+        Ok(())
+    }
+
+    fn mount_neighbor(neighbor: &Neighbor, context: &AppContext) -> Result<()> {
+        let uri = neighbor.neighbor.clone();
+        // Last segment of the uri, ie. the share name, is a sensible default
+        let name = uri.trim_end_matches('/').rsplit('/').next().unwrap_or_default().to_owned();
+
+        modal!(
+            context,
+            InputModal::new(context)
+                .title("Mount neighbor")
+                .confirm_label("Mount")
+                .input_label("Name:")
+                .initial_value(name)
+                .on_confirm(move |context, name| {
+                    let name = name.to_owned();
+                    let uri = uri.clone();
+                    context.command(move |client| {
+                        client.mount(&name, &uri)?;
+                        status_info!("Mounted '{uri}' as '{name}'");
+                        Ok(())
+                    });
+                    Ok(())
+                })
+        );
+        Ok(())
+    }
+
+    fn unmount_selected(&self, context: &AppContext) -> Result<()> {
+        let Some(Entry::Mount(mount)) = self.selected_entry() else {
+            return Ok(());
+        };
+        if mount.mount.is_empty() {
+            status_warn!("The root of the music directory cannot be unmounted");
+            return Ok(());
+        }
+
+        let name = mount.mount.clone();
+        modal!(
+            context,
+            ConfirmModal::new(context)
+                .message("Are you sure you want to unmount this storage?")
+                .on_confirm(move |context| {
+                    let name = name.clone();
+                    context.command(move |client| {
+                        client.unmount(&name)?;
+                        status_info!("Unmounted '{name}'");
+                        Ok(())
+                    });
+                    Ok(())
+                })
+                .confirm_label("Unmount")
+                .size(45, 6)
+        );
+        Ok(())
+    }
+}
+
+impl Modal for MountsModal {
+    fn render(&mut self, frame: &mut ratatui::Frame, app: &mut AppContext) -> anyhow::Result<()> {
+        let popup_area = frame.area().centered_exact(80, 14);
+        frame.render_widget(Clear, popup_area);
+        if let Some(bg_color) = app.config.theme.modal_background_color {
+            frame.render_widget(Block::default().style(Style::default().bg(bg_color)), popup_area);
+        }
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED)
+            .border_style(app.config.as_border_style())
+            .title_alignment(ratatui::prelude::Alignment::Center)
+            .title("Mounts");
+
+        let table_area = popup_area.inner(Margin { horizontal: 1, vertical: 1 });
+
+        let rows = self.entries.iter().map(|entry| match entry {
+            Entry::Mount(mount) => Row::new([
+                Cell::from(if mount.mount.is_empty() {
+                    "/".to_owned()
+                } else {
+                    mount.mount.clone()
+                }),
+                Cell::from(mount.storage.clone()),
+                Cell::from("mount"),
+            ]),
+            Entry::Neighbor(neighbor) => Row::new([
+                Cell::from(neighbor.name.clone()),
+                Cell::from(neighbor.neighbor.clone()),
+                Cell::from("neighbor"),
+            ]),
+        });
+
+        self.scrolling_state.set_viewport_len(Some(table_area.height.into()));
+
+        let table = Table::new(rows, [
+            Constraint::Percentage(35),
+            Constraint::Percentage(65),
+            Constraint::Length(10),
+        ])
+        .column_spacing(1)
+        .style(app.config.as_text_style())
+        .header(Row::new(["Name", "Storage", "Type"]))
+        .row_highlight_style(app.config.theme.current_item_style);
+
+        let table_area = table_area.inner(Margin { horizontal: 1, vertical: 0 });
+        self.table_area = table_area;
+
+        frame.render_widget(block, popup_area);
+        frame.render_stateful_widget(table, table_area, self.scrolling_state.as_render_state_ref());
+        frame.render_stateful_widget(
+            app.config.as_styled_scrollbar(),
+            popup_area.inner(Margin { horizontal: 0, vertical: 1 }),
+            self.scrolling_state.as_scrollbar_state_ref(),
+        );
+
+        Ok(())
+    }
+
+    fn on_query_finished(
+        &mut self,
+        id: &'static str,
+        data: &mut MpdQueryResult,
+        context: &AppContext,
+    ) -> Result<()> {
+        match (id, data) {
+            (REFRESH_MOUNTS, MpdQueryResult::Mounts { mounts, neighbors }) => {
+                self.set_entries(std::mem::take(mounts), std::mem::take(neighbors));
+                context.render()?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_key(&mut self, key: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
+        if let Some(action) = key.as_common_action(context) {
+            match action {
+                CommonAction::DownHalf => {
+                    self.scrolling_state.next_half_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
+                CommonAction::UpHalf => {
+                    self.scrolling_state.prev_half_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
+                CommonAction::Up => {
+                    self.scrolling_state
+                        .prev(context.config.scrolloff, context.config.wrap_navigation);
+
+                    context.render()?;
+                }
+                CommonAction::Down => {
+                    self.scrolling_state
+                        .next(context.config.scrolloff, context.config.wrap_navigation);
+
+                    context.render()?;
+                }
+                CommonAction::Bottom => {
+                    self.scrolling_state.last();
+
+                    context.render()?;
+                }
+                CommonAction::Top => {
+                    self.scrolling_state.first();
+
+                    context.render()?;
+                }
+                CommonAction::Confirm => {
+                    self.confirm_selected(context)?;
+                }
+                CommonAction::Delete => {
+                    self.unmount_selected(context)?;
+                }
+                CommonAction::Close => {
+                    pop_modal!(context);
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_mouse_event(&mut self, event: MouseEvent, context: &mut AppContext) -> Result<()> {
+        match event.kind {
+            MouseEventKind::LeftClick if self.table_area.contains(event.into()) => {
+                let y: usize = event.y.saturating_sub(self.table_area.y).into();
+                let y = y.saturating_sub(1); // Subtract one to account for table header
+                if let Some(idx) = self.scrolling_state.get_at_rendered_row(y) {
+                    self.scrolling_state.select(Some(idx), context.config.scrolloff);
+                    context.render()?;
+                }
+            }
+            MouseEventKind::DoubleClick if self.table_area.contains(event.into()) => {
+                self.confirm_selected(context)?;
+                context.render()?;
+            }
+            MouseEventKind::MiddleClick => {}
+            MouseEventKind::RightClick => {}
+            MouseEventKind::ScrollDown if self.table_area.contains(event.into()) => {
+                self.scrolling_state.next(context.config.scrolloff, false);
+                context.render()?;
+            }
+            MouseEventKind::ScrollUp if self.table_area.contains(event.into()) => {
+                self.scrolling_state.prev(context.config.scrolloff, false);
+                context.render()?;
+            }
+            MouseEventKind::LeftClick => {}
+            MouseEventKind::DoubleClick => {}
+            MouseEventKind::ScrollDown => {}
+            MouseEventKind::ScrollUp => {}
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,262 @@
+use anyhow::Result;
+use ratatui::{
+    layout::{Constraint, Margin, Rect},
+    style::Style,
+    symbols::border,
+    widgets::{Block, Borders, Cell, Clear, Row, Table, TableState},
+};
+
+use super::{Modal, RectExt, confirm_modal::ConfirmModal, input_modal::InputModal};
+use crate::{
+    AppEvent,
+    MpdQueryResult,
+    config::keys::CommonAction,
+    context::AppContext,
+    mpd::{commands::Partition, mpd_client::MpdClient},
+    shared::{
+        key_event::KeyEvent,
+        macros::{modal, pop_modal, status_info},
+        mouse_event::{MouseEvent, MouseEventKind},
+    },
+    ui::dirstack::DirState,
+};
+
+pub const REFRESH_PARTITIONS: &str = "refresh_partitions";
+
+#[derive(Debug)]
+pub struct PartitionsModal {
+    scrolling_state: DirState<TableState>,
+    partitions_table_area: Rect,
+    partitions: Vec<Partition>,
+}
+
+impl PartitionsModal {
+    pub fn new(partitions: Vec<Partition>) -> Self {
+        let mut result = Self {
+            partitions,
+            scrolling_state: DirState::default(),
+            partitions_table_area: Rect::default(),
+        };
+        result.scrolling_state.set_content_len(Some(result.partitions.len()));
+        result.scrolling_state.first();
+
+        result
+    }
+
+    fn selected_partition_name(&self) -> Option<String> {
+        let idx = self.scrolling_state.get_selected()?;
+        self.partitions.get(idx).map(|partition| partition.name.clone())
+    }
+
+    pub fn switch_to_selected_partition(&mut self, context: &AppContext) -> Result<()> {
+        let Some(name) = self.selected_partition_name() else {
+            return Ok(());
+        };
+        if name == context.status.partition {
+            return Ok(());
+        }
+
+        context.command(move |client| {
+            client.switch_to_partition(&name)?;
+            status_info!("Switched to partition '{name}'");
+            Ok(())
+        });
+        context.app_event_sender.send(AppEvent::PartitionSwitched)?;
+
+        Ok(())
+    }
+
+    fn create_partition(context: &AppContext) -> Result<()> {
+        modal!(
+            context,
+            InputModal::new(context)
+                .title("New partition")
+                .confirm_label("Create")
+                .input_label("Name:")
+                .on_confirm(|context, name| {
+                    let name = name.to_owned();
+                    context.command(move |client| {
+                        client.new_partition(&name)?;
+                        status_info!("Partition '{name}' created");
+                        Ok(())
+                    });
+                    Ok(())
+                })
+        );
+        Ok(())
+    }
+
+    fn delete_selected_partition(&self, context: &AppContext) -> Result<()> {
+        let Some(name) = self.selected_partition_name() else {
+            return Ok(());
+        };
+
+        modal!(
+            context,
+            ConfirmModal::new(context)
+                .message("Are you sure you want to delete this partition? Its queue will be lost.")
+                .on_confirm(move |context| {
+                    let name = name.clone();
+                    context.command(move |client| {
+                        client.delete_partition(&name)?;
+                        status_info!("Partition '{name}' deleted");
+                        Ok(())
+                    });
+                    Ok(())
+                })
+                .confirm_label("Delete")
+                .size(45, 6)
+        );
+        Ok(())
+    }
+}
+
+impl Modal for PartitionsModal {
+    fn render(&mut self, frame: &mut ratatui::Frame, app: &mut AppContext) -> anyhow::Result<()> {
+        let popup_area = frame.area().centered_exact(60, 10);
+        frame.render_widget(Clear, popup_area);
+        if let Some(bg_color) = app.config.theme.modal_background_color {
+            frame.render_widget(Block::default().style(Style::default().bg(bg_color)), popup_area);
+        }
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED)
+            .border_style(app.config.as_border_style())
+            .title_alignment(ratatui::prelude::Alignment::Center)
+            .title("Partitions");
+
+        let table_area = popup_area.inner(Margin { horizontal: 1, vertical: 1 });
+
+        let rows = self.partitions.iter().map(|partition| {
+            Row::new([
+                Cell::from(partition.name.clone()),
+                Cell::from(if partition.name == app.status.partition { "yes" } else { "" }),
+            ])
+        });
+
+        self.scrolling_state.set_viewport_len(Some(table_area.height.into()));
+
+        let table = Table::new(rows, [Constraint::Percentage(100), Constraint::Length(10)])
+            .column_spacing(0)
+            .style(app.config.as_text_style())
+            .header(Row::new(["Name", "Current"]))
+            .row_highlight_style(app.config.theme.current_item_style);
+
+        let table_area = table_area.inner(Margin { horizontal: 1, vertical: 0 });
+        self.partitions_table_area = table_area;
+
+        frame.render_widget(block, popup_area);
+        frame.render_stateful_widget(table, table_area, self.scrolling_state.as_render_state_ref());
+        frame.render_stateful_widget(
+            app.config.as_styled_scrollbar(),
+            popup_area.inner(Margin { horizontal: 0, vertical: 1 }),
+            self.scrolling_state.as_scrollbar_state_ref(),
+        );
+
+        Ok(())
+    }
+
+    fn on_query_finished(
+        &mut self,
+        id: &'static str,
+        data: &mut MpdQueryResult,
+        context: &AppContext,
+    ) -> Result<()> {
+        match (id, data) {
+            (REFRESH_PARTITIONS, MpdQueryResult::Partitions(partitions)) => {
+                self.partitions = std::mem::take(partitions);
+                self.scrolling_state.set_content_len(Some(self.partitions.len()));
+                context.render()?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_key(&mut self, key: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
+        if let Some(action) = key.as_common_action(context) {
+            match action {
+                CommonAction::DownHalf => {
+                    self.scrolling_state.next_half_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
+                CommonAction::UpHalf => {
+                    self.scrolling_state.prev_half_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
+                CommonAction::Up => {
+                    self.scrolling_state
+                        .prev(context.config.scrolloff, context.config.wrap_navigation);
+
+                    context.render()?;
+                }
+                CommonAction::Down => {
+                    self.scrolling_state
+                        .next(context.config.scrolloff, context.config.wrap_navigation);
+
+                    context.render()?;
+                }
+                CommonAction::Bottom => {
+                    self.scrolling_state.last();
+
+                    context.render()?;
+                }
+                CommonAction::Top => {
+                    self.scrolling_state.first();
+
+                    context.render()?;
+                }
+                CommonAction::Confirm => {
+                    self.switch_to_selected_partition(context)?;
+                }
+                CommonAction::Add => {
+                    Self::create_partition(context)?;
+                }
+                CommonAction::Delete => {
+                    self.delete_selected_partition(context)?;
+                }
+                CommonAction::Close => {
+                    pop_modal!(context);
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_mouse_event(&mut self, event: MouseEvent, context: &mut AppContext) -> Result<()> {
+        match event.kind {
+            MouseEventKind::LeftClick if self.partitions_table_area.contains(event.into()) => {
+                let y: usize = event.y.saturating_sub(self.partitions_table_area.y).into();
+                let y = y.saturating_sub(1); // Subtract one to account for table header
+                if let Some(idx) = self.scrolling_state.get_at_rendered_row(y) {
+                    self.scrolling_state.select(Some(idx), context.config.scrolloff);
+                    context.render()?;
+                }
+            }
+            MouseEventKind::DoubleClick if self.partitions_table_area.contains(event.into()) => {
+                self.switch_to_selected_partition(context)?;
+                context.render()?;
+            }
+            MouseEventKind::MiddleClick => {}
+            MouseEventKind::RightClick => {}
+            MouseEventKind::ScrollDown if self.partitions_table_area.contains(event.into()) => {
+                self.scrolling_state.next(context.config.scrolloff, false);
+                context.render()?;
+            }
+            MouseEventKind::ScrollUp if self.partitions_table_area.contains(event.into()) => {
+                self.scrolling_state.prev(context.config.scrolloff, false);
+                context.render()?;
+            }
+            MouseEventKind::LeftClick => {}
+            MouseEventKind::DoubleClick => {}
+            MouseEventKind::ScrollDown => {}
+            MouseEventKind::ScrollUp => {}
+        }
+
+        Ok(())
+    }
+}
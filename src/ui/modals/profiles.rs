@@ -0,0 +1,199 @@
+use anyhow::Result;
+use ratatui::{
+    layout::{Constraint, Margin, Rect},
+    style::Style,
+    symbols::border,
+    widgets::{Block, Borders, Cell, Clear, Row, Table, TableState},
+};
+
+use super::{Modal, RectExt};
+use crate::{
+    config::{MpdAddress, address::MpdProfile, keys::CommonAction},
+    context::AppContext,
+    shared::{
+        events::WorkRequest,
+        key_event::KeyEvent,
+        macros::{pop_modal, status_info},
+        mouse_event::{MouseEvent, MouseEventKind},
+    },
+    ui::dirstack::DirState,
+};
+
+#[derive(Debug)]
+pub struct ProfilesModal {
+    scrolling_state: DirState<TableState>,
+    profiles_table_area: Rect,
+    profiles: Vec<MpdProfile>,
+}
+
+impl ProfilesModal {
+    pub fn new(context: &AppContext) -> Self {
+        let mut result = Self {
+            profiles: context.config.profiles.clone(),
+            scrolling_state: DirState::default(),
+            profiles_table_area: Rect::default(),
+        };
+        result.scrolling_state.set_content_len(Some(result.profiles.len()));
+        let active_idx = result
+            .profiles
+            .iter()
+            .position(|profile| Some(&profile.name) == context.config.active_profile.as_ref());
+        result.scrolling_state.select(Some(active_idx.unwrap_or_default()), 0);
+
+        result
+    }
+
+    fn switch_to_selected_profile(&self, context: &AppContext) -> Result<()> {
+        let Some(profile) =
+            self.scrolling_state.get_selected().and_then(|idx| self.profiles.get(idx))
+        else {
+            return Ok(());
+        };
+        if context.config.active_profile.as_ref() == Some(&profile.name) {
+            pop_modal!(context);
+            return Ok(());
+        }
+
+        status_info!("Connecting to profile '{}'", profile.name);
+        context.work_sender.send(WorkRequest::ConnectProfile { profile: profile.clone() })?;
+        pop_modal!(context);
+
+        Ok(())
+    }
+}
+
+impl Modal for ProfilesModal {
+    fn render(&mut self, frame: &mut ratatui::Frame, app: &mut AppContext) -> anyhow::Result<()> {
+        let popup_area = frame.area().centered_exact(70, 12);
+        frame.render_widget(Clear, popup_area);
+        if let Some(bg_color) = app.config.theme.modal_background_color {
+            frame.render_widget(Block::default().style(Style::default().bg(bg_color)), popup_area);
+        }
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED)
+            .border_style(app.config.as_border_style())
+            .title_alignment(ratatui::prelude::Alignment::Center)
+            .title("Profiles");
+
+        let table_area = popup_area.inner(Margin { horizontal: 1, vertical: 1 });
+
+        let rows = self.profiles.iter().map(|profile| {
+            let address = match &profile.address {
+                MpdAddress::IpAndPort(addr) | MpdAddress::SocketPath(addr) => addr.clone(),
+            };
+            let is_active = app.config.active_profile.as_ref() == Some(&profile.name);
+            Row::new([
+                Cell::from(profile.name.clone()),
+                Cell::from(address),
+                Cell::from(if is_active { "yes" } else { "" }),
+            ])
+        });
+
+        self.scrolling_state.set_viewport_len(Some(table_area.height.into()));
+
+        let table = Table::new(rows, [
+            Constraint::Percentage(35),
+            Constraint::Percentage(65),
+            Constraint::Length(10),
+        ])
+        .column_spacing(1)
+        .style(app.config.as_text_style())
+        .header(Row::new(["Name", "Address", "Current"]))
+        .row_highlight_style(app.config.theme.current_item_style);
+
+        let table_area = table_area.inner(Margin { horizontal: 1, vertical: 0 });
+        self.profiles_table_area = table_area;
+
+        frame.render_widget(block, popup_area);
+        frame.render_stateful_widget(table, table_area, self.scrolling_state.as_render_state_ref());
+        frame.render_stateful_widget(
+            app.config.as_styled_scrollbar(),
+            popup_area.inner(Margin { horizontal: 0, vertical: 1 }),
+            self.scrolling_state.as_scrollbar_state_ref(),
+        );
+
+        Ok(())
+    }
+
+    fn handle_key(&mut self, key: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
+        if let Some(action) = key.as_common_action(context) {
+            match action {
+                CommonAction::DownHalf => {
+                    self.scrolling_state.next_half_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
+                CommonAction::UpHalf => {
+                    self.scrolling_state.prev_half_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
+                CommonAction::Up => {
+                    self.scrolling_state
+                        .prev(context.config.scrolloff, context.config.wrap_navigation);
+
+                    context.render()?;
+                }
+                CommonAction::Down => {
+                    self.scrolling_state
+                        .next(context.config.scrolloff, context.config.wrap_navigation);
+
+                    context.render()?;
+                }
+                CommonAction::Bottom => {
+                    self.scrolling_state.last();
+
+                    context.render()?;
+                }
+                CommonAction::Top => {
+                    self.scrolling_state.first();
+
+                    context.render()?;
+                }
+                CommonAction::Confirm => {
+                    self.switch_to_selected_profile(context)?;
+                }
+                CommonAction::Close => {
+                    pop_modal!(context);
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_mouse_event(&mut self, event: MouseEvent, context: &mut AppContext) -> Result<()> {
+        match event.kind {
+            MouseEventKind::LeftClick if self.profiles_table_area.contains(event.into()) => {
+                let y: usize = event.y.saturating_sub(self.profiles_table_area.y).into();
+                let y = y.saturating_sub(1); // Subtract one to account for table header
+                if let Some(idx) = self.scrolling_state.get_at_rendered_row(y) {
+                    self.scrolling_state.select(Some(idx), context.config.scrolloff);
+                    context.render()?;
+                }
+            }
+            MouseEventKind::DoubleClick if self.profiles_table_area.contains(event.into()) => {
+                self.switch_to_selected_profile(context)?;
+                context.render()?;
+            }
+            MouseEventKind::MiddleClick => {}
+            MouseEventKind::RightClick => {}
+            MouseEventKind::ScrollDown if self.profiles_table_area.contains(event.into()) => {
+                self.scrolling_state.next(context.config.scrolloff, false);
+                context.render()?;
+            }
+            MouseEventKind::ScrollUp if self.profiles_table_area.contains(event.into()) => {
+                self.scrolling_state.prev(context.config.scrolloff, false);
+                context.render()?;
+            }
+            MouseEventKind::LeftClick => {}
+            MouseEventKind::DoubleClick => {}
+            MouseEventKind::ScrollDown => {}
+            MouseEventKind::ScrollUp => {}
+        }
+
+        Ok(())
+    }
+}
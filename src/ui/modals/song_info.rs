@@ -13,7 +13,7 @@ use super::{Modal, RectExt};
 use crate::{
     config::keys::CommonAction,
     context::AppContext,
-    mpd::commands::Song,
+    mpd::commands::{MetadataTag, Song},
     shared::{
         key_event::KeyEvent,
         macros::pop_modal,
@@ -103,14 +103,10 @@ impl Modal for SongInfoModal {
                 value_area.width,
             ));
         };
-        if let Some(title) = song.title() {
-            rows.extend(SongInfoModal::row("Title", tag_area.width, title, value_area.width));
-        }
-        if let Some(artist) = song.artist() {
-            rows.extend(SongInfoModal::row("Artist", tag_area.width, artist, value_area.width));
-        }
-        if let Some(album) = song.album() {
-            rows.extend(SongInfoModal::row("Album", tag_area.width, album, value_area.width));
+        for (tag, name) in [("title", "Title"), ("artist", "Artist"), ("album", "Album")] {
+            for value in song.metadata.get(tag).into_iter().flat_map(MetadataTag::iter) {
+                rows.extend(SongInfoModal::row(name, tag_area.width, value, value_area.width));
+            }
         }
         let duration = song.duration.as_ref().map(|d| d.as_secs().to_string()).unwrap_or_default();
         if !duration.is_empty() {
@@ -128,6 +124,7 @@ impl Modal for SongInfoModal {
                 .filter(|(key, _)| {
                     !["title", "album", "artist", "duration"].contains(&(*key).as_str())
                 })
+                .flat_map(|(k, v)| v.iter().map(move |v| (k, v)))
                 .flat_map(|(k, v)| SongInfoModal::row(k, tag_area.width, v, value_area.width)),
         );
 
@@ -0,0 +1,286 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Margin, Rect},
+    style::Style,
+    symbols::border,
+    widgets::{Block, Borders, Cell, Clear, Row, Table, TableState},
+};
+
+use super::{Modal, RectExt};
+use crate::{
+    MpdQueryResult,
+    config::keys::CommonAction,
+    context::AppContext,
+    mpd::{
+        commands::{Stats, TagCount},
+        errors::MpdError,
+        mpd_client::{MpdClient, Tag},
+    },
+    shared::{
+        ext::duration::DurationExt,
+        key_event::KeyEvent,
+        macros::pop_modal,
+        mouse_event::{MouseEvent, MouseEventKind},
+    },
+    ui::dirstack::DirState,
+};
+
+pub const REFRESH_STATS: &str = "refresh_stats";
+
+/// Tags the per-tag table can be grouped by, cycled with left and right
+pub const GROUP_TAGS: [&str; 5] = ["Genre", "Artist", "AlbumArtist", "Album", "Date"];
+
+/// Fetches the library statistics together with the song counts grouped by
+/// the given tag
+pub fn fetch(client: &mut impl MpdClient, group: &str) -> Result<MpdQueryResult, MpdError> {
+    Ok(MpdQueryResult::Stats {
+        stats: client.stats()?,
+        counts: client.count(&[], Some(Tag::Custom(group.to_owned())))?,
+    })
+}
+
+#[derive(Debug)]
+pub struct StatsModal {
+    scrolling_state: DirState<TableState>,
+    table_area: Rect,
+    stats: Stats,
+    counts: Vec<TagCount>,
+    group_idx: usize,
+}
+
+impl StatsModal {
+    pub fn new(stats: Stats, counts: Vec<TagCount>) -> Self {
+        let mut result = Self {
+            stats,
+            counts: Vec::new(),
+            group_idx: 0,
+            scrolling_state: DirState::default(),
+            table_area: Rect::default(),
+        };
+        result.set_counts(counts);
+
+        result
+    }
+
+    fn set_counts(&mut self, mut counts: Vec<TagCount>) {
+        counts.sort_by(|a, b| b.playtime.cmp(&a.playtime).then_with(|| a.value.cmp(&b.value)));
+        self.counts = counts;
+        self.scrolling_state.set_content_len(Some(self.counts.len()));
+        self.scrolling_state.first();
+    }
+
+    fn cycle_group(&mut self, forward: bool, context: &AppContext) {
+        self.group_idx = if forward {
+            (self.group_idx + 1) % GROUP_TAGS.len()
+        } else {
+            (self.group_idx + GROUP_TAGS.len() - 1) % GROUP_TAGS.len()
+        };
+
+        let group = GROUP_TAGS[self.group_idx];
+        context
+            .query()
+            .id(REFRESH_STATS)
+            .replace_id(REFRESH_STATS)
+            .query(move |client| Ok(fetch(client, group)?));
+    }
+
+    fn summary(&self) -> Vec<Row<'static>> {
+        let Stats { artists, albums, songs, uptime, playtime, db_playtime, db_update } = self.stats;
+        let last_update = chrono::DateTime::from_timestamp(db_update.try_into().unwrap_or(0), 0)
+            .map(|date| date.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+
+        [
+            ("Songs", songs.to_string()),
+            ("Artists", artists.to_string()),
+            ("Albums", albums.to_string()),
+            ("Library playtime", Duration::from_secs(db_playtime).to_human_string()),
+            ("Time played", Duration::from_secs(playtime).to_human_string()),
+            ("MPD uptime", Duration::from_secs(uptime).to_human_string()),
+            ("Last database update", last_update),
+        ]
+        .into_iter()
+        .map(|(label, value)| Row::new([Cell::from(label), Cell::from(value)]))
+        .collect()
+    }
+}
+
+impl Modal for StatsModal {
+    fn render(&mut self, frame: &mut Frame, app: &mut AppContext) -> Result<()> {
+        let popup_area = frame.area().centered(60, 80);
+        frame.render_widget(Clear, popup_area);
+        if let Some(bg_color) = app.config.theme.modal_background_color {
+            frame.render_widget(Block::default().style(Style::default().bg(bg_color)), popup_area);
+        }
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED)
+            .border_style(app.config.as_border_style())
+            .title_alignment(ratatui::prelude::Alignment::Center)
+            .title("Library statistics");
+
+        let margin = Margin { horizontal: 1, vertical: 0 };
+        let [summary_area, header_area, table_area] = Layout::vertical([
+            Constraint::Length(8),
+            Constraint::Length(2),
+            Constraint::Percentage(100),
+        ])
+        .areas(block.inner(popup_area));
+        let summary_area = summary_area.inner(margin);
+        let header_area = header_area.inner(margin);
+        let table_area = table_area.inner(margin);
+
+        let summary =
+            Table::new(self.summary(), [Constraint::Length(22), Constraint::Percentage(100)])
+                .style(app.config.as_text_style())
+                .block(
+                    Block::default()
+                        .borders(Borders::BOTTOM)
+                        .border_style(app.config.as_border_style()),
+                );
+
+        let widths = [Constraint::Percentage(100), Constraint::Length(8), Constraint::Length(12)];
+        let header_table = Table::new(
+            vec![Row::new([
+                Cell::from(format!("< {} >", GROUP_TAGS[self.group_idx])),
+                Cell::from("Songs"),
+                Cell::from("Playtime"),
+            ])],
+            widths,
+        )
+        .column_spacing(1)
+        .block(
+            Block::default().borders(Borders::BOTTOM).border_style(app.config.as_border_style()),
+        );
+
+        let rows = self.counts.iter().map(|count| {
+            Row::new([
+                Cell::from(if count.value.is_empty() {
+                    "(untagged)"
+                } else {
+                    count.value.as_str()
+                }),
+                Cell::from(count.songs.to_string()),
+                Cell::from(Duration::from_secs(count.playtime).to_human_string()),
+            ])
+        });
+        let table = Table::new(rows, widths)
+            .column_spacing(1)
+            .style(app.config.as_text_style())
+            .row_highlight_style(app.config.theme.current_item_style);
+
+        self.scrolling_state.set_viewport_len(Some(table_area.height.into()));
+        self.table_area = table_area;
+
+        frame.render_widget(block, popup_area);
+        frame.render_widget(summary, summary_area);
+        frame.render_widget(header_table, header_area);
+        frame.render_stateful_widget(table, table_area, self.scrolling_state.as_render_state_ref());
+        frame.render_stateful_widget(
+            app.config.as_styled_scrollbar(),
+            popup_area.inner(Margin { horizontal: 0, vertical: 1 }),
+            self.scrolling_state.as_scrollbar_state_ref(),
+        );
+
+        Ok(())
+    }
+
+    fn on_query_finished(
+        &mut self,
+        id: &'static str,
+        data: &mut MpdQueryResult,
+        context: &AppContext,
+    ) -> Result<()> {
+        match (id, data) {
+            (REFRESH_STATS, MpdQueryResult::Stats { stats, counts }) => {
+                self.stats = std::mem::take(stats);
+                self.set_counts(std::mem::take(counts));
+                context.render()?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_key(&mut self, key: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
+        if let Some(action) = key.as_common_action(context) {
+            match action {
+                CommonAction::DownHalf => {
+                    self.scrolling_state.next_half_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
+                CommonAction::UpHalf => {
+                    self.scrolling_state.prev_half_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
+                CommonAction::Up => {
+                    self.scrolling_state
+                        .prev(context.config.scrolloff, context.config.wrap_navigation);
+
+                    context.render()?;
+                }
+                CommonAction::Down => {
+                    self.scrolling_state
+                        .next(context.config.scrolloff, context.config.wrap_navigation);
+
+                    context.render()?;
+                }
+                CommonAction::Bottom => {
+                    self.scrolling_state.last();
+
+                    context.render()?;
+                }
+                CommonAction::Top => {
+                    self.scrolling_state.first();
+
+                    context.render()?;
+                }
+                CommonAction::Right => {
+                    self.cycle_group(true, context);
+                }
+                CommonAction::Left => {
+                    self.cycle_group(false, context);
+                }
+                CommonAction::Close => {
+                    pop_modal!(context);
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_mouse_event(&mut self, event: MouseEvent, context: &mut AppContext) -> Result<()> {
+        match event.kind {
+            MouseEventKind::LeftClick if self.table_area.contains(event.into()) => {
+                let y: usize = event.y.saturating_sub(self.table_area.y).into();
+                if let Some(idx) = self.scrolling_state.get_at_rendered_row(y) {
+                    self.scrolling_state.select(Some(idx), context.config.scrolloff);
+                    context.render()?;
+                }
+            }
+            MouseEventKind::ScrollDown if self.table_area.contains(event.into()) => {
+                self.scrolling_state.next(context.config.scrolloff, false);
+                context.render()?;
+            }
+            MouseEventKind::ScrollUp if self.table_area.contains(event.into()) => {
+                self.scrolling_state.prev(context.config.scrolloff, false);
+                context.render()?;
+            }
+            MouseEventKind::LeftClick
+            | MouseEventKind::DoubleClick
+            | MouseEventKind::MiddleClick
+            | MouseEventKind::RightClick
+            | MouseEventKind::ScrollDown
+            | MouseEventKind::ScrollUp => {}
+        }
+
+        Ok(())
+    }
+}
@@ -59,6 +59,7 @@ impl AlbumsPane {
 
         match self.stack.path() {
             [_album] => {
+                context.record_queue_edit();
                 self.add(current, context)?;
                 let queue_len = context.queue.len();
                 if autoplay {
@@ -145,6 +146,10 @@ impl Pane for AlbumsPane {
         self.handle_mouse_action(event, context)
     }
 
+    fn selected_songs(&self, context: &AppContext) -> Result<Vec<Song>> {
+        self.marked_or_selected_songs(context)
+    }
+
     fn handle_action(&mut self, event: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
         self.handle_filter_input(event, context)?;
         self.handle_common_action(event, context)?;
@@ -13,8 +13,11 @@ use crate::{
         mpd_client::{Filter, FilterKind, MpdClient, Tag},
     },
     shared::{
-        ext::mpd_client::MpdClientExt, key_event::KeyEvent, macros::status_info,
-        mouse_event::MouseEvent, mpd_query::PreviewGroup,
+        ext::mpd_client::MpdClientExt,
+        key_event::KeyEvent,
+        macros::status_info,
+        mouse_event::MouseEvent,
+        mpd_query::PreviewGroup,
     },
     ui::{
         UiEvent,
@@ -35,6 +38,7 @@ pub struct DirectoriesPane {
 const INIT: &str = "init";
 const OPEN_OR_PLAY: &str = "open_or_play";
 const PREVIEW: &str = "preview";
+const BROWSE: &str = "browse";
 
 impl DirectoriesPane {
     pub fn new(_context: &AppContext) -> Self {
@@ -46,6 +50,30 @@ impl DirectoriesPane {
         }
     }
 
+    /// Replaces the whole stack with the directories leading to the given path,
+    /// ie. a mount point, and selects each of them along the way
+    pub fn browse(&mut self, path: &str, context: &AppContext) {
+        let path = path.split('/').filter(|part| !part.is_empty()).map(str::to_owned).collect_vec();
+        self.initialized = true;
+
+        context.query().id(BROWSE).replace_id(BROWSE).target(PaneType::Directories).query(
+            move |client| {
+                let mut data = Vec::with_capacity(path.len() + 1);
+                for depth in 0..=path.len() {
+                    let dir = (depth > 0).then(|| path[..depth].join("/"));
+                    let items = client
+                        .lsinfo(dir.as_deref())?
+                        .into_iter()
+                        .filter_map(Into::<Option<DirOrSong>>::into)
+                        .sorted()
+                        .collect_vec();
+                    data.push(items);
+                }
+                Ok(MpdQueryResult::DirOrSongLevels { data, path })
+            },
+        );
+    }
+
     fn open_or_play(&mut self, autoplay: bool, context: &AppContext) -> Result<()> {
         let Some(selected) = self.stack.current().selected() else {
             log::error!("Failed to move deeper inside dir. Current value is None");
@@ -84,6 +112,7 @@ impl DirectoriesPane {
                 context.render()?;
             }
             t @ DirOrSong::Song(_) => {
+                context.record_queue_edit();
                 self.add(t, context)?;
                 let queue_len = context.queue.len();
                 if autoplay {
@@ -165,6 +194,10 @@ impl Pane for DirectoriesPane {
         self.handle_mouse_action(event, context)
     }
 
+    fn selected_songs(&self, context: &AppContext) -> Result<Vec<Song>> {
+        self.marked_or_selected_songs(context)
+    }
+
     fn handle_action(&mut self, event: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
         self.handle_filter_input(event, context)?;
         self.handle_common_action(event, context)?;
@@ -195,6 +228,21 @@ impl Pane for DirectoriesPane {
                 self.prepare_preview(context)?;
                 context.render()?;
             }
+            (BROWSE, MpdQueryResult::DirOrSongLevels { data, path }) => {
+                let mut levels = data.into_iter();
+                self.stack = DirStack::new(levels.next().unwrap_or_default());
+                for (name, level) in path.iter().zip(levels) {
+                    let Some(idx) =
+                        self.stack.current().items.iter().position(|item| item.as_path() == name)
+                    else {
+                        break;
+                    };
+                    self.stack.current_mut().select_idx(idx, context.config.scrolloff);
+                    self.stack.push(level);
+                }
+                self.prepare_preview(context)?;
+                context.render()?;
+            }
             (OPEN_OR_PLAY, MpdQueryResult::DirOrSong { data, origin_path }) => {
                 if let Some(origin_path) = origin_path {
                     if origin_path != self.stack().path() {
@@ -235,9 +283,10 @@ impl BrowserPane<DirOrSong> for DirectoriesPane {
     ) -> impl FnOnce(&mut Client<'_>) -> Result<Vec<Song>> + 'static {
         move |client| {
             Ok(match item {
+                // Trailing separator so that siblings sharing the prefix are not included
                 DirOrSong::Dir { full_path, .. } => client.find(&[Filter::new_with_kind(
                     Tag::File,
-                    &full_path,
+                    format!("{full_path}/"),
                     FilterKind::StartsWith,
                 )])?,
                 DirOrSong::Song(song) => vec![song.clone()],
@@ -245,6 +294,13 @@ impl BrowserPane<DirOrSong> for DirectoriesPane {
         }
     }
 
+    fn add_uri(&self, item: &DirOrSong) -> Option<String> {
+        Some(match item {
+            DirOrSong::Dir { full_path, .. } => full_path.clone(),
+            DirOrSong::Song(song) => song.file.clone(),
+        })
+    }
+
     fn add(&self, item: &DirOrSong, context: &AppContext) -> Result<()> {
         match item {
             DirOrSong::Dir { name: dirname, full_path: _ } => {
@@ -0,0 +1,232 @@
+use anyhow::Result;
+use ratatui::{Frame, prelude::Rect};
+
+use super::Pane;
+use crate::{
+    MpdQueryResult,
+    config::tabs::PaneType,
+    context::AppContext,
+    mpd::{
+        client::Client,
+        commands::Song,
+        mpd_client::{Filter, MpdClient, Tag},
+    },
+    shared::{
+        ext::mpd_client::MpdClientExt,
+        history::HistoryEntry,
+        key_event::KeyEvent,
+        macros::status_info,
+        mouse_event::MouseEvent,
+    },
+    ui::{UiEvent, browser::BrowserPane, dirstack::DirStack, widgets::browser::Browser},
+};
+
+#[derive(Debug)]
+pub struct HistoryPane {
+    stack: DirStack<HistoryEntry>,
+    filter_input_mode: bool,
+    browser: Browser<HistoryEntry>,
+}
+
+const PREVIEW: &str = "preview";
+
+impl HistoryPane {
+    pub fn new(_context: &AppContext) -> Self {
+        Self { stack: DirStack::default(), filter_input_mode: false, browser: Browser::new() }
+    }
+
+    /// Entries are only ever appended to the history or trimmed from its
+    /// start so the cursor is kept where it was unless the pane was empty.
+    fn refresh(&mut self, context: &AppContext) {
+        let entries = context.history.entries();
+        let current = self.stack.current_mut();
+        if current.items.as_slice() == entries {
+            return;
+        }
+
+        let was_empty = current.items.is_empty();
+        current.items = entries.to_vec();
+        current.state.set_content_len(Some(entries.len()));
+        let filter = current.filter().map(ToOwned::to_owned);
+        current.set_filter(filter, &context.config);
+        if was_empty {
+            current.last();
+        }
+    }
+
+    fn add_and_play(&self, item: &HistoryEntry, context: &AppContext) -> Result<()> {
+        context.record_queue_edit();
+        self.add(item, context)?;
+        let queue_len = context.queue.len();
+        context.command(move |client| Ok(client.play_last(queue_len)?));
+        Ok(())
+    }
+}
+
+impl Pane for HistoryPane {
+    fn render(&mut self, frame: &mut Frame, area: Rect, context: &AppContext) -> Result<()> {
+        self.browser.set_filter_input_active(self.filter_input_mode).render(
+            area,
+            frame.buffer_mut(),
+            &mut self.stack,
+            &context.config,
+        );
+
+        Ok(())
+    }
+
+    fn before_show(&mut self, context: &AppContext) -> Result<()> {
+        self.refresh(context);
+        self.prepare_preview(context)?;
+        Ok(())
+    }
+
+    fn on_event(
+        &mut self,
+        event: &mut UiEvent,
+        is_visible: bool,
+        context: &AppContext,
+    ) -> Result<()> {
+        if let UiEvent::SongChanged = event {
+            self.refresh(context);
+            if is_visible {
+                context.render()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_mouse_event(&mut self, event: MouseEvent, context: &AppContext) -> Result<()> {
+        self.handle_mouse_action(event, context)
+    }
+
+    fn selected_songs(&self, context: &AppContext) -> Result<Vec<Song>> {
+        self.marked_or_selected_songs(context)
+    }
+
+    fn handle_action(&mut self, event: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
+        self.handle_filter_input(event, context)?;
+        self.handle_common_action(event, context)?;
+        self.handle_global_action(event, context)?;
+        Ok(())
+    }
+
+    fn on_query_finished(
+        &mut self,
+        id: &'static str,
+        data: MpdQueryResult,
+        _is_visible: bool,
+        context: &AppContext,
+    ) -> Result<()> {
+        match (id, data) {
+            (PREVIEW, MpdQueryResult::Preview { data, origin_path: _ }) => {
+                self.stack_mut().set_preview(data);
+                context.render()?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl BrowserPane<HistoryEntry> for HistoryPane {
+    fn stack(&self) -> &DirStack<HistoryEntry> {
+        &self.stack
+    }
+
+    fn stack_mut(&mut self) -> &mut DirStack<HistoryEntry> {
+        &mut self.stack
+    }
+
+    fn set_filter_input_mode_active(&mut self, active: bool) {
+        self.filter_input_mode = active;
+    }
+
+    fn is_filter_input_mode_active(&self) -> bool {
+        self.filter_input_mode
+    }
+
+    fn list_songs_in_item(
+        &self,
+        item: HistoryEntry,
+    ) -> impl FnOnce(&mut Client<'_>) -> Result<Vec<Song>> + 'static {
+        move |client| {
+            Ok(client.find_one(&[Filter::new(Tag::File, &item.file)])?.into_iter().collect())
+        }
+    }
+
+    fn add(&self, item: &HistoryEntry, context: &AppContext) -> Result<()> {
+        let file = item.file.clone();
+        context.command(move |client| {
+            client.add(&file)?;
+            status_info!("'{file}' added to queue");
+            Ok(())
+        });
+
+        context.render()?;
+
+        Ok(())
+    }
+
+    fn add_next(&self, item: &HistoryEntry, context: &AppContext) -> Result<()> {
+        let file = item.file.clone();
+        context.command(move |client| {
+            client.add_relative_index(&file, 0)?;
+            status_info!("'{file}' added to queue next");
+            Ok(())
+        });
+
+        context.render()?;
+
+        Ok(())
+    }
+
+    fn add_all(&self, context: &AppContext) -> Result<()> {
+        let files: Vec<_> =
+            self.stack().current().items.iter().map(|entry| entry.file.clone()).collect();
+        context.command(move |client| {
+            let uris: Vec<_> = files.iter().map(String::as_str).collect();
+            let added = client.add_files(&uris)?;
+            status_info!("{added} songs from history added to queue");
+            Ok(())
+        });
+
+        Ok(())
+    }
+
+    fn open(&mut self, context: &AppContext) -> Result<()> {
+        if let Some(item) = self.stack().current().selected() {
+            self.add_and_play(item, context)?;
+        }
+        Ok(())
+    }
+
+    fn next(&mut self, context: &AppContext) -> Result<()> {
+        if let Some(item) = self.stack().current().selected() {
+            self.add(item, context)?;
+        }
+        Ok(())
+    }
+
+    fn prepare_preview(&mut self, context: &AppContext) -> Result<()> {
+        let Some(file) = self.stack().current().selected().map(|entry| entry.file.clone()) else {
+            return Ok(());
+        };
+        self.stack_mut().clear_preview();
+        context.query().id(PREVIEW).replace_id("history_preview").target(PaneType::History).query(
+            move |client| {
+                Ok(MpdQueryResult::Preview {
+                    data: client
+                        .find_one(&[Filter::new(Tag::File, &file)])?
+                        .map(|song| song.to_preview()),
+                    origin_path: None,
+                })
+            },
+        );
+        Ok(())
+    }
+
+    fn browser_areas(&self) -> [Rect; 3] {
+        self.browser.areas
+    }
+}
@@ -0,0 +1,247 @@
+use std::collections::VecDeque;
+
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use itertools::Itertools;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    prelude::Rect,
+    text::{Line, Span},
+    widgets::{List, ListState, Paragraph},
+};
+
+use super::Pane;
+use crate::{
+    MpdQueryResult,
+    config::{keys::CommonAction, tabs::PaneType},
+    context::AppContext,
+    mpd::{commands::Message, mpd_client::MpdClient},
+    shared::{
+        key_event::KeyEvent,
+        mouse_event::{MouseEvent, MouseEventKind},
+    },
+    ui::{UiEvent, dirstack::DirState},
+};
+
+/// Only this many most recent messages are kept
+const MAX_MESSAGES: usize = 1000;
+const CHANNELS: &str = "channels";
+
+#[derive(Debug)]
+struct ReceivedMessage {
+    received_at: DateTime<Local>,
+    message: Message,
+}
+
+#[derive(Debug)]
+pub struct MessagesPane {
+    messages: VecDeque<ReceivedMessage>,
+    channels: Vec<String>,
+    scrolling_state: DirState<ListState>,
+    messages_area: Rect,
+    should_scroll_to_last: bool,
+}
+
+impl MessagesPane {
+    pub fn new() -> Self {
+        Self {
+            messages: VecDeque::new(),
+            channels: Vec::new(),
+            scrolling_state: DirState::default(),
+            messages_area: Rect::default(),
+            should_scroll_to_last: false,
+        }
+    }
+
+    fn fetch_channels(context: &AppContext) {
+        context
+            .query()
+            .id(CHANNELS)
+            .replace_id(CHANNELS)
+            .target(PaneType::Messages)
+            .query(move |client| Ok(MpdQueryResult::Channels(client.channels()?.0)));
+    }
+}
+
+impl Pane for MessagesPane {
+    fn render(&mut self, frame: &mut Frame, area: Rect, context: &AppContext) -> Result<()> {
+        let config = &context.config;
+        let [channels_area, area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+
+        let channels = if self.channels.is_empty() {
+            "No channels".to_owned()
+        } else {
+            self.channels.join(", ")
+        };
+        let header = Line::from(vec![
+            Span::styled("Channels: ", config.theme.borders_style),
+            Span::raw(channels),
+        ]);
+        frame.render_widget(Paragraph::new(header).style(config.as_text_style()), channels_area);
+
+        let lines = self
+            .messages
+            .iter()
+            .map(|ReceivedMessage { received_at, message }| {
+                Line::from(vec![
+                    Span::styled(
+                        received_at.format("%H:%M:%S ").to_string(),
+                        config.theme.borders_style,
+                    ),
+                    Span::styled(
+                        format!("[{}] ", message.channel),
+                        config.theme.highlighted_item_style,
+                    ),
+                    Span::raw(message.message.clone()),
+                ])
+            })
+            .collect_vec();
+
+        self.scrolling_state.set_content_len(Some(lines.len()));
+        self.scrolling_state.set_viewport_len(Some(area.height.into()));
+        if self.scrolling_state.get_selected().is_none() || self.should_scroll_to_last {
+            self.should_scroll_to_last = false;
+            self.scrolling_state.last();
+        }
+
+        let messages_wg = List::new(lines)
+            .style(config.as_text_style())
+            .highlight_style(config.theme.current_item_style);
+        frame.render_stateful_widget(
+            config.as_styled_scrollbar(),
+            area,
+            self.scrolling_state.as_scrollbar_state_ref(),
+        );
+        let mut area = area;
+        area.width = area.width.saturating_sub(1);
+        frame.render_stateful_widget(messages_wg, area, self.scrolling_state.as_render_state_ref());
+        self.messages_area = area;
+
+        Ok(())
+    }
+
+    fn before_show(&mut self, context: &AppContext) -> Result<()> {
+        self.scrolling_state.last();
+        Self::fetch_channels(context);
+        Ok(())
+    }
+
+    fn on_event(
+        &mut self,
+        event: &mut UiEvent,
+        is_visible: bool,
+        context: &AppContext,
+    ) -> Result<()> {
+        match event {
+            UiEvent::Messages(messages) => {
+                let received_at = Local::now();
+                self.messages.extend(
+                    messages
+                        .iter()
+                        .cloned()
+                        .map(|message| ReceivedMessage { received_at, message }),
+                );
+                if self.messages.len() > MAX_MESSAGES {
+                    self.messages.drain(..self.messages.len() - MAX_MESSAGES);
+                }
+                self.should_scroll_to_last = true;
+                if is_visible {
+                    context.render()?;
+                }
+            }
+            UiEvent::Subscription | UiEvent::Reconnected if is_visible => {
+                Self::fetch_channels(context);
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn handle_mouse_event(&mut self, event: MouseEvent, context: &AppContext) -> Result<()> {
+        if !self.messages_area.contains(event.into()) {
+            return Ok(());
+        }
+
+        match event.kind {
+            MouseEventKind::ScrollUp => {
+                self.scrolling_state.prev(context.config.scrolloff, false);
+
+                context.render()?;
+            }
+            MouseEventKind::ScrollDown => {
+                self.scrolling_state.next(context.config.scrolloff, false);
+
+                context.render()?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn handle_action(&mut self, event: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
+        let config = &context.config;
+        if let Some(action) = event.as_common_action(context) {
+            match action {
+                CommonAction::DownHalf => {
+                    self.scrolling_state.next_half_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
+                CommonAction::UpHalf => {
+                    self.scrolling_state.prev_half_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
+                CommonAction::Up => {
+                    self.scrolling_state.prev(context.config.scrolloff, config.wrap_navigation);
+
+                    context.render()?;
+                }
+                CommonAction::Down => {
+                    self.scrolling_state.next(context.config.scrolloff, config.wrap_navigation);
+
+                    context.render()?;
+                }
+                CommonAction::Bottom => {
+                    self.scrolling_state.last();
+
+                    context.render()?;
+                }
+                CommonAction::Top => {
+                    self.scrolling_state.first();
+
+                    context.render()?;
+                }
+                CommonAction::Delete => {
+                    self.messages.clear();
+                    self.scrolling_state.select(None, 0);
+
+                    context.render()?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn on_query_finished(
+        &mut self,
+        id: &'static str,
+        data: MpdQueryResult,
+        is_visible: bool,
+        context: &AppContext,
+    ) -> Result<()> {
+        if let (CHANNELS, MpdQueryResult::Channels(channels)) = (id, data) {
+            self.channels = channels;
+            if is_visible {
+                context.render()?;
+            }
+        }
+        Ok(())
+    }
+}
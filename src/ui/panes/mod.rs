@@ -1,4 +1,4 @@
-use std::{borrow::Cow, collections::HashMap};
+use std::{borrow::Cow, collections::HashMap, fmt::Write};
 
 use album_art::AlbumArtPane;
 use albums::AlbumsPane;
@@ -6,7 +6,9 @@ use anyhow::{Context, Result};
 use directories::DirectoriesPane;
 use either::Either;
 use header::HeaderPane;
+use history::HistoryPane;
 use lyrics::LyricsPane;
+use messages::MessagesPane;
 use playlists::PlaylistsPane;
 use progress_bar::ProgressBarPane;
 use property::PropertyPane;
@@ -30,6 +32,7 @@ use crate::{
     MpdQueryResult,
     config::{
         keys::CommonAction,
+        rating::RatingChange,
         tabs::{Pane as ConfigPane, PaneType, SizedPaneOrSplit},
         theme::{
             SymbolsConfig,
@@ -45,10 +48,23 @@ use crate::{
     },
     context::AppContext,
     mpd::{
-        commands::{Song, State, Status, status::OnOffOneshot, volume::Bound},
+        commands::{
+            ConnectionState,
+            Song,
+            State,
+            Status,
+            status::OnOffOneshot,
+            stickers::{LAST_PLAYED, PLAY_COUNT, SKIP_COUNT},
+            volume::Bound,
+        },
         mpd_client::Tag,
     },
-    shared::{ext::duration::DurationExt, key_event::KeyEvent, mouse_event::MouseEvent},
+    shared::{
+        ext::{duration::DurationExt, mpd_client::MpdClientExt},
+        key_event::KeyEvent,
+        macros::status_info,
+        mouse_event::MouseEvent,
+    },
 };
 
 pub mod album_art;
@@ -57,9 +73,11 @@ pub mod directories;
 #[cfg(debug_assertions)]
 pub mod frame_count;
 pub mod header;
+pub mod history;
 #[cfg(debug_assertions)]
 pub mod logs;
 pub mod lyrics;
+pub mod messages;
 pub mod playlists;
 pub mod progress_bar;
 pub mod property;
@@ -79,6 +97,8 @@ pub enum Panes<'pane_ref, 'pane> {
     Albums(&'pane_ref mut AlbumsPane),
     Playlists(&'pane_ref mut PlaylistsPane),
     Search(&'pane_ref mut SearchPane),
+    History(&'pane_ref mut HistoryPane),
+    Messages(&'pane_ref mut MessagesPane),
     AlbumArt(&'pane_ref mut AlbumArtPane),
     Lyrics(&'pane_ref mut LyricsPane),
     ProgressBar(&'pane_ref mut ProgressBarPane),
@@ -106,6 +126,8 @@ pub struct PaneContainer<'panes> {
     pub album_artists: TagBrowserPane,
     pub playlists: PlaylistsPane,
     pub search: SearchPane,
+    pub history: HistoryPane,
+    pub messages: MessagesPane,
     pub album_art: AlbumArtPane,
     pub lyrics: LyricsPane,
     pub progress_bar: ProgressBarPane,
@@ -133,6 +155,8 @@ impl<'panes> PaneContainer<'panes> {
             ),
             playlists: PlaylistsPane::new(context),
             search: SearchPane::new(context),
+            history: HistoryPane::new(context),
+            messages: MessagesPane::new(),
             album_art: AlbumArtPane::new(context),
             lyrics: LyricsPane::new(context),
             progress_bar: ProgressBarPane::new(),
@@ -178,6 +202,8 @@ impl<'panes> PaneContainer<'panes> {
             PaneType::Albums => Ok(Panes::Albums(&mut self.albums)),
             PaneType::Playlists => Ok(Panes::Playlists(&mut self.playlists)),
             PaneType::Search => Ok(Panes::Search(&mut self.search)),
+            PaneType::History => Ok(Panes::History(&mut self.history)),
+            PaneType::Messages => Ok(Panes::Messages(&mut self.messages)),
             PaneType::AlbumArt => Ok(Panes::AlbumArt(&mut self.album_art)),
             PaneType::Lyrics => Ok(Panes::Lyrics(&mut self.lyrics)),
             PaneType::ProgressBar => Ok(Panes::ProgressBar(&mut self.progress_bar)),
@@ -215,12 +241,14 @@ macro_rules! pane_call {
             Panes::Albums(ref mut s) => s.$fn($($param),+),
             Panes::Playlists(ref mut s) => s.$fn($($param),+),
             Panes::Search(ref mut s) => s.$fn($($param),+),
+            Panes::History(ref mut s) => s.$fn($($param),+),
+            Panes::Messages(ref mut s) => s.$fn($($param),+),
             Panes::AlbumArt(ref mut s) => s.$fn($($param),+),
             Panes::Lyrics(ref mut s) => s.$fn($($param),+),
             Panes::ProgressBar(ref mut s) => s.$fn($($param),+),
             Panes::Header(ref mut s) => s.$fn($($param),+),
             Panes::Tabs(ref mut s) => s.$fn($($param),+),
-            Panes::TabContent => Ok(()),
+            Panes::TabContent => Ok(Default::default()),
             #[cfg(debug_assertions)]
             Panes::FrameCount(ref mut s) => s.$fn($($param),+),
             Panes::Property(ref mut s) => s.$fn($($param),+),
@@ -256,6 +284,11 @@ pub(crate) trait Pane {
 
     fn handle_action(&mut self, event: &mut KeyEvent, context: &mut AppContext) -> Result<()>;
 
+    /// Songs marked in the pane or the song under cursor if nothing is marked
+    fn selected_songs(&self, context: &AppContext) -> Result<Vec<Song>> {
+        Ok(Vec::new())
+    }
+
     fn handle_mouse_event(&mut self, event: MouseEvent, context: &AppContext) -> Result<()> {
         Ok(())
     }
@@ -279,6 +312,37 @@ pub(crate) trait Pane {
     }
 }
 
+/// Writes rating or like stickers of the given songs. Does nothing if the
+/// action is not one of the rating actions.
+pub(crate) fn rate_songs(action: CommonAction, songs: Vec<Song>, context: &AppContext) {
+    let change = match action {
+        CommonAction::Rate(value) => Some(RatingChange::Set(value)),
+        CommonAction::RateUp => Some(RatingChange::Increase),
+        CommonAction::RateDown => Some(RatingChange::Decrease),
+        CommonAction::ToggleLike => None,
+        _ => return,
+    };
+    if songs.is_empty() {
+        return;
+    }
+
+    let rating = context.config.rating.clone();
+    context.command(move |client| {
+        for song in &songs {
+            let name = song.title().unwrap_or(&song.file);
+            if let Some(change) = change {
+                let value = client.rate(&song.file, change, &rating)?;
+                status_info!("'{name}' rated {value}/{}", rating.max);
+            } else if client.toggle_like(&song.file, &rating.like_sticker)? {
+                status_info!("'{name}' liked");
+            } else {
+                status_info!("'{name}' unliked");
+            }
+        }
+        Ok(())
+    });
+}
+
 pub mod dirstack {}
 
 pub(crate) mod browser {
@@ -378,6 +442,7 @@ pub(crate) mod browser {
                     !["title", "album", "artist", "duration"].contains(&(*key).as_str())
                 })
                 .sorted_by_key(|(key, _)| *key)
+                .flat_map(|(k, v)| v.iter().map(move |v| (k, v)))
             {
                 tags_group.push(
                     Line::from(vec![
@@ -432,8 +497,8 @@ pub(crate) mod browser {
 
     impl std::cmp::Ord for Song {
         fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-            let a_track = self.metadata.get("track").map(|v| v.parse::<u32>());
-            let b_track = other.metadata.get("track").map(|v| v.parse::<u32>());
+            let a_track = self.tag("track").map(|v| v.parse::<u32>());
+            let b_track = other.tag("track").map(|v| v.parse::<u32>());
             match (a_track, b_track) {
                 (Some(Ok(a)), Some(Ok(b))) => a.cmp(&b),
                 (_, Some(Ok(_))) => Ordering::Greater,
@@ -469,13 +534,11 @@ pub(crate) mod browser {
         use crate::mpd::commands::Song;
 
         fn song(title: &str, track: Option<&str>) -> Song {
-            Song {
-                metadata: HashMap::from([
-                    ("title".to_owned(), title.to_owned()),
-                    track.map(|v| ("track".to_owned(), v.to_owned())).into_iter().collect(),
-                ]),
-                ..Default::default()
+            let mut metadata = HashMap::from([("title".to_owned(), title.to_owned().into())]);
+            if let Some(track) = track {
+                metadata.insert("track".to_owned(), track.to_owned().into());
             }
+            Song { metadata, ..Default::default() }
         }
 
         #[test]
@@ -581,18 +644,64 @@ impl Song {
         std::path::Path::new(&self.file).file_name().map(|file_name| file_name.to_string_lossy())
     }
 
-    fn format<'song>(&'song self, property: &SongProperty) -> Option<Cow<'song, str>> {
+    fn sticker(&self, name: &str) -> Option<Cow<'_, str>> {
+        self.stickers
+            .as_ref()
+            .and_then(|stickers| stickers.get(name))
+            .map(|value| Cow::Borrowed(value.as_str()))
+    }
+
+    fn format<'song>(
+        &'song self,
+        property: &SongProperty,
+        tag_separator: &str,
+    ) -> Option<Cow<'song, str>> {
         match property {
             SongProperty::Filename => self.file_name(),
             SongProperty::File => Some(Cow::Borrowed(self.file.as_str())),
-            SongProperty::Title => self.title().map(|v| Cow::Borrowed(v.as_ref())),
-            SongProperty::Artist => self.artist().map(|v| Cow::Borrowed(v.as_ref())),
-            SongProperty::Album => self.album().map(|v| Cow::Borrowed(v.as_ref())),
-            SongProperty::Track => self.metadata.get("track").map(|v| {
+            SongProperty::Title => self.metadata.get("title").map(|v| v.join(tag_separator)),
+            SongProperty::Artist => self.metadata.get("artist").map(|v| v.join(tag_separator)),
+            SongProperty::Album => self.metadata.get("album").map(|v| v.join(tag_separator)),
+            SongProperty::Track => self.tag("track").map(|v| {
                 Cow::Owned(v.parse::<u32>().map_or_else(|_| v.clone(), |v| format!("{v:0>2}")))
             }),
             SongProperty::Duration => self.duration.map(|d| Cow::Owned(d.to_string())),
-            SongProperty::Other(name) => self.metadata.get(name).map(|v| Cow::Borrowed(v.as_str())),
+            SongProperty::Priority => {
+                (self.priority > 0).then(|| Cow::Owned(self.priority.to_string()))
+            }
+            SongProperty::Other(name) => self.metadata.get(name).map(|v| v.join(tag_separator)),
+            SongProperty::Rating { sticker, max, full, half, empty } => self
+                .stickers
+                .as_ref()
+                .and_then(|stickers| stickers.get(sticker))
+                .and_then(|value| value.parse::<u8>().ok())
+                .map(|value| {
+                    let value = value.min(*max);
+                    let stars = max.div_ceil(2);
+                    let full_count = value / 2;
+                    let half_count = value % 2;
+                    Cow::Owned(format!(
+                        "{}{}{}",
+                        full.repeat(full_count.into()),
+                        half.repeat(half_count.into()),
+                        empty.repeat((stars - full_count - half_count).into())
+                    ))
+                }),
+            SongProperty::PlayCount => self.sticker(PLAY_COUNT),
+            SongProperty::SkipCount => self.sticker(SKIP_COUNT),
+            SongProperty::LastPlayed { format } => self
+                .stickers
+                .as_ref()
+                .and_then(|stickers| stickers.get(LAST_PLAYED))
+                .and_then(|value| value.parse::<i64>().ok())
+                .and_then(|timestamp| chrono::DateTime::from_timestamp(timestamp, 0))
+                .and_then(|time| {
+                    // Invalid format string makes the formatting fail instead of
+                    // panicking in to_string
+                    let mut result = String::new();
+                    write!(result, "{}", time.with_timezone(&chrono::Local).format(format)).ok()?;
+                    Some(Cow::Owned(result))
+                }),
         }
     }
 
@@ -600,6 +709,7 @@ impl Song {
         &self,
         formats: impl IntoIterator<Item = &'a Property<SongProperty>>,
         filter: &str,
+        tag_separator: &str,
     ) -> bool {
         for format in formats {
             let match_found = match &format.kind {
@@ -615,22 +725,22 @@ impl Song {
                             .map(|value| value.to_lowercase().contains(&filter.to_lowercase()))
                     })
                     .or_else(|| {
-                        format
-                            .default
-                            .as_ref()
-                            .map(|f| self.matches(std::iter::once(f.as_ref()), filter))
+                        format.default.as_ref().map(|f| {
+                            self.matches(std::iter::once(f.as_ref()), filter, tag_separator)
+                        })
                     }),
-                PropertyKindOrText::Property(property) => self.format(property).map_or_else(
-                    || {
-                        format
-                            .default
-                            .as_ref()
-                            .map(|f| self.matches(std::iter::once(f.as_ref()), filter))
-                    },
-                    |p| Some(p.to_lowercase().contains(&filter.to_lowercase())),
-                ),
+                PropertyKindOrText::Property(property) => {
+                    self.format(property, tag_separator).map_or_else(
+                        || {
+                            format.default.as_ref().map(|f| {
+                                self.matches(std::iter::once(f.as_ref()), filter, tag_separator)
+                            })
+                        },
+                        |p| Some(p.to_lowercase().contains(&filter.to_lowercase())),
+                    )
+                }
                 PropertyKindOrText::Group(_) => format
-                    .as_string(Some(self))
+                    .as_string(Some(self), tag_separator)
                     .map(|v| v.to_lowercase().contains(&filter.to_lowercase())),
             };
             if match_found.is_some_and(|v| v) {
@@ -645,8 +755,12 @@ impl Song {
         format: &Property<SongProperty>,
         max_len: usize,
         symbols: &SymbolsConfig,
+        tag_separator: &str,
     ) -> Option<Line<'song>> {
-        format.default.as_ref().and_then(|f| self.as_line_ellipsized(f.as_ref(), max_len, symbols))
+        format
+            .default
+            .as_ref()
+            .and_then(|f| self.as_line_ellipsized(f.as_ref(), max_len, symbols, tag_separator))
     }
 
     pub fn as_line_ellipsized<'song>(
@@ -654,6 +768,7 @@ impl Song {
         format: &Property<SongProperty>,
         max_len: usize,
         symbols: &SymbolsConfig,
+        tag_separator: &str,
     ) -> Option<Line<'song>> {
         let style = format.style.unwrap_or_default();
         match &format.kind {
@@ -667,25 +782,28 @@ impl Song {
                 .map(|sticker| Line::styled(sticker.ellipsize(max_len, symbols), style))
                 .or_else(|| {
                     format.default.as_ref().and_then(|format| {
-                        self.as_line_ellipsized(format.as_ref(), max_len, symbols)
+                        self.as_line_ellipsized(format.as_ref(), max_len, symbols, tag_separator)
                     })
                 }),
-            PropertyKindOrText::Property(property) => self.format(property).map_or_else(
-                || self.default_as_line_ellipsized(format, max_len, symbols),
-                |v| Some(Line::styled(v.ellipsize(max_len, symbols).into_owned(), style)),
-            ),
+            PropertyKindOrText::Property(property) => {
+                self.format(property, tag_separator).map_or_else(
+                    || self.default_as_line_ellipsized(format, max_len, symbols, tag_separator),
+                    |v| Some(Line::styled(v.ellipsize(max_len, symbols).into_owned(), style)),
+                )
+            }
             PropertyKindOrText::Group(group) => {
                 let mut buf = Line::default();
                 for grformat in group {
-                    if let Some(res) = self.as_line_ellipsized(grformat, max_len, symbols) {
+                    if let Some(res) =
+                        self.as_line_ellipsized(grformat, max_len, symbols, tag_separator)
+                    {
                         for span in res.spans {
                             buf.push_span(span);
                         }
                     } else {
-                        return format
-                            .default
-                            .as_ref()
-                            .and_then(|format| self.as_line_ellipsized(format, max_len, symbols));
+                        return format.default.as_ref().and_then(|format| {
+                            self.as_line_ellipsized(format, max_len, symbols, tag_separator)
+                        });
                     }
                 }
                 return Some(buf);
@@ -695,11 +813,11 @@ impl Song {
 }
 
 impl Property<SongProperty> {
-    fn default(&self, song: Option<&Song>) -> Option<String> {
-        self.default.as_ref().and_then(|p| p.as_string(song))
+    fn default(&self, song: Option<&Song>, tag_separator: &str) -> Option<String> {
+        self.default.as_ref().and_then(|p| p.as_string(song, tag_separator))
     }
 
-    pub fn as_string(&self, song: Option<&Song>) -> Option<String> {
+    pub fn as_string(&self, song: Option<&Song>, tag_separator: &str) -> Option<String> {
         match &self.kind {
             PropertyKindOrText::Text(value) => Some((*value).to_string()),
             PropertyKindOrText::Sticker(key) => {
@@ -708,24 +826,29 @@ impl Property<SongProperty> {
                 {
                     sticker.cloned()
                 } else {
-                    self.default(song)
+                    self.default(song, tag_separator)
                 }
             }
             PropertyKindOrText::Property(property) => {
                 if let Some(song) = song {
-                    song.format(property)
-                        .map_or_else(|| self.default(Some(song)), |v| Some(v.into_owned()))
+                    song.format(property, tag_separator).map_or_else(
+                        || self.default(Some(song), tag_separator),
+                        |v| Some(v.into_owned()),
+                    )
                 } else {
-                    self.default(song)
+                    self.default(song, tag_separator)
                 }
             }
             PropertyKindOrText::Group(group) => {
                 let mut buf = String::new();
                 for format in group {
-                    if let Some(res) = format.as_string(song) {
+                    if let Some(res) = format.as_string(song, tag_separator) {
                         buf.push_str(&res);
                     } else {
-                        return self.default.as_ref().and_then(|d| d.as_string(song));
+                        return self
+                            .default
+                            .as_ref()
+                            .and_then(|d| d.as_string(song, tag_separator));
                     }
                 }
                 return Some(buf);
@@ -739,14 +862,16 @@ impl Property<PropertyKind> {
         &'s self,
         song: Option<&'song Song>,
         status: &'song Status,
+        tag_separator: &str,
     ) -> Option<Either<Span<'s>, Vec<Span<'s>>>> {
-        self.default.as_ref().and_then(|p| p.as_span(song, status))
+        self.default.as_ref().and_then(|p| p.as_span(song, status, tag_separator))
     }
 
     pub fn as_span<'song: 's, 's>(
         &'s self,
         song: Option<&'song Song>,
         status: &'song Status,
+        tag_separator: &str,
     ) -> Option<Either<Span<'s>, Vec<Span<'s>>>> {
         let style = self.style.unwrap_or_default();
         match &self.kind {
@@ -757,17 +882,17 @@ impl Property<PropertyKind> {
                 {
                     Some(Either::Left(Span::styled(sticker, style)))
                 } else {
-                    self.default_as_span(song, status)
+                    self.default_as_span(song, status, tag_separator)
                 }
             }
             PropertyKindOrText::Property(PropertyKind::Song(property)) => {
                 if let Some(song) = song {
-                    song.format(property).map_or_else(
-                        || self.default_as_span(Some(song), status),
+                    song.format(property, tag_separator).map_or_else(
+                        || self.default_as_span(Some(song), status, tag_separator),
                         |s| Some(Either::Left(Span::styled(s, style))),
                     )
                 } else {
-                    self.default_as_span(song, status)
+                    self.default_as_span(song, status, tag_separator)
                 }
             }
             PropertyKindOrText::Property(PropertyKind::Status(s)) => match s {
@@ -853,13 +978,30 @@ impl Property<PropertyKind> {
                     .unwrap_or(style),
                 ))),
                 StatusProperty::Bitrate => status.bitrate.as_ref().map_or_else(
-                    || self.default_as_span(song, status),
+                    || self.default_as_span(song, status, tag_separator),
                     |v| Some(Either::Left(Span::styled(v.to_string(), style))),
                 ),
                 StatusProperty::Crossfade => status.xfade.as_ref().map_or_else(
-                    || self.default_as_span(song, status),
+                    || self.default_as_span(song, status, tag_separator),
                     |v| Some(Either::Left(Span::styled(v.to_string(), style))),
                 ),
+                StatusProperty::ReplayGain => status.replay_gain.as_ref().map_or_else(
+                    || self.default_as_span(song, status, tag_separator),
+                    |v| Some(Either::Left(Span::styled(v.to_string(), style))),
+                ),
+                StatusProperty::Connection {
+                    connected_label,
+                    reconnecting_label,
+                    connected_style,
+                    reconnecting_style,
+                } => Some(Either::Left(match status.connection {
+                    ConnectionState::Connected => {
+                        Span::styled(connected_label, connected_style.unwrap_or(style))
+                    }
+                    ConnectionState::Reconnecting { .. } => {
+                        Span::styled(reconnecting_label, reconnecting_style.unwrap_or(style))
+                    }
+                })),
             },
             PropertyKindOrText::Property(PropertyKind::Widget(w)) => match w {
                 WidgetProperty::Volume => {
@@ -889,7 +1031,7 @@ impl Property<PropertyKind> {
             PropertyKindOrText::Group(group) => {
                 let mut buf = Vec::new();
                 for format in group {
-                    match format.as_span(song, status) {
+                    match format.as_span(song, status, tag_separator) {
                         Some(Either::Left(span)) => buf.push(span),
                         Some(Either::Right(spans)) => buf.extend(spans),
                         None => return None,
@@ -1030,7 +1172,15 @@ mod format_tests {
                 StyleFile,
                 properties::{PropertyKind, StatusProperty, StatusPropertyFile},
             },
-            mpd::commands::{State, Status, Volume, status::OnOffOneshot},
+            mpd::commands::{
+                ConnectionState,
+                MetadataTag,
+                State,
+                Status,
+                Volume,
+                replay_gain::ReplayGainMode,
+                status::OnOffOneshot,
+            },
         };
 
         #[test_case(SongProperty::Title, "title")]
@@ -1039,6 +1189,7 @@ mod format_tests {
         #[test_case(SongProperty::Track, "123")]
         #[test_case(SongProperty::Duration, "2:03")]
         #[test_case(SongProperty::Other("track".to_string()), "123")]
+        #[test_case(SongProperty::Other("genre".to_string()), "rock | jazz")]
         fn song_property_resolves_correctly(prop: SongProperty, expected: &str) {
             let format = Property::<SongProperty> {
                 kind: PropertyKindOrText::Property(prop),
@@ -1051,24 +1202,104 @@ mod format_tests {
                 file: "file".to_owned(),
                 duration: Some(Duration::from_secs(123)),
                 metadata: HashMap::from([
-                    ("title".to_string(), "title".to_owned()),
-                    ("album".to_string(), "album".to_owned()),
-                    ("track".to_string(), "123".to_string()),
-                    ("artist".to_string(), "artist".to_string()),
+                    ("title".to_string(), "title".to_owned().into()),
+                    ("album".to_string(), "album".to_owned().into()),
+                    ("track".to_string(), "123".to_string().into()),
+                    ("artist".to_string(), "artist".to_string().into()),
+                    (
+                        "genre".to_string(),
+                        MetadataTag::Multiple(vec!["rock".to_string(), "jazz".to_string()]),
+                    ),
                 ]),
                 stickers: None,
+                priority: 0,
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), " | ");
 
             assert_eq!(result, Some(expected.to_string()));
         }
 
+        #[test_case(Some("10"), 10, Some("★★★★★"))]
+        #[test_case(Some("7"), 10, Some("★★★⯪☆"))]
+        #[test_case(Some("0"), 10, Some("☆☆☆☆☆"))]
+        #[test_case(Some("15"), 10, Some("★★★★★"))]
+        #[test_case(Some("3"), 5, Some("★⯪☆"))]
+        #[test_case(Some("nan"), 10, None)]
+        #[test_case(None, 10, None)]
+        fn rating_renders_stars(sticker: Option<&str>, max: u8, expected: Option<&str>) {
+            let format = Property::<SongProperty> {
+                kind: PropertyKindOrText::Property(SongProperty::Rating {
+                    sticker: "rating".to_owned(),
+                    max,
+                    full: "★".to_owned(),
+                    half: "⯪".to_owned(),
+                    empty: "☆".to_owned(),
+                }),
+                style: None,
+                default: None,
+            };
+
+            let song = Song {
+                stickers: sticker
+                    .map(|value| HashMap::from([("rating".to_owned(), value.to_owned())])),
+                ..Default::default()
+            };
+
+            let result = format.as_string(Some(&song), " | ");
+
+            assert_eq!(result.as_deref(), expected);
+        }
+
+        #[test_case(SongProperty::PlayCount, "playCount", "12", Some("12"))]
+        #[test_case(SongProperty::SkipCount, "skipCount", "3", Some("3"))]
+        #[test_case(SongProperty::LastPlayed { format: "%Y".to_owned() }, "lastPlayed", "1700000000", Some("2023"))]
+        #[test_case(SongProperty::LastPlayed { format: "%Y".to_owned() }, "lastPlayed", "yesterday", None)]
+        #[test_case(SongProperty::LastPlayed { format: "%Q".to_owned() }, "lastPlayed", "1700000000", None)]
+        fn playback_stats_properties(
+            prop: SongProperty,
+            sticker: &str,
+            value: &str,
+            expected: Option<&str>,
+        ) {
+            let format = Property::<SongProperty> {
+                kind: PropertyKindOrText::Property(prop),
+                style: None,
+                default: None,
+            };
+
+            let song = Song {
+                stickers: Some(HashMap::from([(sticker.to_owned(), value.to_owned())])),
+                ..Default::default()
+            };
+
+            let result = format.as_string(Some(&song), " | ");
+
+            assert_eq!(result.as_deref(), expected);
+        }
+
+        #[test_case(0, None)]
+        #[test_case(200, Some("200"))]
+        fn priority_property(priority: u8, expected: Option<&str>) {
+            let format = Property::<SongProperty> {
+                kind: PropertyKindOrText::Property(SongProperty::Priority),
+                style: None,
+                default: None,
+            };
+
+            let song = Song { priority, ..Default::default() };
+
+            let result = format.as_string(Some(&song), " | ");
+
+            assert_eq!(result.as_deref(), expected);
+        }
+
         #[test_case(StatusProperty::Volume, "100")]
         #[test_case(StatusProperty::Elapsed, "2:03")]
         #[test_case(StatusProperty::Duration, "2:03")]
         #[test_case(StatusProperty::Crossfade, "3")]
         #[test_case(StatusProperty::Bitrate, "123")]
+        #[test_case(StatusProperty::ReplayGain, "Album")]
         fn status_property_resolves_correctly(prop: StatusProperty, expected: &str) {
             let format = Property::<PropertyKind> {
                 kind: PropertyKindOrText::Property(PropertyKind::Status(prop)),
@@ -1081,12 +1312,13 @@ mod format_tests {
                 file: "file".to_owned(),
                 duration: Some(Duration::from_secs(123)),
                 metadata: HashMap::from([
-                    ("artist".to_string(), "artist".to_string()),
-                    ("album".to_string(), "album".to_owned()),
-                    ("title".to_string(), "title".to_owned()),
-                    ("track".to_string(), "123".to_string()),
+                    ("artist".to_string(), "artist".to_string().into()),
+                    ("album".to_string(), "album".to_owned().into()),
+                    ("title".to_string(), "title".to_owned().into()),
+                    ("track".to_string(), "123".to_string().into()),
                 ]),
                 stickers: None,
+                priority: 0,
             };
             let status = Status {
                 volume: Volume::new(123),
@@ -1098,11 +1330,12 @@ mod format_tests {
                 elapsed: Duration::from_secs(123),
                 duration: Duration::from_secs(123),
                 xfade: Some(3),
+                replay_gain: Some(ReplayGainMode::Album),
                 state: State::Play,
                 ..Default::default()
             };
 
-            let result = format.as_span(Some(&song), &status);
+            let result = format.as_span(Some(&song), &status, " | ");
 
             assert_eq!(
                 result,
@@ -1136,7 +1369,7 @@ mod format_tests {
             let song = Song { id: 1, file: "file".to_owned(), ..Default::default() };
             let status = Status { state, ..Default::default() };
 
-            let result = format.as_span(Some(&song), &status);
+            let result = format.as_span(Some(&song), &status, " | ");
 
             assert_eq!(
                 result,
@@ -1154,6 +1387,8 @@ mod format_tests {
         #[test_case(StatusPropertyFile::RandomV2 { on_label: "ye".to_string(), off_label: "naw".to_string(), on_style: None, off_style: None }, &Status { random: false, ..Default::default() }, "naw")]
         #[test_case(StatusPropertyFile::RepeatV2 { on_label: "ye".to_string(), off_label: "naw".to_string(), on_style: None, off_style: None }, &Status { repeat: true, ..Default::default() }, "ye")]
         #[test_case(StatusPropertyFile::RepeatV2 { on_label: "ye".to_string(), off_label: "naw".to_string(), on_style: None, off_style: None }, &Status { repeat: false, ..Default::default() }, "naw")]
+        #[test_case(StatusPropertyFile::Connection { connected_label: "up".to_string(), reconnecting_label: "down".to_string(), connected_style: None, reconnecting_style: None }, &Status { connection: ConnectionState::Connected, ..Default::default() }, "up")]
+        #[test_case(StatusPropertyFile::Connection { connected_label: "up".to_string(), reconnecting_label: "down".to_string(), connected_style: None, reconnecting_style: None }, &Status { connection: ConnectionState::Reconnecting { attempt: 3 }, ..Default::default() }, "down")]
         #[test_case(StatusPropertyFile::Consume, &Status { consume: OnOffOneshot::On, ..Default::default() }, "On")]
         #[test_case(StatusPropertyFile::Consume, &Status { consume: OnOffOneshot::Off, ..Default::default() }, "Off")]
         #[test_case(StatusPropertyFile::Consume, &Status { consume: OnOffOneshot::Oneshot, ..Default::default() }, "OS")]
@@ -1177,7 +1412,7 @@ mod format_tests {
 
             let song = Song { id: 1, file: "file".to_owned(), ..Default::default() };
 
-            let result = format.as_span(Some(&song), status);
+            let result = format.as_span(Some(&song), status, " | ");
 
             assert_eq!(result, Some(Either::Left(Span::raw(expected_label))));
         }
@@ -1203,7 +1438,7 @@ mod format_tests {
 
             let song = Song { id: 1, file: "file".to_owned(), ..Default::default() };
 
-            let result = format.as_span(Some(&song), status);
+            let result = format.as_span(Some(&song), status, " | ");
 
             dbg!(&result);
             assert_eq!(
@@ -1228,13 +1463,13 @@ mod format_tests {
 
             let song = Song {
                 metadata: HashMap::from([
-                    ("artist".to_string(), "artist".to_string()),
-                    ("title".to_string(), "title".to_owned()),
+                    ("artist".to_string(), "artist".to_string().into()),
+                    ("title".to_string(), "title".to_owned().into()),
                 ]),
                 ..Default::default()
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), " | ");
 
             assert_eq!(result, Some("title".to_owned()));
         }
@@ -1256,13 +1491,13 @@ mod format_tests {
 
             let song = Song {
                 metadata: HashMap::from([
-                    ("artist".to_string(), "artist".to_string()),
-                    ("title".to_string(), "title".to_owned()),
+                    ("artist".to_string(), "artist".to_string().into()),
+                    ("title".to_string(), "title".to_owned().into()),
                 ]),
                 ..Default::default()
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), " | ");
 
             assert_eq!(result, Some("fallback".to_owned()));
         }
@@ -1277,13 +1512,13 @@ mod format_tests {
 
             let song = Song {
                 metadata: HashMap::from([
-                    ("artist".to_string(), "artist".to_string()),
-                    ("title".to_string(), "title".to_owned()),
+                    ("artist".to_string(), "artist".to_string().into()),
+                    ("title".to_string(), "title".to_owned().into()),
                 ]),
                 ..Default::default()
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), " | ");
 
             assert_eq!(result, None);
         }
@@ -1304,13 +1539,13 @@ mod format_tests {
 
             let song = Song {
                 metadata: HashMap::from([
-                    ("artist".to_string(), "artist".to_string()),
-                    ("title".to_string(), "title".to_owned()),
+                    ("artist".to_string(), "artist".to_string().into()),
+                    ("title".to_string(), "title".to_owned().into()),
                 ]),
                 ..Default::default()
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), " | ");
 
             assert_eq!(result, Some("test".to_owned()));
         }
@@ -1332,13 +1567,13 @@ mod format_tests {
 
             let song = Song {
                 metadata: HashMap::from([
-                    ("artist".to_string(), "artist".to_string()),
-                    ("title".to_string(), "title".to_owned()),
+                    ("artist".to_string(), "artist".to_string().into()),
+                    ("title".to_string(), "title".to_owned().into()),
                 ]),
                 ..Default::default()
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), " | ");
 
             assert_eq!(result, Some("test".to_owned()));
         }
@@ -1370,13 +1605,13 @@ mod format_tests {
 
             let song = Song {
                 metadata: HashMap::from([
-                    ("artist".to_string(), "artist".to_string()),
-                    ("title".to_string(), "title".to_owned()),
+                    ("artist".to_string(), "artist".to_string().into()),
+                    ("title".to_string(), "title".to_owned().into()),
                 ]),
                 ..Default::default()
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), " | ");
 
             assert_eq!(result, None);
         }
@@ -1409,13 +1644,13 @@ mod format_tests {
 
             let song = Song {
                 metadata: HashMap::from([
-                    ("artist".to_string(), "artist".to_string()),
-                    ("title".to_string(), "title".to_owned()),
+                    ("artist".to_string(), "artist".to_string().into()),
+                    ("title".to_string(), "title".to_owned().into()),
                 ]),
                 ..Default::default()
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), " | ");
 
             assert_eq!(result, Some("fallback".to_owned()));
         }
@@ -1448,13 +1683,13 @@ mod format_tests {
 
             let song = Song {
                 metadata: HashMap::from([
-                    ("artist".to_string(), "artist".to_string()),
-                    ("title".to_string(), "title".to_owned()),
+                    ("artist".to_string(), "artist".to_string().into()),
+                    ("title".to_string(), "title".to_owned().into()),
                 ]),
                 ..Default::default()
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), " | ");
 
             assert_eq!(result, Some("titletext".to_owned()));
         }
@@ -1487,13 +1722,13 @@ mod format_tests {
 
             let song = Song {
                 metadata: HashMap::from([
-                    ("artist".to_string(), "artist".to_string()),
-                    ("title".to_string(), "title".to_owned()),
+                    ("artist".to_string(), "artist".to_string().into()),
+                    ("title".to_string(), "title".to_owned().into()),
                 ]),
                 ..Default::default()
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), " | ");
 
             assert_eq!(result, Some("fallbacktext".to_owned()));
         }
@@ -1536,11 +1771,11 @@ mod format_tests {
             };
 
             let song = Song {
-                metadata: HashMap::from([("title".to_string(), "title".to_owned())]),
+                metadata: HashMap::from([("title".to_string(), "title".to_owned().into())]),
                 ..Default::default()
             };
 
-            let result = format.as_string(Some(&song));
+            let result = format.as_string(Some(&song), " | ");
 
             assert_eq!(result, Some("innerfallbackouter".to_owned()));
         }
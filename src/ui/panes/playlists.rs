@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use anyhow::{Context, Result, anyhow};
 use itertools::Itertools;
 use ratatui::{Frame, prelude::Rect};
@@ -5,7 +7,12 @@ use ratatui::{Frame, prelude::Rect};
 use super::{Pane, browser::DirOrSong};
 use crate::{
     MpdQueryResult,
-    config::tabs::PaneType,
+    config::{
+        keys::PlaylistsActions,
+        smart_playlist::SmartPlaylist,
+        tabs::PaneType,
+        utils::tilde_expand,
+    },
     context::AppContext,
     mpd::{
         client::Client,
@@ -15,9 +22,10 @@ use crate::{
     shared::{
         ext::mpd_client::MpdClientExt,
         key_event::KeyEvent,
-        macros::{modal, status_error, status_info},
+        macros::{modal, status_error, status_info, status_warn},
         mouse_event::MouseEvent,
         mpd_query::PreviewGroup,
+        playlist_file::{self, PlaylistFormat},
     },
     ui::{
         UiEvent,
@@ -38,24 +46,153 @@ pub struct PlaylistsPane {
     browser: Browser<DirOrSong>,
     initialized: bool,
     selected_song: Option<(usize, String)>,
+    smart_playlists: Vec<SmartPlaylist>,
 }
 
 const INIT: &str = "init";
 const REINIT: &str = "reinit";
 const OPEN_OR_PLAY: &str = "open_or_play";
 const PREVIEW: &str = "preview";
+/// Marks playlists defined in the config as opposed to the ones stored in MPD
+const SMART_PLAYLIST_PATH: &str = "smart_playlist";
 
 impl PlaylistsPane {
-    pub fn new(_context: &AppContext) -> Self {
+    pub fn new(context: &AppContext) -> Self {
         Self {
             stack: DirStack::default(),
             filter_input_mode: false,
             browser: Browser::new(),
             initialized: false,
             selected_song: None,
+            smart_playlists: context.config.smart_playlists.clone(),
         }
     }
 
+    /// Returns the smart playlist the item refers to, if any
+    fn export(&self, item: &DirOrSong, context: &AppContext) -> Result<()> {
+        let DirOrSong::Dir { name, .. } = item else {
+            return Ok(());
+        };
+        let name = name.clone();
+        let smart_playlist = self.smart_playlist(item);
+        modal!(
+            context,
+            InputModal::new(context)
+                .title("Export playlist")
+                .confirm_label("Export")
+                .input_label("File (m3u, m3u8, pls or xspf):")
+                .initial_value(format!("~/{name}.{}", PlaylistFormat::M3u8.extension()))
+                .on_confirm(move |context, value| {
+                    let path = PathBuf::from(tilde_expand(value).as_ref());
+                    let format = PlaylistFormat::from_path(&path).unwrap_or(PlaylistFormat::M3u8);
+                    let name = name.clone();
+                    let smart_playlist = smart_playlist.clone();
+                    context.command(move |client| {
+                        let rendered = match smart_playlist {
+                            Some(smart_playlist) => {
+                                let songs = client.smart_playlist_songs(&smart_playlist)?;
+                                playlist_file::render(format, &name, &songs, None)
+                            }
+                            None => client.export_playlist(&name, format, false)?,
+                        };
+                        std::fs::write(&path, rendered)?;
+                        status_info!("Playlist '{}' exported to '{}'", name, path.display());
+                        Ok(())
+                    });
+                    Ok(())
+                })
+        );
+        Ok(())
+    }
+
+    fn import(context: &AppContext) -> Result<()> {
+        modal!(
+            context,
+            InputModal::new(context)
+                .title("Import playlist")
+                .confirm_label("Import")
+                .input_label("File (m3u, m3u8, pls or xspf):")
+                .on_confirm(move |context, value| {
+                    let path = PathBuf::from(tilde_expand(value).as_ref());
+                    context.command(move |client| {
+                        let path = std::fs::canonicalize(&path)?;
+                        let entries = playlist_file::read(&path)?;
+                        let name = path
+                            .file_stem()
+                            .map(|stem| stem.to_string_lossy().into_owned())
+                            .context("Playlist file has no name")?;
+                        let result = client.import_playlist(&name, &entries, path.parent())?;
+                        if result.missing.is_empty() {
+                            status_info!(
+                                "Imported {} songs into playlist '{}'",
+                                result.added,
+                                result.name
+                            );
+                        } else {
+                            status_warn!(
+                                "Imported {} of {} songs into playlist '{}', {} were not found",
+                                result.added,
+                                entries.len(),
+                                result.name,
+                                result.missing.len()
+                            );
+                        }
+                        Ok(())
+                    });
+                    Ok(())
+                })
+        );
+        Ok(())
+    }
+
+    fn smart_playlist(&self, item: &DirOrSong) -> Option<SmartPlaylist> {
+        match item {
+            DirOrSong::Dir { name, full_path } if full_path == SMART_PLAYLIST_PATH => {
+                self.smart_playlists.iter().find(|playlist| &playlist.name == name).cloned()
+            }
+            _ => None,
+        }
+    }
+
+    /// Adds the whole playlist to the end of the queue
+    fn load(&self, name: &str, smart_playlist: Option<SmartPlaylist>, context: &AppContext) {
+        let name = name.to_owned();
+        if let Some(smart_playlist) = smart_playlist {
+            context.command(move |client| {
+                let count = client.load_smart_playlist(&smart_playlist)?;
+                status_info!("{count} songs from smart playlist '{name}' added to queue");
+                Ok(())
+            });
+        } else {
+            context.command(move |client| {
+                client.load_playlist(&name)?;
+                status_info!("Playlist '{name}' added to queue");
+                Ok(())
+            });
+        }
+    }
+
+    fn list_playlists(&self, id: &'static str, context: &AppContext) {
+        let smart_playlists =
+            self.smart_playlists.iter().map(|playlist| playlist.name.clone()).collect_vec();
+        context.query().id(id).replace_id(id).target(PaneType::Playlists).query(move |client| {
+            let result: Vec<_> = client
+                .list_playlists()
+                .context("Cannot list playlists")?
+                .into_iter()
+                .map(|playlist| DirOrSong::Dir { name: playlist.name, full_path: String::new() })
+                .sorted()
+                .chain(
+                    smart_playlists.into_iter().map(|name| DirOrSong::Dir {
+                        name,
+                        full_path: SMART_PLAYLIST_PATH.to_owned(),
+                    }),
+                )
+                .collect();
+            Ok(MpdQueryResult::DirOrSong { data: result, origin_path: None })
+        });
+    }
+
     fn open_or_play(
         &mut self,
         autoplay: bool,
@@ -76,17 +213,20 @@ impl PlaylistsPane {
         match selected {
             DirOrSong::Dir { name: playlist, .. } => {
                 let playlist = playlist.clone();
+                let smart_playlist = self.smart_playlist(selected);
                 context.query().id(action_id).target(PaneType::Playlists).query(move |client| {
-                    Ok(MpdQueryResult::SongsList {
-                        data: client.list_playlist_info(&playlist, None)?,
-                        origin_path: Some(next_path),
-                    })
+                    let data = match smart_playlist {
+                        Some(smart_playlist) => client.smart_playlist_songs(&smart_playlist)?,
+                        None => client.list_playlist_info(&playlist, None)?,
+                    };
+                    Ok(MpdQueryResult::SongsList { data, origin_path: Some(next_path) })
                 });
                 self.stack_mut().push(Vec::new());
                 self.stack_mut().clear_preview();
                 context.render()?;
             }
             DirOrSong::Song(_song) => {
+                context.record_queue_edit();
                 self.add(selected, context)?;
                 let queue_len = context.queue.len();
                 if autoplay {
@@ -113,21 +253,7 @@ impl Pane for PlaylistsPane {
 
     fn before_show(&mut self, context: &AppContext) -> Result<()> {
         if !self.initialized {
-            context.query().id(INIT).target(PaneType::Playlists).replace_id(INIT).query(
-                move |client| {
-                    let result: Vec<_> = client
-                        .list_playlists()
-                        .context("Cannot list playlists")?
-                        .into_iter()
-                        .map(|playlist| DirOrSong::Dir {
-                            name: playlist.name,
-                            full_path: String::new(),
-                        })
-                        .sorted()
-                        .collect();
-                    Ok(MpdQueryResult::DirOrSong { data: result, origin_path: None })
-                },
-            );
+            self.list_playlists(INIT, context);
 
             self.initialized = true;
         }
@@ -148,23 +274,13 @@ impl Pane for PlaylistsPane {
         match event {
             UiEvent::Database | UiEvent::StoredPlaylist => {
                 if let Some(id) = id {
-                    context.query().id(id).replace_id(id).target(PaneType::Playlists).query(
-                        move |client| {
-                            let result: Vec<_> = client
-                                .list_playlists()
-                                .context("Cannot list playlists")?
-                                .into_iter()
-                                .map(|playlist| DirOrSong::Dir {
-                                    name: playlist.name,
-                                    full_path: String::new(),
-                                })
-                                .sorted()
-                                .collect();
-                            Ok(MpdQueryResult::DirOrSong { data: result, origin_path: None })
-                        },
-                    );
+                    self.list_playlists(id, context);
                 }
             }
+            UiEvent::ConfigChanged if self.smart_playlists != context.config.smart_playlists => {
+                self.smart_playlists.clone_from(&context.config.smart_playlists);
+                self.list_playlists(INIT, context);
+            }
             UiEvent::Reconnected => {
                 self.initialized = false;
                 self.before_show(context)?;
@@ -179,8 +295,22 @@ impl Pane for PlaylistsPane {
         self.handle_mouse_action(event, context)
     }
 
+    fn selected_songs(&self, context: &AppContext) -> Result<Vec<Song>> {
+        self.marked_or_selected_songs(context)
+    }
+
     fn handle_action(&mut self, event: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
         self.handle_filter_input(event, context)?;
+        if let Some(action) = event.as_playlists_action(context) {
+            match action {
+                PlaylistsActions::ExportPlaylist => {
+                    if let Some(item) = self.stack().current().selected() {
+                        self.export(item, context)?;
+                    }
+                }
+                PlaylistsActions::ImportPlaylist => Self::import(context)?,
+            }
+        }
         self.handle_common_action(event, context)?;
         self.handle_global_action(event, context)?;
         Ok(())
@@ -225,6 +355,11 @@ impl Pane for PlaylistsPane {
                 let old_content_len = self.stack.current().state.content_len();
                 match self.stack.path() {
                     [playlist_name] => {
+                        let smart_playlist = self
+                            .stack()
+                            .previous()
+                            .selected()
+                            .and_then(|playlist| self.smart_playlist(playlist));
                         let (selected_idx, selected_playlist) = self
                             .stack()
                             .previous()
@@ -256,7 +391,12 @@ impl Pane for PlaylistsPane {
                         self.stack_mut().current_mut().state.set_viewport_len(old_viewport_len);
 
                         let songs = context.query_sync(move |client| {
-                            Ok(client.list_playlist_info(&playlist, None)?)
+                            Ok(match smart_playlist {
+                                Some(smart_playlist) => {
+                                    client.smart_playlist_songs(&smart_playlist)?
+                                }
+                                None => client.list_playlist_info(&playlist, None)?,
+                            })
                         })?;
 
                         self.stack_mut().push(songs.into_iter().map(DirOrSong::Song).collect());
@@ -336,7 +476,11 @@ impl BrowserPane<DirOrSong> for PlaylistsPane {
         &self,
         item: DirOrSong,
     ) -> impl FnOnce(&mut Client<'_>) -> Result<Vec<Song>> + 'static {
+        let smart_playlist = self.smart_playlist(&item);
         move |client| {
+            if let Some(smart_playlist) = smart_playlist {
+                return Ok(client.smart_playlist_songs(&smart_playlist)?);
+            }
             Ok(match item {
                 DirOrSong::Dir { name, .. } => client.list_playlist_info(&name, None)?,
                 DirOrSong::Song(song) => vec![song.clone()],
@@ -346,6 +490,11 @@ impl BrowserPane<DirOrSong> for PlaylistsPane {
 
     fn delete(&self, item: &DirOrSong, index: usize, context: &AppContext) -> Result<()> {
         match item {
+            DirOrSong::Dir { name, .. } if self.smart_playlist(item).is_some() => {
+                status_warn!(
+                    "Smart playlist '{name}' is defined in the config and cannot be deleted"
+                );
+            }
             DirOrSong::Dir { name: d, .. } => {
                 let d = d.clone();
                 modal!(
@@ -366,10 +515,15 @@ impl BrowserPane<DirOrSong> for PlaylistsPane {
                 );
             }
             DirOrSong::Song(s) => {
-                let Some(DirOrSong::Dir { name: playlist, .. }) = self.stack.previous().selected()
+                let Some(previous @ DirOrSong::Dir { name: playlist, .. }) =
+                    self.stack.previous().selected()
                 else {
                     return Ok(());
                 };
+                if self.smart_playlist(previous).is_some() {
+                    status_warn!("Songs cannot be deleted from smart playlist '{playlist}'");
+                    return Ok(());
+                }
                 let playlist = playlist.clone();
                 let file = s.file.clone();
                 context.command(move |client| {
@@ -387,12 +541,9 @@ impl BrowserPane<DirOrSong> for PlaylistsPane {
     fn add_all(&self, context: &AppContext) -> Result<()> {
         match self.stack().path() {
             [playlist] => {
-                let playlist = playlist.clone();
-                context.command(move |client| {
-                    client.load_playlist(&playlist)?;
-                    status_info!("Playlist '{playlist}' added to queue");
-                    Ok(())
-                });
+                let smart_playlist =
+                    self.stack().previous().selected().and_then(|item| self.smart_playlist(item));
+                self.load(playlist, smart_playlist, context);
             }
             [] => {
                 for playlist in &self.stack().current().items {
@@ -408,13 +559,8 @@ impl BrowserPane<DirOrSong> for PlaylistsPane {
 
     fn add_next(&self, item: &DirOrSong, context: &AppContext) -> Result<()> {
         match item {
-            DirOrSong::Dir { name: d, .. } => {
-                let d = d.clone();
-                context.command(move |client| {
-                    client.load_playlist(&d)?;
-                    status_info!("Playlist '{d}' added to queue");
-                    Ok(())
-                });
+            DirOrSong::Dir { name, .. } => {
+                self.load(name, self.smart_playlist(item), context);
             }
             DirOrSong::Song(s) => {
                 let file = s.file.clone();
@@ -437,13 +583,8 @@ impl BrowserPane<DirOrSong> for PlaylistsPane {
 
     fn add(&self, item: &DirOrSong, context: &AppContext) -> Result<()> {
         match item {
-            DirOrSong::Dir { name: d, .. } => {
-                let d = d.clone();
-                context.command(move |client| {
-                    client.load_playlist(&d)?;
-                    status_info!("Playlist '{d}' added to queue");
-                    Ok(())
-                });
+            DirOrSong::Dir { name, .. } => {
+                self.load(name, self.smart_playlist(item), context);
             }
             DirOrSong::Song(s) => {
                 let file = s.file.clone();
@@ -466,6 +607,11 @@ impl BrowserPane<DirOrSong> for PlaylistsPane {
 
     fn rename(&self, item: &DirOrSong, context: &AppContext) -> Result<()> {
         match item {
+            DirOrSong::Dir { name, .. } if self.smart_playlist(item).is_some() => {
+                status_warn!(
+                    "Smart playlist '{name}' is defined in the config and cannot be renamed"
+                );
+            }
             DirOrSong::Dir { name: d, .. } => {
                 let current_name = d.clone();
                 modal!(
@@ -507,28 +653,43 @@ impl BrowserPane<DirOrSong> for PlaylistsPane {
         self.open_or_play(false, context, OPEN_OR_PLAY)
     }
 
-    fn move_selected(&mut self, direction: MoveDirection, context: &AppContext) -> Result<()> {
+    fn move_selected(
+        &mut self,
+        direction: MoveDirection,
+        count: usize,
+        context: &AppContext,
+    ) -> Result<()> {
         let Some((idx, selected)) = self.stack().current().selected_with_idx() else {
             status_error!("Failed to move playlist. No playlist selected");
             return Ok(());
         };
-        let Some(DirOrSong::Dir { name: playlist, .. }) = self.stack.previous().selected() else {
+        let Some(previous @ DirOrSong::Dir { name: playlist, .. }) =
+            self.stack.previous().selected()
+        else {
             return Ok(());
         };
+        if self.smart_playlist(previous).is_some() {
+            status_warn!("Songs in smart playlist '{playlist}' cannot be moved");
+            return Ok(());
+        }
 
         match selected {
             DirOrSong::Dir { .. } => {}
             DirOrSong::Song(_) => {
                 let new_idx = match direction {
-                    MoveDirection::Up => idx.saturating_sub(1),
-                    MoveDirection::Down => (idx + 1).min(self.stack().current().items.len() - 1),
+                    MoveDirection::Up => idx.saturating_sub(count),
+                    MoveDirection::Down => {
+                        idx.saturating_add(count).min(self.stack().current().items.len() - 1)
+                    }
                 };
                 let playlist = playlist.clone();
                 context.command(move |client| {
                     client.move_in_playlist(&playlist, &SingleOrRange::single(idx), new_idx)?;
                     Ok(())
                 });
-                self.stack_mut().current_mut().items.swap(idx, new_idx);
+                let items = &mut self.stack_mut().current_mut().items;
+                let song = items.remove(idx);
+                items.insert(new_idx, song);
                 self.stack_mut().current_mut().select_idx(new_idx, context.config.scrolloff);
             }
         };
@@ -540,6 +701,7 @@ impl BrowserPane<DirOrSong> for PlaylistsPane {
     fn prepare_preview(&mut self, context: &AppContext) -> Result<()> {
         let config = std::sync::Arc::clone(&context.config);
         let s = self.stack().current().selected().cloned();
+        let smart_playlist = s.as_ref().and_then(|item| self.smart_playlist(item));
         self.stack_mut().clear_preview();
         let origin_path = Some(self.stack().path().to_vec());
         context
@@ -552,12 +714,16 @@ impl BrowserPane<DirOrSong> for PlaylistsPane {
                     let response = match current {
                         DirOrSong::Dir { name: d, .. } => Some(vec![PreviewGroup::from(
                             None,
-                            client
-                                .list_playlist_info(d, None)?
-                                .into_iter()
-                                .map(DirOrSong::Song)
-                                .map(|s| s.to_list_item_simple(&config))
-                                .collect_vec(),
+                            match smart_playlist {
+                                Some(smart_playlist) => {
+                                    client.smart_playlist_songs(&smart_playlist)?
+                                }
+                                None => client.list_playlist_info(d, None)?,
+                            }
+                            .into_iter()
+                            .map(DirOrSong::Song)
+                            .map(|s| s.to_list_item_simple(&config))
+                            .collect_vec(),
                         )]),
                         DirOrSong::Song(song) => {
                             match client
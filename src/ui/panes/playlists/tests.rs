@@ -522,6 +522,7 @@ fn song(name: &str) -> Song {
         duration: Some(Duration::from_secs(1)),
         metadata: HashMap::new(),
         stickers: None,
+        priority: 0,
     }
 }
 
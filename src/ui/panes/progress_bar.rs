@@ -10,6 +10,7 @@ use ratatui::{
 
 use super::Pane;
 use crate::{
+    config::keys::KeySequence,
     context::AppContext,
     mpd::{
         commands::State,
@@ -66,6 +67,23 @@ impl Pane for ProgressBarPane {
             };
             frame.render_widget(elapsed_bar, self.area);
         }
+
+        let pending = format!(
+            "{}{}",
+            context.pending_count.map(|count| count.to_string()).unwrap_or_default(),
+            KeySequence(context.pending_keys.clone())
+        );
+        if !pending.is_empty() {
+            let width = u16::try_from(pending.chars().count()).unwrap_or(u16::MAX);
+            let mut pending_area = self.area;
+            pending_area.x = self.area.right().saturating_sub(width);
+            pending_area.width = width.min(self.area.width);
+            frame.render_widget(
+                Paragraph::new(pending).style(context.config.as_text_style()),
+                pending_area,
+            );
+        }
+
         Ok(())
     }
 
@@ -12,31 +12,37 @@ use ratatui::{
     widgets::{Block, Borders, Row, Table, TableState},
 };
 
-use super::{CommonAction, Pane};
+use super::{CommonAction, Pane, rate_songs};
 use crate::{
     MpdQueryResult,
     config::{
+        Config,
         keys::{GlobalAction, QueueActions},
+        queue_sort::QueueSort,
         tabs::PaneType,
         theme::properties::{Property, SongProperty},
     },
     context::AppContext,
     core::command::{create_env, run_external},
     mpd::{
+        command_list::CommandList,
         commands::Song,
-        mpd_client::{MpdClient, QueueMoveTarget},
+        mpd_client::{MpdClient, PlaybackRange, QueueMoveTarget, SingleOrRange, ValueChange},
     },
     shared::{
-        ext::{btreeset_ranges::BTreeSetRanges, rect::RectExt},
+        ext::{btreeset_ranges::BTreeSetRanges, mpd_client::MpdClientExt, rect::RectExt},
         key_event::KeyEvent,
         macros::{modal, status_error, status_info, status_warn},
         mouse_event::{MouseEvent, MouseEventKind},
+        queue_order::QueueOrder,
     },
     ui::{
         UiEvent,
         dirstack::DirState,
         modals::{
-            confirm_modal::ConfirmModal, input_modal::InputModal, select_modal::SelectModal,
+            confirm_modal::ConfirmModal,
+            input_modal::InputModal,
+            select_modal::SelectModal,
             song_info::SongInfoModal,
         },
     },
@@ -61,6 +67,25 @@ enum Areas {
     TableBlock,
 }
 
+#[derive(Debug, Clone, Copy)]
+enum ShuffleMode {
+    Queue,
+    Upcoming,
+    Marked,
+    Albums,
+}
+
+impl std::fmt::Display for ShuffleMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShuffleMode::Queue => write!(f, "Whole queue"),
+            ShuffleMode::Upcoming => write!(f, "Songs after the current one"),
+            ShuffleMode::Marked => write!(f, "Marked songs"),
+            ShuffleMode::Albums => write!(f, "By album, keeping album tracks together"),
+        }
+    }
+}
+
 const ADD_TO_PLAYLIST: &str = "add_to_playlist";
 
 impl QueuePane {
@@ -153,7 +178,12 @@ impl Pane for QueuePane {
                     }
 
                     let mut line = song
-                        .as_line_ellipsized(&formats[i].prop, max_len, &config.theme.symbols)
+                        .as_line_ellipsized(
+                            &formats[i].prop,
+                            max_len,
+                            &config.theme.symbols,
+                            &config.theme.format_tag_separator,
+                        )
                         .unwrap_or_default()
                         .alignment(formats[i].alignment.into());
 
@@ -169,10 +199,13 @@ impl Pane for QueuePane {
                 });
 
                 let is_highlighted = is_current
-                    || self
-                        .filter
-                        .as_ref()
-                        .is_some_and(|filter| song.matches(self.column_formats.as_slice(), filter));
+                    || self.filter.as_ref().is_some_and(|filter| {
+                        song.matches(
+                            self.column_formats.as_slice(),
+                            filter,
+                            &config.theme.format_tag_separator,
+                        )
+                    });
 
                 if is_highlighted {
                     Row::new(
@@ -333,6 +366,7 @@ impl Pane for QueuePane {
                     .and_then(|idx| context.queue.get(idx))
                 {
                     let id = selected_song.id;
+                    context.record_queue_edit();
                     context.command(move |client| {
                         client.delete_id(id)?;
                         Ok(())
@@ -393,9 +427,23 @@ impl Pane for QueuePane {
         Ok(())
     }
 
+    fn selected_songs(&self, context: &AppContext) -> Result<Vec<Song>> {
+        let marked = self.scrolling_state.get_marked();
+        Ok(if marked.is_empty() {
+            self.scrolling_state
+                .get_selected()
+                .and_then(|idx| context.queue.get(idx))
+                .cloned()
+                .into_iter()
+                .collect()
+        } else {
+            marked.iter().filter_map(|idx| context.queue.get(*idx)).cloned().collect()
+        })
+    }
+
     fn handle_action(&mut self, event: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
         if self.filter_input_mode {
-            match event.as_common_action(context) {
+            match event.as_input_common_action(context) {
                 Some(CommonAction::Confirm) => {
                     self.filter_input_mode = false;
 
@@ -414,7 +462,7 @@ impl Pane for QueuePane {
                             if let Some(ref mut f) = self.filter {
                                 f.push(c);
                             };
-                            self.jump_first(&context.queue, context.config.scrolloff);
+                            self.jump_first(&context.queue, &context.config);
 
                             context.render()?;
                         }
@@ -432,23 +480,37 @@ impl Pane for QueuePane {
         } else if let Some(action) = event.as_queue_action(context) {
             match action {
                 QueueActions::Delete if !self.scrolling_state.marked.is_empty() => {
+                    context.record_queue_edit();
+                    let mut list = CommandList::default();
                     for range in self.scrolling_state.marked.ranges().rev() {
-                        context.command(move |client| {
-                            client.delete_from_queue(range.into())?;
-                            Ok(())
-                        });
+                        list.delete_from_queue(range.into());
                     }
+                    context.command(move |client| {
+                        for result in client.batch::<()>(&list)? {
+                            result?;
+                        }
+                        Ok(())
+                    });
                     self.scrolling_state.marked.clear();
                     status_info!("Marked songs removed from queue");
                     context.render()?;
                 }
                 QueueActions::Delete => {
-                    if let Some(selected_song) =
-                        self.scrolling_state.get_selected().and_then(|idx| context.queue.get(idx))
+                    let count = context.count();
+                    if let Some((idx, selected_song)) = self
+                        .scrolling_state
+                        .get_selected()
+                        .and_then(|idx| context.queue.get(idx).map(|song| (idx, song)))
                     {
                         let id = selected_song.id;
+                        let end = idx.saturating_add(count).min(context.queue.len());
+                        context.record_queue_edit();
                         context.command(move |client| {
-                            client.delete_id(id)?;
+                            if count > 1 {
+                                client.delete_from_queue(SingleOrRange::range(idx, end))?;
+                            } else {
+                                client.delete_id(id)?;
+                            }
                             Ok(())
                         });
                     } else {
@@ -459,8 +521,9 @@ impl Pane for QueuePane {
                     modal!(
                         context,
                         ConfirmModal::new(context)
-                            .message("Are you sure you want to clear the queue? This action cannot be undone.")
+                            .message("Are you sure you want to clear the queue? It can be restored with Undo.")
                             .on_confirm(|context| {
+                                context.record_queue_edit();
                                 context.command(|client| Ok(client.clear()?));
                                 Ok(())
                             })
@@ -541,6 +604,154 @@ impl Pane for QueuePane {
                         status_error!("No song selected");
                     }
                 }
+                QueueActions::PriorityUp | QueueActions::PriorityDown => {
+                    let songs = self.selected_songs(context)?;
+                    if songs.is_empty() {
+                        status_error!("No song selected");
+                        return Ok(());
+                    }
+
+                    // The current priority is read from MPD, the one in the queue
+                    // is not updated until MPD reports the change
+                    let step = u32::try_from(context.count()).unwrap_or(u32::MAX);
+                    let change = if action == QueueActions::PriorityUp {
+                        ValueChange::Increase(step)
+                    } else {
+                        ValueChange::Decrease(step)
+                    };
+                    let ids = songs.iter().map(|song| song.id).collect_vec();
+                    context.command(move |client| Ok(client.change_priority(&ids, change)?));
+                }
+                QueueActions::SetRange => {
+                    let ids =
+                        self.selected_songs(context)?.iter().map(|song| song.id).collect_vec();
+                    if ids.is_empty() {
+                        status_error!("No song selected");
+                        return Ok(());
+                    }
+
+                    modal!(
+                        context,
+                        InputModal::new(context)
+                            .title("Set played range")
+                            .confirm_label("Set")
+                            .input_label("Range in seconds, START:END, empty plays everything:")
+                            .on_confirm(move |context, value| {
+                                let range = match value.parse::<PlaybackRange>() {
+                                    Ok(range) => range,
+                                    Err(err) => {
+                                        status_error!(err:?; "Invalid range '{}'", value);
+                                        return Ok(());
+                                    }
+                                };
+                                let mut list = CommandList::default();
+                                for id in &ids {
+                                    list.set_range_id(*id, range);
+                                }
+                                context.command(move |client| {
+                                    for result in client.batch::<()>(&list)? {
+                                        result?;
+                                    }
+                                    Ok(())
+                                });
+                                Ok(())
+                            })
+                    );
+                }
+                QueueActions::SortBy => {
+                    let positions = self.scrolling_state.get_marked().iter().copied().collect_vec();
+                    let title =
+                        if positions.is_empty() { "Sort queue by" } else { "Sort marked songs by" };
+                    modal!(
+                        context,
+                        SelectModal::new(context)
+                            .options(context.config.queue_sort.clone())
+                            .confirm_label("Sort")
+                            .title(title)
+                            .on_confirm(move |context, sort: &QueueSort, _idx| {
+                                let order = QueueOrder::Sort(sort.clone());
+                                let positions = positions.clone();
+                                let name = sort.name.clone();
+                                context.record_queue_edit();
+                                context.command(move |client| {
+                                    let moved = client.reorder_queue(&order, &positions)?;
+                                    status_info!("Queue sorted by {name}, {moved} songs moved");
+                                    Ok(())
+                                });
+                                Ok(())
+                            })
+                    );
+                }
+                QueueActions::Shuffle => {
+                    let positions = self.scrolling_state.get_marked().iter().copied().collect_vec();
+                    let upcoming = context.find_current_song_in_queue().map(|(idx, _)| idx + 1);
+                    let queue_len = context.queue.len();
+                    modal!(
+                        context,
+                        SelectModal::new(context)
+                            .options(vec![
+                                ShuffleMode::Queue,
+                                ShuffleMode::Upcoming,
+                                ShuffleMode::Marked,
+                                ShuffleMode::Albums,
+                            ])
+                            .confirm_label("Shuffle")
+                            .title("Shuffle")
+                            .on_confirm(move |context, mode: &ShuffleMode, _idx| {
+                                let positions = positions.clone();
+                                match mode {
+                                    ShuffleMode::Queue => {
+                                        context.record_queue_edit();
+                                        context.command(move |client| {
+                                            client.shuffle(None)?;
+                                            status_info!("Queue shuffled");
+                                            Ok(())
+                                        });
+                                    }
+                                    ShuffleMode::Upcoming => {
+                                        let Some(start) =
+                                            upcoming.filter(|start| *start + 1 < queue_len)
+                                        else {
+                                            status_warn!("No upcoming songs to shuffle");
+                                            return Ok(());
+                                        };
+                                        context.record_queue_edit();
+                                        context.command(move |client| {
+                                            client.shuffle(Some(SingleOrRange::range(
+                                                start, queue_len,
+                                            )))?;
+                                            status_info!("Upcoming songs shuffled");
+                                            Ok(())
+                                        });
+                                    }
+                                    ShuffleMode::Marked if positions.is_empty() => {
+                                        status_warn!("No songs are marked");
+                                    }
+                                    ShuffleMode::Marked => {
+                                        context.record_queue_edit();
+                                        context.command(move |client| {
+                                            client
+                                                .reorder_queue(&QueueOrder::Shuffle, &positions)?;
+                                            status_info!("Marked songs shuffled");
+                                            Ok(())
+                                        });
+                                    }
+                                    ShuffleMode::Albums => {
+                                        context.record_queue_edit();
+                                        context.command(move |client| {
+                                            client.reorder_queue(
+                                                &QueueOrder::ShuffleByAlbum,
+                                                &positions,
+                                            )?;
+                                            status_info!("Albums shuffled");
+                                            Ok(())
+                                        });
+                                    }
+                                }
+                                Ok(())
+                            })
+                    );
+                }
             }
         } else if let Some(action) = event.as_common_action(context) {
             match action {
@@ -548,16 +759,22 @@ impl Pane for QueuePane {
                 CommonAction::AddAllNext => {}
                 CommonAction::Up => {
                     if !context.queue.is_empty() {
-                        self.scrolling_state
-                            .prev(context.config.scrolloff, context.config.wrap_navigation);
+                        self.scrolling_state.prev_by(
+                            context.count(),
+                            context.config.scrolloff,
+                            context.config.wrap_navigation,
+                        );
                     }
 
                     context.render()?;
                 }
                 CommonAction::Down => {
                     if !context.queue.is_empty() {
-                        self.scrolling_state
-                            .next(context.config.scrolloff, context.config.wrap_navigation);
+                        self.scrolling_state.next_by(
+                            context.count(),
+                            context.config.scrolloff,
+                            context.config.wrap_navigation,
+                        );
                     }
 
                     context.render()?;
@@ -566,35 +783,35 @@ impl Pane for QueuePane {
                     if context.queue.is_empty() {
                         return Ok(());
                     }
-
-                    if let Some(0) = self.scrolling_state.marked.first() {
+                    let Some(first) = self.scrolling_state.marked.first().copied() else {
+                        return Ok(());
+                    };
+                    let shift = context.count().min(first);
+                    if shift == 0 {
                         return Ok(());
                     }
+                    context.record_queue_edit();
 
+                    // Moving the ranges front to back keeps the positions of the ranges
+                    // not yet moved intact, the same holds for back to front when moving down
+                    let mut moves = CommandList::default();
                     for range in self.scrolling_state.marked.ranges() {
-                        for idx in range.clone() {
-                            let new_idx = idx.saturating_sub(1);
-                            context.queue.swap(idx, new_idx);
-                        }
-
-                        let new_start_idx = range.start().saturating_sub(1);
-                        context.command(move |client| {
-                            client.move_in_queue(
-                                range.into(),
-                                QueueMoveTarget::Absolute(new_start_idx),
-                            )?;
-                            Ok(())
-                        });
+                        let new_start_idx = range.start() - shift;
+                        let songs = context.queue.drain(range.clone()).collect_vec();
+                        context.queue.splice(new_start_idx..new_start_idx, songs);
+                        moves.move_in_queue(range.into(), QueueMoveTarget::Absolute(new_start_idx));
                     }
 
-                    if let Some(start) = self.scrolling_state.marked.first() {
-                        let new_idx = start.saturating_sub(1);
-                        self.scrolling_state.select(Some(new_idx), context.config.scrolloff);
-                    }
+                    self.scrolling_state.select(Some(first - shift), context.config.scrolloff);
+                    self.scrolling_state.marked =
+                        self.scrolling_state.marked.iter().map(|i| i - shift).collect();
 
-                    let mut new_marked =
-                        self.scrolling_state.marked.iter().map(|i| i.saturating_sub(1)).collect();
-                    std::mem::swap(&mut self.scrolling_state.marked, &mut new_marked);
+                    context.command(move |client| {
+                        for result in client.batch::<()>(&moves)? {
+                            result?;
+                        }
+                        Ok(())
+                    });
 
                     context.render()?;
                     return Ok(());
@@ -603,37 +820,33 @@ impl Pane for QueuePane {
                     if context.queue.is_empty() {
                         return Ok(());
                     }
-
-                    if let Some(last_idx) = self.scrolling_state.marked.last() {
-                        if *last_idx == context.queue.len() - 1 {
-                            return Ok(());
-                        }
+                    let Some(last) = self.scrolling_state.marked.last().copied() else {
+                        return Ok(());
+                    };
+                    let shift = context.count().min((context.queue.len() - 1).saturating_sub(last));
+                    if shift == 0 {
+                        return Ok(());
                     }
+                    context.record_queue_edit();
 
+                    let mut moves = CommandList::default();
                     for range in self.scrolling_state.marked.ranges().rev() {
-                        for idx in range.clone().rev() {
-                            let new_idx = idx.saturating_add(1);
-                            context.queue.swap(idx, new_idx);
-                        }
-
-                        let new_start_idx = range.start().saturating_add(1);
-                        context.command(move |client| {
-                            client.move_in_queue(
-                                range.into(),
-                                QueueMoveTarget::Absolute(new_start_idx),
-                            )?;
-                            Ok(())
-                        });
+                        let new_start_idx = range.start() + shift;
+                        let songs = context.queue.drain(range.clone()).collect_vec();
+                        context.queue.splice(new_start_idx..new_start_idx, songs);
+                        moves.move_in_queue(range.into(), QueueMoveTarget::Absolute(new_start_idx));
                     }
 
-                    if let Some(start) = self.scrolling_state.marked.last() {
-                        let new_idx = start.saturating_add(1);
-                        self.scrolling_state.select(Some(new_idx), context.config.scrolloff);
-                    }
+                    self.scrolling_state.select(Some(last + shift), context.config.scrolloff);
+                    self.scrolling_state.marked =
+                        self.scrolling_state.marked.iter().map(|i| i + shift).collect();
 
-                    let mut new_marked =
-                        self.scrolling_state.marked.iter().map(|i| i.saturating_add(1)).collect();
-                    std::mem::swap(&mut self.scrolling_state.marked, &mut new_marked);
+                    context.command(move |client| {
+                        for result in client.batch::<()>(&moves)? {
+                            result?;
+                        }
+                        Ok(())
+                    });
 
                     context.render()?;
                     return Ok(());
@@ -642,6 +855,7 @@ impl Pane for QueuePane {
                     if context.queue.is_empty() {
                         return Ok(());
                     }
+                    context.record_queue_edit();
 
                     let Some(idx) = self.scrolling_state.get_selected() else {
                         return Ok(());
@@ -653,20 +867,22 @@ impl Pane for QueuePane {
                         return Ok(());
                     };
 
-                    let new_idx = idx.saturating_sub(1);
+                    let new_idx = idx.saturating_sub(context.count());
                     let id = selected.id;
                     context.command(move |client| {
                         client.move_id(id, QueueMoveTarget::Absolute(new_idx))?;
                         Ok(())
                     });
                     self.scrolling_state.select(Some(new_idx), context.config.scrolloff);
-                    context.queue.swap(idx, new_idx);
+                    let song = context.queue.remove(idx);
+                    context.queue.insert(new_idx, song);
                     context.render()?;
                 }
                 CommonAction::MoveDown => {
                     if context.queue.is_empty() {
                         return Ok(());
                     }
+                    context.record_queue_edit();
 
                     let Some(idx) = self.scrolling_state.get_selected() else {
                         return Ok(());
@@ -677,14 +893,15 @@ impl Pane for QueuePane {
                         return Ok(());
                     };
 
-                    let new_idx = (idx + 1).min(context.queue.len() - 1);
+                    let new_idx = idx.saturating_add(context.count()).min(context.queue.len() - 1);
                     let id = selected.id;
                     context.command(move |client| {
                         client.move_id(id, QueueMoveTarget::Absolute(new_idx))?;
                         Ok(())
                     });
                     self.scrolling_state.select(Some(new_idx), context.config.scrolloff);
-                    context.queue.swap(idx, new_idx);
+                    let song = context.queue.remove(idx);
+                    context.queue.insert(new_idx, song);
                     context.render()?;
                 }
                 CommonAction::DownHalf => {
@@ -738,12 +955,12 @@ impl Pane for QueuePane {
                     context.render()?;
                 }
                 CommonAction::NextResult => {
-                    self.jump_forward(&context.queue, context.config.scrolloff);
+                    self.jump_forward(&context.queue, &context.config);
 
                     context.render()?;
                 }
                 CommonAction::PreviousResult => {
-                    self.jump_back(&context.queue, context.config.scrolloff);
+                    self.jump_back(&context.queue, &context.config);
 
                     context.render()?;
                 }
@@ -773,6 +990,12 @@ impl Pane for QueuePane {
                 CommonAction::PaneUp => {}
                 CommonAction::PaneRight => {}
                 CommonAction::PaneLeft => {}
+                CommonAction::Rate(_)
+                | CommonAction::RateUp
+                | CommonAction::RateDown
+                | CommonAction::ToggleLike => {
+                    rate_songs(action, self.selected_songs(context)?, context);
+                }
             }
         } else if let Some(action) = event.as_global_action(context) {
             match action {
@@ -795,7 +1018,7 @@ impl Pane for QueuePane {
 }
 
 impl QueuePane {
-    pub fn jump_forward(&mut self, queue: &[Song], scrolloff: usize) {
+    pub fn jump_forward(&mut self, queue: &[Song], config: &Config) {
         let Some(filter) = self.filter.as_ref() else {
             status_warn!("No filter set");
             return;
@@ -808,14 +1031,18 @@ impl QueuePane {
         let length = queue.len();
         for i in selected + 1..length + selected {
             let i = i % length;
-            if queue[i].matches(self.column_formats.as_slice(), filter) {
-                self.scrolling_state.select(Some(i), scrolloff);
+            if queue[i].matches(
+                self.column_formats.as_slice(),
+                filter,
+                &config.theme.format_tag_separator,
+            ) {
+                self.scrolling_state.select(Some(i), config.scrolloff);
                 break;
             }
         }
     }
 
-    pub fn jump_back(&mut self, queue: &[Song], scrolloff: usize) {
+    pub fn jump_back(&mut self, queue: &[Song], config: &Config) {
         let Some(filter) = self.filter.as_ref() else {
             status_warn!("No filter set");
             return;
@@ -828,14 +1055,18 @@ impl QueuePane {
         let length = queue.len();
         for i in (0..length).rev() {
             let i = (i + selected) % length;
-            if queue[i].matches(self.column_formats.as_slice(), filter) {
-                self.scrolling_state.select(Some(i), scrolloff);
+            if queue[i].matches(
+                self.column_formats.as_slice(),
+                filter,
+                &config.theme.format_tag_separator,
+            ) {
+                self.scrolling_state.select(Some(i), config.scrolloff);
                 break;
             }
         }
     }
 
-    pub fn jump_first(&mut self, queue: &[Song], scrolloff: usize) {
+    pub fn jump_first(&mut self, queue: &[Song], config: &Config) {
         let Some(filter) = self.filter.as_ref() else {
             status_warn!("No filter set");
             return;
@@ -844,7 +1075,13 @@ impl QueuePane {
         queue
             .iter()
             .enumerate()
-            .find(|(_, item)| item.matches(self.column_formats.as_slice(), filter))
-            .inspect(|(idx, _)| self.scrolling_state.select(Some(*idx), scrolloff));
+            .find(|(_, item)| {
+                item.matches(
+                    self.column_formats.as_slice(),
+                    filter,
+                    &config.theme.format_tag_separator,
+                )
+            })
+            .inspect(|(idx, _)| self.scrolling_state.select(Some(*idx), config.scrolloff));
     }
 }
@@ -10,15 +10,22 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, Padding},
 };
 
-use super::{CommonAction, Pane};
+use super::{CommonAction, Pane, rate_songs};
 use crate::{
     MpdQueryResult,
-    config::{Config, Search, keys::GlobalAction, tabs::PaneType},
+    config::{
+        Config,
+        Search,
+        keys::GlobalAction,
+        smart_playlist::{SmartPlaylist, SmartPlaylistFilter, StickerCondition},
+        tabs::PaneType,
+    },
     context::AppContext,
     core::command::{create_env, run_external},
     mpd::{
+        command_list::CommandList,
         commands::Song,
-        mpd_client::{Filter, FilterKind, MpdClient, Tag},
+        mpd_client::{Filter, FilterKind, MpdClient, QueueMoveTarget, Tag},
     },
     shared::{
         ext::mpd_client::MpdClientExt,
@@ -76,6 +83,7 @@ impl SearchPane {
     }
 
     fn add_current(&mut self, autoplay: bool, context: &AppContext) -> Result<()> {
+        context.record_queue_edit();
         if !self.songs_dir.marked().is_empty() {
             for idx in self.songs_dir.marked() {
                 let item = self.songs_dir.items[*idx].file.clone();
@@ -106,6 +114,7 @@ impl SearchPane {
     }
 
     fn add_current_next(&mut self, autoplay: bool, context: &AppContext) -> Result<()> {
+        context.record_queue_edit();
         if !self.songs_dir.marked().is_empty() {
             for (relative_queue_index, idx) in self.songs_dir.marked().iter().enumerate() {
                 let item = self.songs_dir.items[*idx].file.clone();
@@ -239,9 +248,9 @@ impl SearchPane {
         let mut idx = 0;
         for input in &self.inputs.textbox_inputs {
             match input {
-                Textbox { value, label, filter_key } => {
+                Textbox { value, label, filter_key, sticker } => {
                     let is_focused = matches!(self.inputs.focused(),
-                        FocusedInputGroup::Textboxes(Textbox { filter_key: filter_key2, .. }) if filter_key == filter_key2);
+                        FocusedInputGroup::Textboxes(Textbox { filter_key: filter_key2, sticker: sticker2, .. }) if filter_key == filter_key2 && sticker == sticker2);
 
                     let mut widget = Input::default()
                         .set_borderless(true)
@@ -343,117 +352,107 @@ impl SearchPane {
         })
     }
 
-    fn search_add(&mut self, context: &AppContext) {
+    /// Tag filters and sticker conditions of the filled in inputs. Sticker
+    /// conditions cannot be expressed in MPD's filters and are applied to the
+    /// songs found by the tags afterwards, same as in smart playlists.
+    fn search_query(&self) -> Option<SmartPlaylist> {
         let (filter_kind, case_sensitive) = self.filter_type();
-        let filter = self.inputs.textbox_inputs.iter().filter_map(|input| match &input {
-            Textbox { value, filter_key, .. } if !value.is_empty() => {
-                Some((filter_key.to_owned(), value.to_owned(), filter_kind))
-            }
-            _ => None,
-        });
-
-        let mut filter = filter.collect_vec();
-
-        if filter.is_empty() {
-            return;
+        let (stickers, filters): (Vec<_>, Vec<_>) = self
+            .inputs
+            .textbox_inputs
+            .iter()
+            .filter(|input| !input.value.is_empty())
+            .partition(|input| input.sticker);
+
+        if filters.is_empty() && stickers.is_empty() {
+            return None;
         }
 
-        if case_sensitive {
-            context.command(move |client| {
-                client.find_add(
-                    &filter
-                        .iter_mut()
-                        .map(|(ref mut key, ref value, kind)| {
-                            Filter::new(std::mem::take(key), value).with_type(*kind)
-                        })
-                        .collect_vec(),
-                )?;
-                Ok(())
-            });
-        } else {
-            context.command(move |client| {
-                client.search_add(
-                    &filter
-                        .iter_mut()
-                        .map(|(ref mut key, ref value, kind)| {
-                            Filter::new(std::mem::take(key), value).with_type(*kind)
-                        })
-                        .collect_vec(),
-                )?;
-                Ok(())
-            });
-        }
+        Some(SmartPlaylist {
+            name: String::new(),
+            filters: filters
+                .into_iter()
+                .map(|input| SmartPlaylistFilter {
+                    tag: input.filter_key.clone(),
+                    value: input.value.clone(),
+                    kind: filter_kind,
+                })
+                .collect_vec(),
+            stickers: stickers
+                .into_iter()
+                .map(|input| StickerCondition::parse(&input.filter_key, &input.value))
+                .collect_vec(),
+            case_sensitive,
+            sort: None,
+            reverse: false,
+            limit: None,
+        })
     }
 
-    fn search_add_next(&mut self, context: &AppContext) {
-        let (filter_kind, case_sensitive) = self.filter_type();
-        let filter = self.inputs.textbox_inputs.iter().filter_map(|input| match &input {
-            Textbox { value, filter_key, .. } if !value.is_empty() => {
-                Some((filter_key.to_owned(), value.to_owned(), filter_kind))
+    fn search_add(&mut self, context: &AppContext) {
+        context.record_queue_edit();
+        let Some(query) = self.search_query() else {
+            return;
+        };
+
+        context.command(move |client| {
+            if query.stickers.is_empty() {
+                let filter = query.mpd_filters();
+                if query.case_sensitive {
+                    client.find_add(&filter)?;
+                } else {
+                    client.search_add(&filter)?;
+                }
+            } else {
+                client.load_smart_playlist(&query)?;
             }
-            _ => None,
+            Ok(())
         });
+    }
 
-        let mut filter = filter.collect_vec();
-
-        if filter.is_empty() {
+    fn search_add_next(&mut self, context: &AppContext) {
+        context.record_queue_edit();
+        let Some(query) = self.search_query() else {
             return;
-        }
-
-        if case_sensitive {
-            context.command(move |client| {
-                client.find_add_next(
-                    &filter
-                        .iter_mut()
-                        .map(|(ref mut key, ref value, kind)| {
-                            Filter::new(std::mem::take(key), value).with_type(*kind)
-                        })
-                        .collect_vec(),
-                )?;
-                Ok(())
-            });
-        } else {
-            context.command(move |client| {
-                client.search_add_next(
-                    &filter
-                        .iter_mut()
-                        .map(|(ref mut key, ref value, kind)| {
-                            Filter::new(std::mem::take(key), value).with_type(*kind)
-                        })
-                        .collect_vec(),
-                )?;
-                Ok(())
-            });
-        }
-    }
+        };
 
-    fn search(&mut self, context: &AppContext) {
-        let (filter_kind, case_sensitive) = self.filter_type();
-        let filter = self.inputs.textbox_inputs.iter().filter_map(|input| match &input {
-            Textbox { value, filter_key, .. } if !value.is_empty() => {
-                Some((filter_key.to_owned(), value.to_owned(), filter_kind))
+        context.command(move |client| {
+            if query.stickers.is_empty() {
+                let filter = query.mpd_filters();
+                if query.case_sensitive {
+                    client.find_add_next(&filter)?;
+                } else {
+                    client.search_add_next(&filter)?;
+                }
+            } else {
+                let songs = client.smart_playlist_songs(&query)?;
+                let mut list = CommandList::default();
+                for (idx, song) in songs.iter().enumerate() {
+                    list.add_to_position(&song.file, QueueMoveTarget::RelativeAdd(idx));
+                }
+                for result in client.batch::<()>(&list)? {
+                    result?;
+                }
             }
-            _ => None,
+            Ok(())
         });
+    }
 
-        let mut filter = filter.collect_vec();
-
-        if filter.is_empty() {
+    fn search(&mut self, context: &AppContext) {
+        let Some(query) = self.search_query() else {
             let _ = std::mem::take(&mut self.songs_dir);
             self.preview.take();
             return;
-        }
+        };
+
+        let fetch_stickers = context.should_fetch_stickers;
 
         context.query().id(SEARCH).replace_id(SEARCH).target(PaneType::Search).query(
             move |client| {
-                let filter = filter
-                    .iter_mut()
-                    .map(|(ref mut key, ref value, kind)| {
-                        Filter::new(std::mem::take(key), value).with_type(*kind)
-                    })
-                    .collect_vec();
-                let result =
-                    if case_sensitive { client.find(&filter) } else { client.search(&filter) }?;
+                let mut result = client.smart_playlist_songs(&query)?;
+                if fetch_stickers && !query.needs_stickers() {
+                    client.fetch_stickers(&mut result)?;
+                }
 
                 Ok(MpdQueryResult::SongsList { data: result, origin_path: None })
             },
@@ -772,6 +771,7 @@ impl Pane for SearchPane {
                             self.songs_dir.select_idx(idx, context.config.scrolloff);
                             if let Some(item) = self.songs_dir.selected() {
                                 let item = item.file.clone();
+                                context.record_queue_edit();
                                 context.command(move |client| {
                                     client.add(&item)?;
                                     status_info!("Added '{item}' to queue");
@@ -825,10 +825,20 @@ impl Pane for SearchPane {
         Ok(())
     }
 
+    fn selected_songs(&self, _context: &AppContext) -> Result<Vec<Song>> {
+        Ok(match self.phase {
+            Phase::SearchTextboxInput | Phase::Search => self.songs_dir.items.clone(),
+            Phase::BrowseResults { .. } if !self.songs_dir.marked().is_empty() => {
+                self.songs_dir.marked_items().cloned().collect()
+            }
+            Phase::BrowseResults { .. } => self.songs_dir.selected().cloned().into_iter().collect(),
+        })
+    }
+
     fn handle_action(&mut self, event: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
         let config = &context.config;
         match &mut self.phase {
-            Phase::SearchTextboxInput => match event.as_common_action(context) {
+            Phase::SearchTextboxInput => match event.as_input_common_action(context) {
                 Some(CommonAction::Close) => {
                     self.phase = Phase::Search;
                     self.search(context);
@@ -965,11 +975,15 @@ impl Pane for SearchPane {
                         CommonAction::PaneUp => {}
                         CommonAction::PaneRight => {}
                         CommonAction::PaneLeft => {}
+                        CommonAction::Rate(_) => {}
+                        CommonAction::RateUp => {}
+                        CommonAction::RateDown => {}
+                        CommonAction::ToggleLike => {}
                     }
                 }
             }
             Phase::BrowseResults { filter_input_on: filter_input_on @ true } => {
-                match event.as_common_action(context) {
+                match event.as_input_common_action(context) {
                     Some(CommonAction::Close) => {
                         *filter_input_on = false;
                         self.songs_dir.set_filter(None, config);
@@ -1138,6 +1152,12 @@ impl Pane for SearchPane {
                         CommonAction::PaneUp => {}
                         CommonAction::PaneRight => {}
                         CommonAction::PaneLeft => {}
+                        CommonAction::Rate(_)
+                        | CommonAction::RateUp
+                        | CommonAction::RateDown
+                        | CommonAction::ToggleLike => {
+                            rate_songs(action, self.selected_songs(context)?, context);
+                        }
                     }
                 }
             }
@@ -1177,10 +1197,13 @@ impl<const N2: usize, const N3: usize> InputGroups<N2, N3> {
             textbox_inputs: search_config
                 .tags
                 .iter()
-                .map(|tag| Textbox {
+                .map(|tag| (tag, false))
+                .chain(search_config.stickers.iter().map(|sticker| (sticker, true)))
+                .map(|(tag, sticker)| Textbox {
                     filter_key: tag.value.clone(),
                     label: format!(" {:<16}:", tag.label),
                     value: String::new(),
+                    sticker,
                 })
                 .collect_vec(),
             filter_inputs,
@@ -1318,6 +1341,8 @@ struct Textbox {
     value: String,
     label: String,
     filter_key: String,
+    /// `filter_key` is the name of a sticker and `value` a condition for it
+    sticker: bool,
 }
 
 #[derive(Debug)]
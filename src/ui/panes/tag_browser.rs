@@ -107,6 +107,7 @@ impl TagBrowserPane {
 
         match self.stack.path() {
             [_artist, _album] => {
+                context.record_queue_edit();
                 self.add(current, context)?;
                 let queue_len = context.queue.len();
                 if autoplay {
@@ -180,7 +181,7 @@ impl TagBrowserPane {
             .into_iter()
             .into_group_map_by(|song| {
                 let album = song.album().map_or("<no album>", |v| v.as_str());
-                let song_date = song.metadata.get("date").map_or("<no date>", |v| v.as_str());
+                let song_date = song.tag("date").map_or("<no date>", |v| v.as_str());
                 (album.to_string(), song_date.to_string(), song.album().cloned())
             })
             .into_iter()
@@ -281,6 +282,10 @@ impl Pane for TagBrowserPane {
         self.handle_mouse_action(event, context)
     }
 
+    fn selected_songs(&self, context: &AppContext) -> Result<Vec<Song>> {
+        self.marked_or_selected_songs(context)
+    }
+
     fn handle_action(&mut self, event: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
         self.handle_filter_input(event, context)?;
         self.handle_common_action(event, context)?;
@@ -724,6 +729,7 @@ mod tests {
     use super::*;
     use crate::{
         config::Config,
+        mpd::commands::MetadataTag,
         tests::fixtures::{app_context, config},
     };
 
@@ -736,10 +742,11 @@ mod tests {
             file: format!("{date:?} {album:?}"),
             duration: None,
             metadata: HashMap::from([
-                ("album".to_string(), album.into()),
-                ("date".to_string(), date.into()),
+                ("album".to_string(), MetadataTag::Single(album.into())),
+                ("date".to_string(), MetadataTag::Single(date.into())),
             ]),
             stickers: None,
+            priority: 0,
         }
     }
 
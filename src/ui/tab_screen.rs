@@ -6,7 +6,10 @@ use ratatui::{Frame, layout::Rect};
 
 use super::{Pane as _, PaneContainer, Panes, panes::pane_call};
 use crate::{
-    config::{keys::CommonAction, tabs::SizedPaneOrSplit},
+    config::{
+        keys::CommonAction,
+        tabs::{PaneType, SizedPaneOrSplit},
+    },
     context::AppContext,
     shared::{
         ext::{rect::RectExt, vec::VecExt},
@@ -50,6 +53,10 @@ impl TabScreen {
         Ok(Self { panes, focused, initialized: false, pane_data: HashMap::default() })
     }
 
+    pub(in crate::ui) fn focused_pane(&self) -> Option<&PaneType> {
+        self.panes.panes_iter().find(|pane| pane.id == self.focused).map(|pane| &pane.pane)
+    }
+
     fn set_focused(&mut self, id: Id) {
         self.focused = id;
         if let Some(data) = self.pane_data.get_mut(&id) {
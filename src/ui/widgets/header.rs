@@ -38,15 +38,21 @@ impl Widget for Header<'_> {
                 return;
             };
             let template = PropertyTemplates(&config.theme.header.rows[row].left);
-            let widget = template.format(song, &self.context.status).left_aligned();
+            let widget = template
+                .format(song, &self.context.status, &config.theme.format_tag_separator)
+                .left_aligned();
             widget.render(left, buf);
 
             let template = PropertyTemplates(&config.theme.header.rows[row].center);
-            let widget = template.format(song, &self.context.status).centered();
+            let widget = template
+                .format(song, &self.context.status, &config.theme.format_tag_separator)
+                .centered();
             widget.render(center, buf);
 
             let template = PropertyTemplates(&config.theme.header.rows[row].right);
-            let widget = template.format(song, &self.context.status).right_aligned();
+            let widget = template
+                .format(song, &self.context.status, &config.theme.format_tag_separator)
+                .right_aligned();
             widget.render(right, buf);
         }
     }
@@ -54,9 +60,14 @@ impl Widget for Header<'_> {
 
 struct PropertyTemplates<'a>(&'a [Property<PropertyKind>]);
 impl<'a> PropertyTemplates<'a> {
-    fn format(&'a self, song: Option<&'a Song>, status: &'a Status) -> Line<'a> {
+    fn format(
+        &'a self,
+        song: Option<&'a Song>,
+        status: &'a Status,
+        tag_separator: &str,
+    ) -> Line<'a> {
         Line::from(self.0.iter().fold(Vec::new(), |mut acc, val| {
-            match val.as_span(song, status) {
+            match val.as_span(song, status, tag_separator) {
                 Some(Either::Left(span)) => acc.push(span),
                 Some(Either::Right(ref mut spans)) => acc.append(spans),
                 None => {}